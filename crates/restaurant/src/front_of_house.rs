@@ -0,0 +1,17 @@
+//! The front of house: hosting guests and serving them
+//!     * the `hosting` module is declared here and lives in `front_of_house/hosting.rs`
+pub mod hosting;
+
+/// Serving is for the staff only, `pub(crate)` keeps it out of the crate's public API
+pub(crate) mod serving {
+    pub(crate) fn serve_order(order: &str) -> String {
+        take_order();
+        take_payment();
+        format!("serving {order}")
+    }
+
+    // - private functions, visible in `serving` and its children only
+    fn take_order() {}
+
+    fn take_payment() {}
+}
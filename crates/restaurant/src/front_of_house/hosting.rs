@@ -0,0 +1,14 @@
+//! Hosting: the waitlist of the guests waiting for a table
+use std::collections::VecDeque;
+
+/// The guests waiting for a table, first come first seated
+pub type Waitlist = VecDeque<String>;
+
+pub fn add_to_waitlist(waitlist: &mut Waitlist, guest: &str) {
+    waitlist.push_back(String::from(guest));
+}
+
+/// The guest who has waited the longest, if any
+pub fn seat_at_table(waitlist: &mut Waitlist) -> Option<String> {
+    waitlist.pop_front()
+}
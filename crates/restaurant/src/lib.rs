@@ -0,0 +1,102 @@
+//! # restaurant
+//!
+//! The library crate of the book's chapter 7, its modules split into files as in 7.5
+//!     * `front_of_house` lives in `src/front_of_house.rs`, its `hosting` child in `src/front_of_house/hosting.rs`
+//!     * `front_of_house` is private, the outside reaches `hosting` through the `pub use` below
+mod front_of_house;
+
+// - a re-export: `restaurant::hosting` is public although `restaurant::front_of_house::hosting` is not reachable
+pub use crate::front_of_house::hosting;
+
+/// The kitchen: a struct with private fields and an enum whose variants are all public
+pub mod back_of_house {
+    /// A breakfast whose toast the customer picks, and whose fruit the chef picks
+    #[derive(Debug)]
+    pub struct Breakfast {
+        pub toast: String,
+        // - private, so `Breakfast` cannot be built outside this module without `summer`
+        seasonal_fruit: String,
+    }
+
+    impl Breakfast {
+        pub fn summer(toast: &str) -> Breakfast {
+            Breakfast {
+                toast: String::from(toast),
+                seasonal_fruit: String::from("peaches"),
+            }
+        }
+
+        pub fn seasonal_fruit(&self) -> &str {
+            &self.seasonal_fruit
+        }
+    }
+
+    /// The variants of a public enum are public
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Appetizer {
+        Soup,
+        Salad,
+    }
+
+    /// Cook the order again and hand it back to the front of house
+    pub fn fix_incorrect_order(order: &str) -> String {
+        let order = cook_order(order);
+        // - `super` starts from the parent module, here the crate root
+        super::deliver_order(&order)
+    }
+
+    fn cook_order(order: &str) -> String {
+        format!("{order}, cooked again")
+    }
+}
+
+fn deliver_order(order: &str) -> String {
+    // - a `pub(crate)` item, visible anywhere in this crate but not to its users
+    front_of_house::serving::serve_order(order)
+}
+
+/// Seat a guest and order breakfast, going through the modules by absolute and relative paths
+pub fn eat_at_restaurant(waitlist: &mut hosting::Waitlist, guest: &str) -> String {
+    // - absolute path, from the crate root
+    crate::front_of_house::hosting::add_to_waitlist(waitlist, guest);
+    // - relative path, from the current module, here also the crate root
+    let seated = front_of_house::hosting::seat_at_table(waitlist).unwrap_or_default();
+    // - through the `use`d re-export
+    hosting::add_to_waitlist(waitlist, "the next guest");
+
+    let mut meal = back_of_house::Breakfast::summer("Rye");
+    meal.toast = String::from("Wheat");
+    // - does not compile, `seasonal_fruit` is private (E0616)
+    // meal.seasonal_fruit = String::from("blueberries");
+    let appetizer = back_of_house::Appetizer::Salad;
+    format!(
+        "{seated} would like {} toast please, with {} and a {appetizer:?}",
+        meal.toast,
+        meal.seasonal_fruit()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seats_the_first_guest_and_orders() {
+        let mut waitlist = hosting::Waitlist::default();
+        assert_eq!(
+            eat_at_restaurant(&mut waitlist, "Ferris"),
+            "Ferris would like Wheat toast please, with peaches and a Salad"
+        );
+        assert_eq!(waitlist.len(), 1);
+    }
+
+    #[test]
+    fn private_items_are_visible_to_child_modules() {
+        // - `tests` is a child of the crate root, so it may use the private `front_of_house`
+        assert_eq!(front_of_house::serving::serve_order("tea"), "serving tea");
+        assert_eq!(
+            back_of_house::fix_incorrect_order("eggs"),
+            "serving eggs, cooked again"
+        );
+    }
+}
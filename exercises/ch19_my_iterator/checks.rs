@@ -0,0 +1,21 @@
+use ch19_my_iterator::{Counter, MyIterator};
+
+#[test]
+fn counts_from_one_to_five() {
+    let mut counter = Counter::new();
+    let mut items: Vec<u32> = Vec::new();
+    while let Some(item) = counter.next() {
+        items.push(item);
+    }
+    assert_eq!(items, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn stays_exhausted() {
+    let mut counter = Counter::new();
+    for _ in 0..5 {
+        counter.next();
+    }
+    assert_eq!(counter.next(), None);
+    assert_eq!(counter.next(), None);
+}
@@ -0,0 +1,33 @@
+//! # Ch19.2 - Associated types: `impl MyIterator for Counter`
+//!     * `MyIterator` mirrors `Iterator`, with an associated type `Item` rather than a generic parameter
+//!     * implement it for `Counter` so that it yields `1, 2, 3, 4, 5` and then `None` forever
+
+pub trait MyIterator {
+    type Item;
+
+    fn next(&mut self) -> Option<Self::Item>;
+}
+
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl MyIterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        todo!("count from 1 up to 5, then return `None`")
+    }
+}
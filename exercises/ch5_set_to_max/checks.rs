@@ -0,0 +1,39 @@
+use ch5_set_to_max::Rectangle;
+
+#[test]
+fn grows_to_a_larger_rectangle() {
+    let mut rect = Rectangle {
+        width: 30,
+        height: 50,
+    };
+    rect.set_to_max(Rectangle {
+        width: 40,
+        height: 60,
+    });
+    assert_eq!(
+        rect,
+        Rectangle {
+            width: 40,
+            height: 60
+        }
+    );
+}
+
+#[test]
+fn keeps_the_larger_dimensions_of_each() {
+    let mut rect = Rectangle {
+        width: 30,
+        height: 50,
+    };
+    rect.set_to_max(Rectangle {
+        width: 10,
+        height: 70,
+    });
+    assert_eq!(
+        rect,
+        Rectangle {
+            width: 30,
+            height: 70
+        }
+    );
+}
@@ -0,0 +1,28 @@
+//! # Ch5.2 - `set_to_max` without moving `*self`
+//!     * `std::mem::replace` moves `*self` out while leaving a placeholder behind, so `self` is never left empty
+
+#[derive(Debug, PartialEq)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn max(self, other: Rectangle) -> Rectangle {
+        Rectangle {
+            width: self.width.max(other.width),
+            height: self.height.max(other.height),
+        }
+    }
+
+    pub fn set_to_max(&mut self, other: Rectangle) {
+        let current = std::mem::replace(
+            self,
+            Rectangle {
+                width: 0,
+                height: 0,
+            },
+        );
+        *self = current.max(other);
+    }
+}
@@ -0,0 +1,24 @@
+//! # Ch5.2 - `set_to_max` without moving `*self`
+//!     * `max` takes ownership of both rectangles, and `Rectangle` is neither `Copy` nor `Clone`
+//!     * so `*self = self.max(other);` is rejected: it would move `*self` out of a `&mut` borrow
+//!     * implement `set_to_max` so that `self` becomes the larger of the two, in each dimension
+//!         * without deriving `Copy` or `Clone` for `Rectangle`
+
+#[derive(Debug, PartialEq)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn max(self, other: Rectangle) -> Rectangle {
+        Rectangle {
+            width: self.width.max(other.width),
+            height: self.height.max(other.height),
+        }
+    }
+
+    pub fn set_to_max(&mut self, other: Rectangle) {
+        todo!("make `self` the max of `self` and `other`, e.g. with `std::mem::replace`")
+    }
+}
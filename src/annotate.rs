@@ -0,0 +1,213 @@
+//! # Output annotated with the source lines that printed it
+//!     * `outln!` records the `file!()` and `line!()` of every line a lesson prints
+//!     * `run --annotate` prefixes each output line with that location, e.g. `src/ch9/results.rs:27 | ...`
+//!     * `run --source` prints the note's source lines before the output they produce, so code and output read side by side
+use crate::lesson::{self, Lesson, Location};
+use std::io::{self, Write};
+
+/// How `Annotated` decorates a lesson's output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Annotation {
+    /// Prefix every line with `file:line`
+    Locations,
+    /// Quote the source lines above the output they produce
+    Source,
+}
+
+/// At most this many source lines are quoted since the previous output, more are cut to the last few
+const MAX_QUOTED_LINES: u32 = 6;
+
+/// A `Write` wrapper that decorates each line with the location recorded by `outln!`
+pub struct Annotated<'a> {
+    out: &'a mut dyn Write,
+    annotation: Annotation,
+    /// `ch9/results.rs` for `ch9::results`, to tell the lesson's own lines from others
+    file: String,
+    source: Vec<&'static str>,
+    at_line_start: bool,
+    /// Location of the last quoted statement, and the last source line quoted for it
+    last: Option<(Location, u32)>,
+}
+
+impl<'a> Annotated<'a> {
+    pub fn new(
+        out: &'a mut dyn Write,
+        annotation: Annotation,
+        lesson: &dyn Lesson,
+    ) -> Annotated<'a> {
+        // - a location left over from a previous lesson must not be reported for this one
+        lesson::set_location(None);
+        Annotated {
+            out,
+            annotation,
+            file: format!("{}.rs", lesson.id().replace("::", "/")),
+            source: lesson.source().lines().collect(),
+            at_line_start: true,
+            last: None,
+        }
+    }
+
+    fn is_lesson_file(&self, location: &Location) -> bool {
+        location.file.replace('\\', "/").ends_with(&self.file)
+    }
+
+    /// Lines of the statement at `location`, starting after the previously quoted ones when close enough
+    fn quoted_lines(&self, location: &Location) -> std::ops::RangeInclusive<u32> {
+        let first = match self.last {
+            Some((last, quoted_to)) if last.file == location.file && quoted_to < location.line => {
+                (quoted_to + 1).max(location.line.saturating_sub(MAX_QUOTED_LINES - 1))
+            }
+            _ => location.line.saturating_sub(2).max(1),
+        };
+        // - a statement spread over several lines, e.g. a long `outln!`, is quoted up to its closing parenthesis
+        let mut end = location.line;
+        let mut depth = 0;
+        while let Some(line) = self.source.get(end as usize - 1) {
+            depth += line.matches('(').count() as i32 - line.matches(')').count() as i32;
+            if depth <= 0 || end >= location.line + MAX_QUOTED_LINES {
+                break;
+            }
+            end += 1;
+        }
+        first..=end
+    }
+
+    fn write_prefix(&mut self) -> io::Result<()> {
+        let location = lesson::location();
+        match self.annotation {
+            Annotation::Locations => match location {
+                Some(location) => write!(self.out, "{location:<36} | "),
+                None => write!(self.out, "{:<36} | ", "?"),
+            },
+            Annotation::Source => {
+                if let Some(location) = location.filter(|l| self.is_lesson_file(l)) {
+                    if self.last.map(|(last, _)| last) != Some(location) {
+                        let lines = self.quoted_lines(&location);
+                        for n in lines.clone() {
+                            let line = self.source.get(n as usize - 1).copied().unwrap_or("");
+                            writeln!(self.out, "{n:>5} | {line}")?;
+                        }
+                        self.last = Some((location, *lines.end()));
+                    }
+                }
+                write!(self.out, "      > ")
+            }
+        }
+    }
+}
+
+impl Write for Annotated<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for line in buf.split_inclusive(|&b| b == b'\n') {
+            if self.at_line_start {
+                self.write_prefix()?;
+            }
+            self.out.write_all(line)?;
+            self.at_line_start = line.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::{SharedOutput, TestLesson};
+
+    /// A lesson whose source is this file, so that the `outln!` lines of `run` can be quoted
+    fn lesson() -> TestLesson {
+        TestLesson {
+            source: include_str!("annotate.rs"),
+            run,
+            ..TestLesson::new("annotate")
+        }
+    }
+
+    fn run(out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "one")?;
+        let two = 2;
+        outln!(out, "{two}\nthree")
+    }
+
+    fn annotated(annotation: Annotation) -> String {
+        let mut buf = Vec::new();
+        let lesson = lesson();
+        lesson
+            .run(&mut Annotated::new(&mut buf, annotation, &lesson))
+            .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Line of the first `outln!` in `run`
+    fn first_outln() -> u32 {
+        let source = include_str!("annotate.rs");
+        let index = source.find("outln!(out, \"one\")").unwrap();
+        source[..index].lines().count() as u32
+    }
+
+    #[test]
+    fn prefixes_lines_with_their_location() {
+        let n = first_outln();
+        let file = file!();
+        assert_eq!(
+            annotated(Annotation::Locations),
+            format!(
+                "{:<36} | one\n{:<36} | 2\n{:<36} | three\n",
+                format!("{file}:{n}"),
+                format!("{file}:{}", n + 2),
+                format!("{file}:{}", n + 2)
+            )
+        );
+    }
+
+    #[test]
+    fn quotes_the_source_above_its_output() {
+        let n = first_outln();
+        assert_eq!(
+            annotated(Annotation::Source),
+            format!(
+                "{:>5} | \n\
+                 {:>5} |     fn run(out: &mut dyn Write) -> io::Result<()> {{\n\
+                 {:>5} |         outln!(out, \"one\")?;\n      > one\n\
+                 {:>5} |         let two = 2;\n\
+                 {:>5} |         outln!(out, \"{{two}}\\nthree\")\n      > 2\n      > three\n",
+                n - 2,
+                n - 1,
+                n,
+                n + 1,
+                n + 2
+            )
+        );
+    }
+
+    #[test]
+    fn thread_output_keeps_its_location() {
+        let mut buf = Vec::new();
+        let lesson = lesson();
+        let mut annotated = Annotated::new(&mut buf, Annotation::Locations, &lesson);
+        let mut shared = SharedOutput::new(&mut annotated);
+        let mut writer = shared.writer();
+        let thread_line = line!() + 1;
+        std::thread::spawn(move || outln!(writer, "from thread"))
+            .join()
+            .unwrap()
+            .unwrap();
+        let main_line = line!() + 1;
+        outln!(shared, "from main").unwrap();
+        shared.flush().unwrap();
+
+        let file = file!();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            format!(
+                "{:<36} | from thread\n{:<36} | from main\n",
+                format!("{file}:{thread_line}"),
+                format!("{file}:{main_line}")
+            )
+        );
+    }
+}
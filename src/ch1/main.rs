@@ -1,10 +1,41 @@
+use std::io::{self, Write};
+
 #[derive(Debug)]
 pub struct Main {
 }
 
 impl Main {
-    pub fn print(&self) {
-        println!("\n======The note of ch1======");
-        println!("Hello World!");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of ch1======")?;
+        outln!(out, "Hello World!")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Main {
+    fn id(&self) -> &'static str {
+        "ch1::main"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(1, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Hello, World!"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["basics"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("main.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
-}    
\ No newline at end of file
+}
@@ -1 +1,10 @@
 pub mod main;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(main::Main {}),
+    ]
+}
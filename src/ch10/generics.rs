@@ -1,14 +1,15 @@
 /// # Ch10.1 - Generic Data Types
 
 use std::cmp::PartialOrd;
+use std::io::{self, Write};
 
 #[derive(Debug)]
 pub struct Generics{
 }
 
 impl Generics{
-    pub fn print(&self) {
-        println!("\n======The note on generic data types======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on generic data types======")?;
     // In Function Definitions
         // - use generics where we specifying types of parameters and return value
         // - we need to restrict generic types, discussed in ch10.2
@@ -26,11 +27,11 @@ impl Generics{
         }
         let number_list = vec![34, 50, 25, 100, 65];
         let result = largest(&number_list);
-        println!("\nThe largest number is {}", result);
+        outln!(out, "\nThe largest number is {}", result)?;
 
         let char_list = vec!['y', 'm', 'a', 'q'];
         let result = largest(&char_list);
-        println!("The largest char is {}", result);
+        outln!(out, "The largest char is {}", result)?;
 
     // In Struct Definitions
         #[derive(Debug)]
@@ -40,9 +41,9 @@ impl Generics{
         }
         
         let integer = Point { x: 5, y: 10 };
-        println!("\ninteger is {:?}", integer);
+        outln!(out, "\ninteger is {:?}", integer)?;
         let float = Point { x: 1.0, y: 4.0 };
-        println!("float is {:?}", float);
+        outln!(out, "float is {:?}", float)?;
     
     // In Enum Definitions
         // - Option
@@ -66,8 +67,8 @@ impl Generics{
                 &self.x
             }
         }
-        println!("\ninteger.x() is {}", integer.x());
-        println!("float.x() is {}", float.x());
+        outln!(out, "\ninteger.x() is {}", integer.x())?;
+        outln!(out, "float.x() is {}", float.x())?;
         // - we could implement a method to a specific concrete type only, e.g., f32
             // - we do declare generic type after `impl`
         impl Point<f32> {
@@ -75,7 +76,7 @@ impl Generics{
                 (self.x.powi(2) + self.y.powi(2)).sqrt()
             }
         }
-        println!("float.distance_from_origin() is {}", float.distance_from_origin());
+        outln!(out, "float.distance_from_origin() is {}", float.distance_from_origin())?;
         // - in struct definition, we may need to use different generic types for fields 
         struct Point2<X1, Y1> {
             x: X1,
@@ -94,7 +95,39 @@ impl Generics{
         let p1 = Point2 { x: 5, y: 10.4 };
         let p2 = Point2 { x: "Hello", y: 'c' };
         let p3 = p1.mixup(p2);
-        println!("\np3.x = {}, p3.y = {}", p3.x, p3.y);
+        outln!(out, "\np3.x = {}, p3.y = {}", p3.x, p3.y)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Generics {
+    fn id(&self) -> &'static str {
+        "ch10::generics"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(10, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Generic Data Types"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["generics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch5::method", "ch6::define"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("generics.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
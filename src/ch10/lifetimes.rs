@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch10.3 - Validating References with Lifetimes
 /// * main aim of lifetimes is to prevent dangling references
 /// * Rust borrow checker will compare scopes to determine whether all borrows are valid
@@ -7,8 +9,8 @@ pub struct Lifetimes{
 }
 
 impl Lifetimes{
-    pub fn print(&self) {
-        println!("\n======The note on lifetimes======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on lifetimes======")?;
     // Generic Lifetimes in Functions
         // - e.g., Rust can not tell the returned reference refers to x or y, we don't either
         // - we don't know concrete lifetimes of the references that will be passed in
@@ -41,7 +43,7 @@ impl Lifetimes{
             {
                 let string2 = String::from("xyz");
                 let result = longest(string1.as_str(), string2.as_str());
-                println!("The longest string is {}", result);
+                outln!(out, "The longest string is {}", result)?;
             }
                 // - below does not work
                     // - because for `result` to be valid in the `println!` statement, `string2` would need to live untill after the `println1` statement
@@ -69,15 +71,15 @@ impl Lifetimes{
             part: &'a str,
         }
         
-        fn main() {
+        fn main(out: &mut dyn Write) -> io::Result<()> {
             let novel = String::from("Call me Ishmael. Some years ago...");
             let first_sentence = novel.split('.').next().expect("Could not find a '.'");
             let i = ImportantExcerpt {
                 part: first_sentence,
             };
-            println!("\ni is: {:?}", i);
+            outln!(out, "\ni is: {:?}", i)
         }
-        main();
+        main(out)?;
     // Three Elision Rules 
         // - compiler use to figure out lifetimes of ref when no explicit annotations
         // - first rule is compiler assigns a different lifetime parameter to each lifetime in each input type
@@ -102,9 +104,9 @@ impl Lifetimes{
             fn level(&self) -> i32 {3}
             // - based on third elision rule, both `&self` and `announcement` are given own lifetimes   
                 // - and lifetime of `&self` is given to output `&str`
-            fn announce_and_return_part(&self, announcement: &str) -> &str {
-                println!("Attention please: {}", announcement);
-                self.part
+            fn announce_and_return_part(&self, announcement: &str, out: &mut dyn Write) -> io::Result<&str> {
+                outln!(out, "Attention please: {}", announcement)?;
+                Ok(self.part)
             }
         }
     
@@ -113,8 +115,38 @@ impl Lifetimes{
         // - all string literals have such lifetime, i.e., we can annotate as follows
             // - bacause text of string is stored directly in program's binary which is alway available
         let s: &'static str = "I have a static lifetime.";
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Lifetimes {
+    fn id(&self) -> &'static str {
+        "ch10::lifetimes"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(10, 3)
+    }
 
+    fn title(&self) -> &'static str {
+        "Validating References with Lifetimes"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["lifetimes", "borrowing", "generics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch10::traits", "ch4::slice_type"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("lifetimes.rs")
+    }
 
-            
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
-}
\ No newline at end of file
+}
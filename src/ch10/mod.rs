@@ -1,3 +1,15 @@
+#![allow(dead_code, unused_mut, unused_variables, clippy::empty_line_after_doc_comments)]
 pub mod generics;
 pub mod traits;
-pub mod lifetimes;
\ No newline at end of file
+pub mod lifetimes;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(generics::Generics {}),
+        Box::new(traits::Traits {}),
+        Box::new(lifetimes::Lifetimes {}),
+    ]
+}
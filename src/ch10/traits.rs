@@ -5,14 +5,15 @@
 /// * peers in other language - interfaces
 
 use std::fmt::{Debug, Display};
+use std::io::{self, Write};
 #[allow(unused_mut)]
 #[derive(Debug)]
 pub struct Traits{
 }
 
 impl Traits{
-    pub fn print(&self) {
-        println!("\n======The note on traits======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on traits======")?;
     // Define a Trait
         // - abstract, i.e., just function signature, not function body
             // - we can define default implementation
@@ -68,7 +69,7 @@ impl Traits{
             reply: false,
             retweet: false,
         };
-        println!("1 new tweet: {}", tweet.summarize());
+        outln!(out, "1 new tweet: {}", tweet.summarize())?;
 
         // - `NewsArticle`
         let article = NewsArticle {
@@ -77,14 +78,14 @@ impl Traits{
             author: String::from("Iceburgh"),
             content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
         };
-        println!("New article available! {}", article.summarize());
+        outln!(out, "New article available! {}", article.summarize())?;
 
         // - `Page`
         let page = Page{
             username: String::from("netizen"),
             content: String::from("The page you requested is not found")
         };
-        println!("New page available! {}", page.summarize());
+        outln!(out, "New page available! {}", page.summarize())?;
     
     // Default implementation can call other methods in the trait
         // - even a method that has no default implementation
@@ -101,39 +102,39 @@ impl Traits{
                 format!("@{}", self.username)
             }
         }
-        println!("\n1 new tweet: {}", tweet.summarize2());
+        outln!(out, "\n1 new tweet: {}", tweet.summarize2())?;
     
     // Trait as Parameter
         // - use traits to define functions that accept many different types
             // - e.g., `notify` accepts any time that implements `Summary`
             // - within body of `notify` we can use any methods that implemented in `Summary` 
-        fn notify(item: &impl Summary) {
-            println!("Breaking news! {}", item.summarize());
+        fn notify(item: &impl Summary, out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "Breaking news! {}", item.summarize())
         }
-        println!("");
-        notify(&page);
-        notify(&tweet);
-        notify(&article);
+        outln!(out)?;
+        notify(&page, out)?;
+        notify(&tweet, out)?;
+        notify(&article, out)?;
         
         // - Trait Bound Syntax
             // - above `notify` uses a syntax sugar for `trait bound`
             // - e.g., `notify` could be defined as
-        fn notify2<T: Summary>(item: &T) {
-            println!("Breaking news! {}", item.summarize());
+        fn notify2<T: Summary>(item: &T, out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "Breaking news! {}", item.summarize())
         }
-        println!("");
-        notify2(&page);
-        notify2(&tweet);
-        notify2(&article);
+        outln!(out)?;
+        notify2(&page, out)?;
+        notify2(&tweet, out)?;
+        notify2(&article, out)?;
         
             // - specifying multiple traits bouds with + syntax
                 // - e.g., `notify3` accepts those types that implement `Summary` and `Debug`
                 // - only `Tweet` implements both
-        fn notify3<T: Summary + Debug>(item: &T) {
-            println!("\nBreaking news! {}", item.summarize());
+        fn notify3<T: Summary + Debug>(item: &T, out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "\nBreaking news! {}", item.summarize())
         }
-        println!("");
-        notify3(&tweet);
+        outln!(out)?;
+        notify3(&tweet, out)?;
                 // - will not compile if we call `notify3` on page or article
         // notify3(&page);
         // notify3(&article);
@@ -203,11 +204,11 @@ impl Traits{
 
         // - implement .cmp_display method only for types that implement Display and PartialOrd
         impl<T: Display + PartialOrd> Pair<T> {
-            fn cmp_display(&self) {
+            fn cmp_display(&self, out: &mut dyn Write) -> io::Result<()> {
                 if self.x >= self.y {
-                    println!("The largest member is x = {}", self.x);
+                    outln!(out, "The largest member is x = {}", self.x)
                 } else {
-                    println!("The largest member is y = {}", self.y);
+                    outln!(out, "The largest member is y = {}", self.y)
                 }
             }
         }
@@ -219,7 +220,39 @@ impl Traits{
         let s = String::from("hello");
         let mut s2 = displayable(s);
         // s2.push_str(" world");
-        println!("{s2}");
+        outln!(out, "{s2}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Traits {
+    fn id(&self) -> &'static str {
+        "ch10::traits"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(10, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Traits: Defining Shared Behavior"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["traits", "generics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch10::generics"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("traits.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch11.2 - Controlling How Tests Are Run
 /// * control number of threads
 ///     * `cargo test -- --test-threads=1`
@@ -17,8 +19,41 @@
 pub struct ControllingTests {}
 
 impl ControllingTests {
-    pub fn print(&self) {
-        println!("\n======The note on controlling tests======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on controlling tests======")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for ControllingTests {
+    fn id(&self) -> &'static str {
+        "ch11::controlling_tests"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(11, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Controlling How Tests Are Run"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["testing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch11::writing_tests"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("controlling_tests.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
 
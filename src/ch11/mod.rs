@@ -1,3 +1,15 @@
+#![allow(clippy::assertions_on_constants, clippy::let_unit_value, clippy::manual_range_contains)]
 pub mod controlling_tests;
 pub mod organizing_tests;
 pub mod writing_tests;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(writing_tests::WritingTests {}),
+        Box::new(controlling_tests::ControllingTests {}),
+        Box::new(organizing_tests::OrganizingTests {}),
+    ]
+}
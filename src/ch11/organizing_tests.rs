@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// Ch11.3 - Organizing Tests
 /// * Rust community thinks about test in terms of two main categories
 ///     * unit tests - small, more focued, testin one module in isolation at a time, can test private function
@@ -26,7 +28,40 @@
 pub struct OrganizingTests {}
 
 impl OrganizingTests {
-    pub fn print(&self) {
-        println!("\n======The note on organizing tests======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on organizing tests======")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for OrganizingTests {
+    fn id(&self) -> &'static str {
+        "ch11::organizing_tests"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(11, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Test Organization"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["testing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch11::controlling_tests"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("organizing_tests.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -1,11 +1,14 @@
+use std::io::{self, Write};
+
 /// # Ch11.1 - How to Writing Tests
 #[derive(Debug)]
 pub struct WritingTests {}
 
 #[allow(dead_code, unused_variables)]
 impl WritingTests {
-    pub fn print(&self) {
-        println!("\n======The note on writing tests======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on writing tests======")?;
+        Ok(())
     }
 
     pub fn add_two(a: i32) -> i32 {
@@ -28,6 +31,38 @@ impl WritingTests {
     }
 }
 
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for WritingTests {
+    fn id(&self) -> &'static str {
+        "ch11::writing_tests"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(11, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "How to Write Tests"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["testing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch9::results"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("writing_tests.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // use crate::ch11::writing_tests::WritingTests;
@@ -0,0 +1,417 @@
+//! # Ch12 - An I/O Project: Building a Command Line Program
+//!     * `minigrep` searches files for lines containing a query, a small version of `grep`
+//!     * Separation of Concerns for Binary Projects
+//!         * `main` only collects the arguments, calls `Config::build` and `run`, and reports errors
+//!         * the logic lives in a library, here `search`, `search_case_insensitive` and `run`, so it can be tested
+//!     * Beyond the book
+//!         * several files, with each line prefixed by its file name, and stdin when there are none or for `-`
+//!         * `*` matches any text and `?` any single character in the query, there is no regex
+//!         * `-n` numbers the lines, `-C <n>` prints `n` lines of context around each match, groups split by `--`
+//!         * `-i` or the `IGNORE_CASE` environment variable ignore case
+//!     * run it with `cargo run -- minigrep -n to src/ch12/poem.txt`
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug)]
+pub struct MiniGrep {}
+
+/// What to search for, and where
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    pub query: String,
+    /// Paths to search, `-` for stdin, which is also searched when there are none
+    pub files: Vec<String>,
+    pub ignore_case: bool,
+    pub line_numbers: bool,
+    /// Lines printed before and after each match
+    pub context: usize,
+}
+
+impl Config {
+    // Extracting the Argument Parser
+    // - the arguments, without the program name, e.g. `env::args().skip(1)`
+    // - returns `Err` rather than panicking, `main` tells a user error from a bug
+    // - `IGNORE_CASE` only needs to be set, to any value, as in the book
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Config, String> {
+        Config::parse(args, env::var("IGNORE_CASE").is_ok())
+    }
+
+    /// `build` without reading the environment, `ignore_case` being the default
+    pub fn parse(
+        mut args: impl Iterator<Item = String>,
+        ignore_case: bool,
+    ) -> Result<Config, String> {
+        let mut config = Config {
+            query: String::new(),
+            files: Vec::new(),
+            ignore_case,
+            line_numbers: false,
+            context: 0,
+        };
+        let mut query = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-i" => config.ignore_case = true,
+                "-n" => config.line_numbers = true,
+                "-C" => {
+                    let n = args.next().ok_or("`-C` needs a number of lines")?;
+                    config.context = n
+                        .parse()
+                        .map_err(|_| format!("`-C` needs a number of lines, got `{n}`"))?;
+                }
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return Err(format!("Unknown flag `{flag}`"))
+                }
+                _ if query.is_none() => query = Some(arg),
+                _ => config.files.push(arg),
+            }
+        }
+        config.query = query.ok_or("Didn't get a query string")?;
+        Ok(config)
+    }
+}
+
+/// A query where `*` stands for any text and `?` for any character, found anywhere in a line
+#[derive(Debug)]
+struct Pattern {
+    chars: Vec<char>,
+    ignore_case: bool,
+}
+
+impl Pattern {
+    fn new(query: &str, ignore_case: bool) -> Pattern {
+        let query = if ignore_case {
+            query.to_lowercase()
+        } else {
+            query.to_string()
+        };
+        // - a match anywhere in the line is a match of the whole line with `*` around the query
+        let chars = std::iter::once('*')
+            .chain(query.chars())
+            .chain(std::iter::once('*'))
+            .collect();
+        Pattern { chars, ignore_case }
+    }
+
+    fn is_match(&self, line: &str) -> bool {
+        // - without wildcards this is the book's `line.contains(query)`
+        let line: Vec<char> = if self.ignore_case {
+            line.to_lowercase().chars().collect()
+        } else {
+            line.chars().collect()
+        };
+        matches_whole(&self.chars, &line)
+    }
+}
+
+/// Whether `pattern` matches all of `text`
+///     * on a mismatch, only the last `*` seen takes one more character, and the pattern resumes after it
+///         * an earlier `*` never needs to take more, the last one can absorb the same characters
+///         * so each step moves forward in `text` or in `pattern` from the last `*`, at most pattern × text steps
+fn matches_whole(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // - where the pattern resumes after the last `*`, and where the text resumes when that `*` takes one more character
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                star = Some((p, t));
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((after_star, taken)) => {
+                    p = after_star;
+                    t = taken + 1;
+                    star = Some((after_star, t));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// Developing the Library's Functionality with Test-Driven Development
+// - the lifetime `'a` says that the returned lines borrow from `contents`, not from `query`
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let pattern = Pattern::new(query, false);
+    contents
+        .lines()
+        .filter(|line| pattern.is_match(line))
+        .collect()
+}
+
+// Working with Environment Variables
+// - `to_lowercase` on both the query and each line, `query` becomes a `String` rather than a `&str`
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let pattern = Pattern::new(query, true);
+    contents
+        .lines()
+        .filter(|line| pattern.is_match(line))
+        .collect()
+}
+
+/// Print the lines of `contents` that match, with their context, as `grep` does
+///     * `name` prefixes every line when given, followed by `:` for a match and `-` for context, as is the line number
+///     * returns the number of matching lines
+pub fn write_matches(
+    config: &Config,
+    name: Option<&str>,
+    contents: &str,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    let pattern = Pattern::new(&config.query, config.ignore_case);
+    let lines: Vec<&str> = contents.lines().collect();
+    let matched: Vec<usize> = (0..lines.len())
+        .filter(|&i| pattern.is_match(lines[i]))
+        .collect();
+
+    let mut printed_to = None;
+    for &i in &matched {
+        let first = i.saturating_sub(config.context);
+        let last = (i + config.context).min(lines.len() - 1);
+        let first = match printed_to {
+            Some(to) if to + 1 >= first => to + 1,
+            Some(_) if config.context > 0 => {
+                writeln!(out, "--")?;
+                first
+            }
+            _ => first,
+        };
+        for (n, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+            let separator = if matched.binary_search(&n).is_ok() {
+                ':'
+            } else {
+                '-'
+            };
+            if let Some(name) = name {
+                write!(out, "{name}{separator}")?;
+            }
+            if config.line_numbers {
+                write!(out, "{}{separator}", n + 1)?;
+            }
+            writeln!(out, "{line}")?;
+        }
+        printed_to = Some(last.max(printed_to.unwrap_or(0)));
+    }
+    Ok(matched.len())
+}
+
+// Extracting Logic from `main`
+// - returns whether any line matched, `main` turns that into the exit code
+// - errors bubble up with `?`, a missing file being reported with its path
+pub fn run(config: &Config, stdin: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<bool> {
+    let stdin_only = [String::from("-")];
+    let files = if config.files.is_empty() {
+        &stdin_only[..]
+    } else {
+        &config.files[..]
+    };
+    let mut found = 0;
+    for file in files {
+        let contents = if file == "-" {
+            let mut contents = String::new();
+            stdin.read_to_string(&mut contents)?;
+            contents
+        } else {
+            fs::read_to_string(file)
+                .map_err(|e| io::Error::new(e.kind(), format!("{file}: {e}")))?
+        };
+        // - a name only tells the files apart when there are several
+        let name = (files.len() > 1).then_some(file.as_str());
+        found += write_matches(config, name, &contents, out)?;
+    }
+    Ok(found > 0)
+}
+
+impl MiniGrep {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on minigrep======")?;
+        let poem = include_str!("poem.txt");
+        // - the book's first query, case-sensitive
+        outln!(out, "Searching for `to`:")?;
+        for line in search("to", poem) {
+            outln!(out, "{line}")?;
+        }
+        outln!(out, "\nSearching for `BODY`, ignoring case:")?;
+        for line in search_case_insensitive("BODY", poem) {
+            outln!(out, "{line}")?;
+        }
+        // - a wildcard query, with numbers and a line of context
+        let config = Config::parse(
+            ["-n", "-C", "1", "How*a?"].map(String::from).into_iter(),
+            false,
+        )
+        .unwrap();
+        outln!(out, "\nSearching for `How*a?` with -n -C 1:")?;
+        write_matches(&config, None, poem, out)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for MiniGrep {
+    fn id(&self) -> &'static str {
+        "ch12::minigrep"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(12, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "An I/O Project: Building a Command Line Program"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["io", "testing", "error-handling"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch11::writing_tests", "ch9::results", "ch10::lifetimes"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("minigrep.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Trust me.";
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(String::from)
+    }
+
+    fn grep(config: &str, name: Option<&str>, contents: &str) -> String {
+        let config = Config::parse(args(config), false).unwrap();
+        let mut out = Vec::new();
+        write_matches(&config, name, contents, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    // The book's two tests, written before `search` itself
+    #[test]
+    fn case_sensitive() {
+        let query = "duct";
+        assert_eq!(vec!["safe, fast, productive."], search(query, CONTENTS));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let query = "rUsT";
+        assert_eq!(
+            vec!["Rust:", "Trust me."],
+            search_case_insensitive(query, CONTENTS)
+        );
+    }
+
+    // Wildcards
+    // - `*` may match nothing at all, `?` exactly one character
+    #[test]
+    fn wildcards() {
+        assert_eq!(vec!["safe, fast, productive."], search("f*t", CONTENTS));
+        assert_eq!(vec!["Duct tape."], search("D?ct", CONTENTS));
+        assert_eq!(vec!["Pick three."], search("Pi*ck", CONTENTS));
+        assert!(search("D??ct", CONTENTS).is_empty());
+        assert_eq!(CONTENTS.lines().count(), search("*", CONTENTS).len());
+        assert_eq!(CONTENTS.lines().count(), search("", CONTENTS).len());
+    }
+
+    // - backtracking into every `*` from every start took exponential time on these
+    #[test]
+    fn many_stars_do_not_backtrack() {
+        let line = "a".repeat(10_000);
+        assert!(search("a*a*a*a*b", &line).is_empty());
+        assert!(search("*a*a*a*a*a*a*a*b", &line).is_empty());
+        let line = line + "b";
+        assert_eq!(vec![line.as_str()], search("a*a*a*a*b", &line));
+        assert_eq!(vec![line.as_str()], search("?*a*?b", &line));
+    }
+
+    #[test]
+    fn numbers_lines_and_prints_context() {
+        assert_eq!(grep("-n tape", None, CONTENTS), "4:Duct tape.\n");
+        assert_eq!(
+            grep("-n -C 1 Pick", Some("poem.txt"), CONTENTS),
+            "poem.txt-2-safe, fast, productive.\npoem.txt:3:Pick three.\npoem.txt-4-Duct tape.\n"
+        );
+    }
+
+    // - overlapping context is printed once, and distant groups are split by `--`
+    #[test]
+    fn merges_and_separates_context() {
+        assert_eq!(
+            grep("-C 1 -i rust", None, CONTENTS),
+            "Rust:\nsafe, fast, productive.\n--\nDuct tape.\nTrust me.\n"
+        );
+        assert_eq!(
+            grep("-C 1 t", None, CONTENTS),
+            "Rust:\nsafe, fast, productive.\nPick three.\nDuct tape.\nTrust me.\n"
+        );
+    }
+
+    #[test]
+    fn reads_stdin_without_files() -> Result<(), String> {
+        let config = Config::parse(args("-n three"), false)?;
+        let mut out = Vec::new();
+        let found = run(&config, &mut CONTENTS.as_bytes(), &mut out).map_err(|e| e.to_string())?;
+        assert!(found);
+        assert_eq!(String::from_utf8(out).unwrap(), "3:Pick three.\n");
+        Ok(())
+    }
+
+    #[test]
+    fn reports_a_missing_file_with_its_path() {
+        let config = Config::parse(args("x no/such/file.txt"), false).unwrap();
+        let err = run(&config, &mut io::empty(), &mut io::sink()).unwrap_err();
+        assert!(err.to_string().starts_with("no/such/file.txt: "));
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(
+            Config::parse(args("-i -C 2 to a.txt - b.txt"), false),
+            Ok(Config {
+                query: String::from("to"),
+                files: vec![
+                    String::from("a.txt"),
+                    String::from("-"),
+                    String::from("b.txt")
+                ],
+                ignore_case: true,
+                line_numbers: false,
+                context: 2,
+            })
+        );
+        // - `IGNORE_CASE` is passed in as the default, the flag can only turn it on
+        assert!(Config::parse(args("to"), true).unwrap().ignore_case);
+        assert!(Config::parse(args(""), false).is_err());
+        assert!(Config::parse(args("-C x to"), false).is_err());
+        assert!(Config::parse(args("-x to"), false).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Didn't get a query string")]
+    fn missing_query() {
+        Config::parse(args("-n"), false).unwrap();
+    }
+}
@@ -0,0 +1,8 @@
+pub mod minigrep;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![Box::new(minigrep::MiniGrep {})]
+}
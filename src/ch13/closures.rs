@@ -30,6 +30,8 @@
 ///         * important in cases such as calling a closre multiple times concurrently
 /// * Closures Must Name Captured Lifetimes
 ///     * in some cases, we need to tell Rust about lifetimes of its returned value
+use crate::lesson::SharedOutput;
+use std::io::{self, Write};
 use std::thread;
 
 #[derive(Debug)]
@@ -81,8 +83,8 @@ impl Inventory {
 
 #[allow(dead_code, unused_variables)]
 impl Closures{
-    pub fn print(&self) {
-        println!("\n======The note on closures======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on closures======")?;
 
         let store = Inventory{
             shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]
@@ -90,11 +92,11 @@ impl Closures{
 
         let user_pref1 = Some(ShirtColor::Red);
         let giveaway1 = store.giveaway(user_pref1);
-        println!("\nThe user with preference {:?} gets {:?}", user_pref1, giveaway1);
+        outln!(out, "\nThe user with preference {:?} gets {:?}", user_pref1, giveaway1)?;
 
         let user_pref2 = None;
         let giveaway2 = store.giveaway(user_pref2);
-        println!("The user with preference {:?} gets {:?}", user_pref2, giveaway2);
+        outln!(out, "The user with preference {:?} gets {:?}", user_pref2, giveaway2)?;
 
     // We can annotate closures to the extent that they look like a `fn` function
     fn  add_one_v1   (x: u32) -> u32 { x + 1 }
@@ -115,31 +117,35 @@ impl Closures{
 
     // Capturing References or Moving Ownership
         let list = vec![1, 2, 3];
-        println!("\nBefore defining closure list is: {:?}", list);
+        outln!(out, "\nBefore defining closure list is: {:?}", list)?;
     
         // - borrowing immutably by immutable ref
-        let only_borrows = || println!("From closure list is: {:?}", list);
+        let only_borrows = |out: &mut dyn Write| outln!(out, "From closure list is: {:?}", list);
     
-        println!("Before calling closure list is: {:?}", list);
-        only_borrows();
-        println!("After calling closure list is: {:?}", list);
+        outln!(out, "Before calling closure list is: {:?}", list)?;
+        only_borrows(out)?;
+        outln!(out, "After calling closure list is: {:?}", list)?;
 
         // - borrowing mutably by mutable ref
         let mut list2 = vec![4, 5, 6];
-        println!("\nBefore defining closure list2 is : {:?}", list2);
+        outln!(out, "\nBefore defining closure list2 is : {:?}", list2)?;
     
         let mut borrows_mutably = || list2.push(7);
 
         borrows_mutably();
-        println!("After calling closure list2 is: {:?}", list2);
+        outln!(out, "After calling closure list2 is: {:?}", list2)?;
 
 
         let list3 = vec![8, 9, 10];
-        println!("\nBefore defining closure list3 is: {:?}", list3);
+        outln!(out, "\nBefore defining closure list3 is: {:?}", list3)?;
 
-        thread::spawn(move || println!("From thread list3 is: {:?}", list3))
+        // - the spawned thread cannot borrow `out`, so it is handed a writer of its own
+        let mut shared = SharedOutput::new(out);
+        let mut thread_out = shared.writer();
+        thread::spawn(move || outln!(thread_out, "From thread list3 is: {:?}", list3))
             .join()
-            .unwrap();
+            .unwrap()?;
+        shared.flush()?;
 
             // - list3 is unusable after ownership was taken
         // println!("After calling closure list3 is: {:?}", list3);
@@ -154,7 +160,7 @@ impl Closures{
             // - returns `width` field of a `Rectangle` struct for each instance in the array
             // - so it implements `FnMut`
         list_rec.sort_by_key(|r| r.width);
-        println!("\nAfter sort, list_rec is: {:#?}", list_rec);
+        outln!(out, "\nAfter sort, list_rec is: {:#?}", list_rec)?;
 
         
         // - the closure provided to `sort_by_key`
@@ -177,7 +183,7 @@ impl Closures{
             num_sort_operations += 1;
             r.width
         });
-        println!("\n{:#?}, sorted in {num_sort_operations} operations", list_rec);
+        outln!(out, "\n{:#?}, sorted in {num_sort_operations} operations", list_rec)?;
 
         // - below function is rejected by compiler
             // - because the return value, a closure, may outlive s_ref then it causes a use-after-free issue
@@ -198,7 +204,38 @@ impl Closures{
         fn make_a_cloner2(s_ref: &str) -> impl Fn() -> String + '_ {
             move || s_ref.to_string()
         }
-
+        Ok(())
     }
 }
 
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Closures {
+    fn id(&self) -> &'static str {
+        "ch13::closures"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(13, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Closures: Anonymous Functions that Capture Their Environment"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["closures", "functional"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch10::traits"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("closures.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
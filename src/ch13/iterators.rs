@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch13.2 - Processing a Series of Items with Iterators
 /// * The iterator pattern allows you to perform some task on sequence of items in turn.
 ///     * An iterator is responsible for the logic of iterating over each item and determining when the sequence has finished
@@ -25,8 +27,8 @@ struct Shoe {
 }
 
 impl Iterators{
-    pub fn print(&self) {
-        println!("\n======The note on iterators======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on iterators======")?;
     // Create an iterator
         // - the code by itself does not do anything useful so far
         let v1 = vec![1, 2, 3];
@@ -34,25 +36,25 @@ impl Iterators{
         // - consume the iterator
             // - v1_iter cannot be used after this for loop
         for val in v1_iter {
-            println!("{val}");
+            outln!(out, "{val}")?;
         }
-        println!("\nAfter for loop, v1 is {:?} ", v1);
+        outln!(out, "\nAfter for loop, v1 is {:?} ", v1)?;
     
     // Consume an iterator
         let v2: Vec<i32> = vec![4, 5, 6];
         let v2_iter = v2.iter();
         let total: i32 = v2_iter.sum();
-        println!("\nsum of {:?} is {}", v2, total);
+        outln!(out, "\nsum of {:?} is {}", v2, total)?;
 
     // Iterator adaptor
         let v3: Vec<i32> = vec![7, 8, 9];
         // - iterator adaptor is lazy, so far it does nothing so v3 looks like unchagned
         let v3_map = v3.iter().map(|x| x + 1);
-        println!("\nAfter map, v3 is: {:?}", v3);
+        outln!(out, "\nAfter map, v3 is: {:?}", v3)?;
             // - when collect(), we may need to explicit annotatation
         let v4: Vec<i32> = v3_map.collect();
-        println!("After collect, v4 is: {:?}", v4);
-        println!("After collect, v3 is: {:?}", v3);
+        outln!(out, "After collect, v4 is: {:?}", v4)?;
+        outln!(out, "After collect, v3 is: {:?}", v3)?;
 
         // - taking closures as argument
             // - closures may capture their environment
@@ -74,7 +76,7 @@ impl Iterators{
         let shoe_size = 10;
         // - closure provided to `filter` takes ownership of s but shoe_size is copied because it is i32
         let in_my_size: Vec<Shoe> = shoes.into_iter().filter(|s| s.size == shoe_size).collect();
-        println!("\nShoes in my size {} is {:?}", shoe_size, in_my_size);
+        outln!(out, "\nShoes in my size {} is {:?}", shoe_size, in_my_size)?;
 
     // Quiz
     let v = vec![1, 2, 3, 4];
@@ -93,8 +95,74 @@ impl Iterators{
 
     let a: Vec<_> = v.iter().filter(|x: &&i32| *x % 2 == 0).map(|x: &i32| x * 2).collect();
     let b: Vec<_> = v.iter().map(|x: &i32| x * 2).filter(|x: &i32| x % 2 == 0).collect();
-    println!("\nAfter above operations, a is {:?}, b is {:?}", a, b);
+    outln!(out, "\nAfter above operations, a is {:?}, b is {:?}", a, b)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+use crate::quiz::Quiz;
+
+const QUIZZES: &[Quiz] = &[
+    Quiz {
+        question: "What are `a` and `b`?",
+        snippet: "\
+let v = vec![1, 2, 3, 4];
+let a: Vec<_> = v.iter().filter(|x| *x % 2 == 0).map(|x| x * 2).collect();
+let b: Vec<_> = v.iter().map(|x| x * 2).filter(|x| x % 2 == 0).collect();",
+        choices: &[
+            "a = [4, 8], b = [4, 8]",
+            "a = [4, 8], b = [2, 4, 6, 8]",
+            "a = [2, 4], b = [2, 4, 6, 8]",
+            "a = [4, 8], b = [4, 8, 12, 16]",
+        ],
+        answer: 1,
+        explanation: "\
+`a` keeps the even numbers 2 and 4, then doubles them;
+`b` doubles every number first, and every doubled number is even, so the filter keeps all of them.",
+    },
+    Quiz {
+        question: "What is the type of `x` in the closure passed to `filter`?",
+        snippet: "v.iter().filter(|x| *x % 2 == 0)",
+        choices: &["i32", "&i32", "&&i32", "&mut i32"],
+        answer: 2,
+        explanation: "\
+`.iter()` yields `&i32`, and `filter` passes a reference to each item to its predicate, hence `&&i32`;
+`%` is implemented for `&i32` but not `&&i32`, which is why the closure dereferences with `*x`.",
+    },
+];
+
+impl Lesson for Iterators {
+    fn id(&self) -> &'static str {
+        "ch13::iterators"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(13, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Processing a Series of Items with Iterators"
+    }
 
+    fn tags(&self) -> &'static [&'static str] {
+        &["iterators", "closures", "functional"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch13::closures", "ch8::vectors"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("iterators.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn quizzes(&self) -> &'static [Quiz] {
+        QUIZZES
     }
 }
 
@@ -123,4 +191,4 @@ mod tests {
         assert_eq!(total, 6);
     }
 
-}
\ No newline at end of file
+}
@@ -1,2 +1,13 @@
+#![allow(clippy::useless_vec)]
 pub mod closures;
-pub mod iterators;
\ No newline at end of file
+pub mod iterators;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(closures::Closures {}),
+        Box::new(iterators::Iterators {}),
+    ]
+}
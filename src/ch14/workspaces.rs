@@ -0,0 +1,92 @@
+//! # Ch14.3 - Cargo Workspaces
+//!     * a workspace is a set of packages that share one `Cargo.lock` and one output directory, `target/`
+//!         * so the packages agree on the versions of their dependencies, and are compiled once for all
+//!     * the top `Cargo.toml` has a `[workspace]` section, whose `members` lists the packages' directories
+//!         * it can have a `[package]` of its own, as this repository does, or be a virtual manifest without one
+//! ```toml
+//! [workspace]
+//! members = ["crates/add_one", "crates/restaurant"]
+//! ```
+//!     * members depend on each other by path, `add_one = { path = "crates/add_one" }`, Cargo does not assume it
+//!         * here the dependencies are optional, pulled in by the `ch7` and `ch14` features
+//!     * from the top directory, `cargo build` and `cargo test` act on the root package
+//!         * `-p add_one` picks a member, `--workspace` takes them all, e.g. `cargo test --workspace`
+//!     * an external dependency, e.g. `rand`, must be listed in each member that uses it, but is resolved once
+//!     * each member is published on its own, `cargo publish -p add_one`
+use std::io::{self, Write};
+
+// - `pub use` of another crate's item, `rust_after_cpp::ch14::workspaces::add_one` is `add_one::add_one`
+pub use add_one::add_one;
+
+#[derive(Debug)]
+pub struct Workspaces {}
+
+impl Workspaces {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on cargo workspaces======")?;
+        // - the book's `adder` binary, calling into the `add_one` member
+        let num = 10;
+        outln!(
+            out,
+            "Hello, world! {num} plus one is {}!",
+            add_one::add_one(num)
+        )?;
+        // - the re-export is the same function
+        outln!(out, "through the re-export: {}", self::add_one(41))?;
+        // - both paths resolve to the one item of the `add_one` crate
+        outln!(
+            out,
+            "{} is {}",
+            std::any::type_name_of_val(&self::add_one),
+            std::any::type_name_of_val(&add_one::add_one)
+        )?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Workspaces {
+    fn id(&self) -> &'static str {
+        "ch14::workspaces"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(14, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Cargo Workspaces"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["modules", "cargo"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch7::modules"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("workspaces.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_re_export_is_the_member_function() {
+        assert_eq!(add_one(2), ::add_one::add_one(2));
+        let mut out = Vec::new();
+        Workspaces {}.print(&mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("Hello, world! 10 plus one is 11!\n"));
+    }
+}
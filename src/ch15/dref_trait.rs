@@ -10,6 +10,8 @@
 //!         * From `&mut T` to `&mut U` when `T`: `DerefMut<Target=U>`
 //!         * From `&mut T` to `&U` when `T`: `Deref<Target=U>`
 //!             * not reverse
+
+use std::io::{self, Write};
 #[derive(Debug)]
 #[allow(unused)]
 pub struct DerefTrait {}
@@ -45,8 +47,8 @@ impl Deref for AccessLogger {
 }
 
 impl DerefTrait {
-    pub fn print(&self) {
-        println!("\n======The note on Deref trait======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on Deref trait======")?;
         // Following the Pointer to the Value
         let x = 5;
         // - y is a regular ref to x
@@ -68,21 +70,90 @@ impl DerefTrait {
 
         // Deref coercion
         // - fn hello takes &str as argument
-        fn hello(name: &str) {
-            println!("Hello, {name}!");
+        fn hello(name: &str, out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "Hello, {name}!")
         }
-        hello("Rust");
+        hello("Rust", out)?;
         // - when we provide `MyBox` which implements `Deref`, Rust does following steps for us automatically
         // - deref `MyBox` to `String` so `&MyBox` to `&String`, and `String` implements `Deref` too so
         // - Rust deref `&String` to `&str`
         // - Rust will do as many times as needed to get the matching type
         // - at compile time, so no runtime penalty
         let m = MyBox::new(String::from("Rust"));
-        hello(&m);
+        hello(&m, out)?;
         // - or we could manually do this ourselves
         // - get the `String` which is pointee of m by *m
         // - get a string slice, i.e., str, by indexing, by (*m)[..]
         // - get &str by borrowing the str with `&`
-        hello(&((*m)[..]));
+        hello(&((*m)[..]), out)?;
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`Deref` vs `operator*` and `operator->`",
+    cpp: "\
+#include <string>
+#include <utility>
+
+template <typename T>
+class MyBox {
+public:
+    explicit MyBox(T x) : value(std::move(x)) {}
+    T& operator*() { return value; }
+    T* operator->() { return &value; }
+
+private:
+    T value;
+};
+
+std::size_t hello(const std::string& name) { return name.size(); }
+
+std::size_t deref() {
+    MyBox<std::string> m(std::string(\"Rust\"));
+    return hello(*m) + m->size(); // `*` and `->` are explicit, C++ has no deref coercion
+}
+",
+    pitfalls: &[
+        "C++ never applies `operator*` implicitly, `hello(m)` does not compile where Rust turns `&MyBox<String>` into `&str`",
+        "`operator->` is a separate overload to keep in sync with `operator*`, Rust derives both uses from `Deref`",
+        "a conversion operator could make `hello(m)` compile, but it applies in every context, unlike deref coercion on references",
+    ],
+}];
+
+impl Lesson for DerefTrait {
+    fn id(&self) -> &'static str {
+        "ch15::dref_trait"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(15, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Treating Smart Pointers Like Regular References with Deref"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["smart-pointers", "traits"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch15::pointers_to_heap", "ch10::traits"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("dref_trait.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
     }
 }
@@ -9,46 +9,131 @@
 //!         * use `drop(obj)` to drop obj
 //!             * `drop` is included in prelude
 
+use std::cell::RefCell;
+use std::io::{self, Write};
+
 #[derive(Debug)]
 #[allow(unused)]
 pub struct DropTrait {}
 
-struct CustomSmartPointer {
+// - `drop` takes no other parameter, so the pointer keeps a handle to the output it reports to
+struct CustomSmartPointer<'a, W: Write> {
     data: String,
+    out: &'a RefCell<W>,
 }
 
-impl Drop for CustomSmartPointer {
+impl<W: Write> Drop for CustomSmartPointer<'_, W> {
     fn drop(&mut self) {
-        println!("Dropping CustomSmartPointer with data `{}`!", self.data);
+        // - `drop` cannot return an error either, so a failed write is ignored
+        let _ = outln!(
+            self.out.borrow_mut(),
+            "Dropping CustomSmartPointer with data `{}`!",
+            self.data
+        );
     }
 }
 
 #[allow(unused)]
 impl DropTrait {
-    pub fn print(&self) {
-        println!("\n======The note on drop trait======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on drop trait======")?;
+        // - declared before the pointers so it outlives them and receives their messages
+        let out = RefCell::new(out);
 
         // A smart pointer that implements `Drop` trait
         // - show when `Drop` is run automatically
         let c = CustomSmartPointer {
             data: String::from("stuff c"),
+            out: &out,
         };
 
         let d = CustomSmartPointer {
             data: String::from("stuff d"),
+            out: &out,
         };
 
         let e = CustomSmartPointer {
             data: String::from("stuff e"),
+            out: &out,
         };
 
         // Use `std::mem::drop` to drop an object earlier
-        println!("CustomSmartPointers created.");
+        outln!(out.borrow_mut(), "CustomSmartPointers created.")?;
         // - `drop` in `Drop` trait is not allowed to be called explicitly
         // c.drop();
         // - use `std::mem::drop` instead
         drop(c);
-        // - d and e will be dropped after this println!
-        println!("CustomSmartPointer dropped before the end of main.");
+        // - d and e will be dropped after this writeln!
+        outln!(
+            out.borrow_mut(),
+            "CustomSmartPointer dropped before the end of main."
+        )?;
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`Drop` vs destructors",
+    cpp: "\
+#include <iostream>
+#include <string>
+#include <utility>
+
+class CustomSmartPointer {
+public:
+    explicit CustomSmartPointer(std::string data) : data(std::move(data)) {}
+    ~CustomSmartPointer() { std::cout << \"Dropping CustomSmartPointer with data `\" << data << \"`\\n\"; }
+
+private:
+    std::string data;
+};
+
+void drop() {
+    CustomSmartPointer c(\"my stuff\");
+    CustomSmartPointer d(\"other stuff\");
+    c.~CustomSmartPointer(); // compiles, and `c` is destroyed again at the end of the scope
+}
+",
+    pitfalls: &[
+        "calling a destructor explicitly is allowed and destroys the object twice, Rust rejects `c.drop()` and offers `drop(c)`",
+        "both languages destroy locals in reverse order of declaration",
+        "a moved-from C++ object is still destroyed, a moved Rust value is dropped only by its new owner",
+    ],
+}];
+
+impl Lesson for DropTrait {
+    fn id(&self) -> &'static str {
+        "ch15::drop_trait"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(15, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Running Code on Cleanup with the Drop Trait"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["smart-pointers", "traits"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch15::dref_trait"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("drop_trait.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
     }
 }
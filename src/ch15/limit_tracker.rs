@@ -1,38 +1,655 @@
+//! # The `LimitTracker` of Ch15.5, which reports how much of a quota a value uses up
+//!     * a `ThresholdPolicy` lists the thresholds, in percent of the max, each with the template of its message
+//!         * `ThresholdPolicy::default()` is the book's 75%, 90% and 100%
+//!     * `set_value` sends a `QuotaEvent` to an `EventMessenger` when the value crosses a threshold
+//!         * every `Messenger` is an `EventMessenger` that receives the event's formatted message
+//!         * `ch15::messengers` has ready-made ones, e.g. to record the messages, write them to a file or send them down a channel
+//!     * a threshold is announced once when the value reaches it, not on every `set_value` above it
+//!         * falling back under it is announced when the threshold has a `recovery` template
+//!         * with a `hysteresis` margin, the value has to fall that many points under the threshold to leave it
+//!     * `with_cooldown` holds back the messages of a crossing already announced within the cooldown, read on a `Clock`
+//!         * where the value stands is announced once the cooldown is over, if that changed meanwhile
+//!     * `SharedLimitTracker` is the same for several threads, with `add` to count up from each of them
+//!     * rate limits, e.g. 100 requests a minute, with the same thresholds and messages
+//!         * `SlidingWindowTracker` counts the requests of the last minute
+//!         * `TokenBucketTracker` counts the tokens missing from a bucket that refills over time
+use crate::schedule::{Clock, RealClock};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 pub trait Messenger {
     fn send(&self, msg: &str);
 }
 
-pub struct LimitTracker<'a, T: Messenger> {
+/// Which way the tracked value went through a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Crossing {
+    /// Up to the threshold or over it
+    Reached,
+    /// Back under the threshold, and its hysteresis margin
+    Recovered,
+}
+
+/// The tracked value has crossed a threshold of its policy
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaEvent {
+    /// Index of the threshold in the policy, `0` for the lowest
+    ///     * for a recovery, the lowest threshold left, e.g. `0` when going from 95% back to 50%
+    pub level: usize,
+    pub crossing: Crossing,
+    pub value: usize,
+    pub max: usize,
+    /// `value` in percent of `max`
+    pub percentage: f64,
+    /// The threshold's template, or recovery template, filled in
+    pub message: String,
+}
+
+impl fmt::Display for QuotaEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Receives the events of a `LimitTracker` as they are, rather than as text
+pub trait EventMessenger {
+    fn notify(&self, event: &QuotaEvent);
+}
+
+/// A plain `Messenger` gets the formatted message of the event
+impl<M: Messenger + ?Sized> EventMessenger for M {
+    fn notify(&self, event: &QuotaEvent) {
+        self.send(&event.message);
+    }
+}
+
+/// A level of a `ThresholdPolicy`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    /// Reached when the value is at least this percentage of the max
+    pub percentage: f64,
+    /// The message, where `{value}`, `{max}` and `{percentage}` are replaced by the event's
+    pub template: String,
+    /// The message when the value falls back under the threshold, nothing is sent without one
+    pub recovery: Option<String>,
+    /// Percentage points the value has to fall under `percentage` to leave the threshold
+    pub hysteresis: f64,
+}
+
+impl Threshold {
+    pub fn new(percentage: f64, template: &str) -> Threshold {
+        Threshold {
+            percentage,
+            template: String::from(template),
+            recovery: None,
+            hysteresis: 0.0,
+        }
+    }
+
+    /// Announce the way back under the threshold, e.g. `recovery("Back under 75% of your quota")`
+    pub fn recovery(mut self, template: &str) -> Threshold {
+        self.recovery = Some(String::from(template));
+        self
+    }
+
+    pub fn hysteresis(mut self, margin: f64) -> Threshold {
+        self.hysteresis = margin;
+        self
+    }
+}
+
+/// The thresholds a `LimitTracker` reports, sorted by percentage
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdPolicy {
+    thresholds: Vec<Threshold>,
+}
+
+impl ThresholdPolicy {
+    /// A policy without any threshold, add them with `threshold` or `with`
+    ///     * `ThresholdPolicy::default()` is the book's thresholds instead
+    pub fn empty() -> ThresholdPolicy {
+        ThresholdPolicy {
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Add a threshold at `percentage` of the max, e.g. `threshold(50.0, "Half of {max} used")`
+    pub fn threshold(self, percentage: f64, template: &str) -> ThresholdPolicy {
+        self.with(Threshold::new(percentage, template))
+    }
+
+    /// Add a threshold built with its recovery message or hysteresis
+    pub fn with(mut self, threshold: Threshold) -> ThresholdPolicy {
+        let at = self
+            .thresholds
+            .partition_point(|other| other.percentage <= threshold.percentage);
+        self.thresholds.insert(at, threshold);
+        self
+    }
+
+    pub fn thresholds(&self) -> &[Threshold] {
+        &self.thresholds
+    }
+
+    /// `value` in percent of `max`
+    pub fn percentage(value: usize, max: usize) -> f64 {
+        // - multiplying first keeps whole percentages exact, `29.0 / 100.0 * 100.0` is `28.999999999999996`
+        value as f64 * 100.0 / max as f64
+    }
+
+    /// The level of the highest threshold that `value` reaches, if any
+    pub fn level(&self, value: usize, max: usize) -> Option<usize> {
+        let percentage = ThresholdPolicy::percentage(value, max);
+        self.thresholds
+            .iter()
+            .rposition(|threshold| percentage >= threshold.percentage)
+    }
+
+    /// The level of `value` when it was at level `from` before
+    ///     * going up, the highest threshold reached
+    ///     * going down, the highest threshold up to `from` that `value` has not left by its hysteresis margin
+    pub fn level_from(&self, from: Option<usize>, value: usize, max: usize) -> Option<usize> {
+        let reached = self.level(value, max);
+        // - `None` orders before any `Some`
+        let Some(from) = from.filter(|&from| reached < Some(from)) else {
+            return reached;
+        };
+        let percentage = ThresholdPolicy::percentage(value, max);
+        self.thresholds[..=from]
+            .iter()
+            .rposition(|threshold| percentage >= threshold.percentage - threshold.hysteresis)
+    }
+
+    /// The event of going from level `from` to level `to`, if it is announced
+    pub fn transition(
+        &self,
+        from: Option<usize>,
+        to: Option<usize>,
+        value: usize,
+        max: usize,
+    ) -> Option<QuotaEvent> {
+        if to > from {
+            self.event(to?, Crossing::Reached, value, max)
+        } else if to < from {
+            // - the lowest threshold left, just above the new level
+            self.event(to.map_or(0, |to| to + 1), Crossing::Recovered, value, max)
+        } else {
+            None
+        }
+    }
+
+    /// The event of the threshold at `level`, with its message filled in
+    ///     * `None` for a recovery when the threshold has no recovery template, or when there is no such level
+    pub fn event(
+        &self,
+        level: usize,
+        crossing: Crossing,
+        value: usize,
+        max: usize,
+    ) -> Option<QuotaEvent> {
+        let threshold = self.thresholds.get(level)?;
+        let template = match crossing {
+            Crossing::Reached => &threshold.template,
+            Crossing::Recovered => threshold.recovery.as_ref()?,
+        };
+        let percentage = ThresholdPolicy::percentage(value, max);
+        let message = template
+            .replace("{value}", &value.to_string())
+            .replace("{max}", &max.to_string())
+            // - rounded down, so the message never claims more than what is used
+            .replace("{percentage}", &format!("{:.0}", percentage.floor()));
+        Some(QuotaEvent {
+            level,
+            crossing,
+            value,
+            max,
+            percentage,
+            message,
+        })
+    }
+}
+
+impl Default for ThresholdPolicy {
+    /// The thresholds and messages of the book
+    fn default() -> ThresholdPolicy {
+        ThresholdPolicy::empty()
+            .threshold(75.0, "Warning: You've used up over 75% of your quota!")
+            .threshold(
+                90.0,
+                "Urgent warning: You've used up over 90% of your quota!",
+            )
+            .threshold(100.0, "Error: You are over your quota!")
+    }
+}
+
+/// What a tracker remembers between two values, to announce each crossing once
+struct Announcements {
+    /// The level last reached, `None` under the lowest threshold
+    level: Option<usize>,
+    /// The level as the messenger knows it, behind `level` while the cooldown drops a message
+    told: Option<usize>,
+    cooldown: Duration,
+    clock: Arc<dyn Clock>,
+    /// When each crossing of each level was last announced, on `clock`
+    announced: HashMap<(usize, Crossing), Duration>,
+}
+
+impl Announcements {
+    fn new() -> Announcements {
+        Announcements {
+            level: None,
+            told: None,
+            cooldown: Duration::ZERO,
+            clock: Arc::new(RealClock::new()),
+            announced: HashMap::new(),
+        }
+    }
+
+    /// Move to the level of `value`, and return the event to send for it, if any
+    ///     * the event goes from the level last told, so a crossing the cooldown dropped is sent once it is over
+    fn next(&mut self, policy: &ThresholdPolicy, value: usize, max: usize) -> Option<QuotaEvent> {
+        let level = policy.level_from(self.level, value, max);
+        self.level = level;
+        let Some(event) = policy.transition(self.told, level, value, max) else {
+            // - nothing to say about this crossing, e.g. a recovery without a template
+            self.told = level;
+            return None;
+        };
+        let now = self.clock.now();
+        let key = (event.level, event.crossing);
+        if let Some(&last) = self.announced.get(&key) {
+            if now < last + self.cooldown {
+                return None;
+            }
+        }
+        self.announced.insert(key, now);
+        self.told = level;
+        Some(event)
+    }
+}
+
+pub struct LimitTracker<'a, T: EventMessenger + ?Sized> {
     messenger: &'a T,
     value: usize,
     max: usize,
+    policy: ThresholdPolicy,
+    announcements: Announcements,
 }
 
 impl<'a, T> LimitTracker<'a, T>
 where
-    T: Messenger,
+    T: EventMessenger + ?Sized,
 {
     pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
+        LimitTracker::with_policy(messenger, max, ThresholdPolicy::default())
+    }
+
+    pub fn with_policy(
+        messenger: &'a T,
+        max: usize,
+        policy: ThresholdPolicy,
+    ) -> LimitTracker<'a, T> {
         LimitTracker {
             messenger,
             value: 0,
             max,
+            policy,
+            announcements: Announcements::new(),
         }
     }
 
+    /// Do not announce the same crossing of a threshold twice within `cooldown`, as told by `clock`
+    ///     * the level still follows the value, only the message is held back
+    ///     * the first value after the cooldown announces the level if it is not the one last announced
+    pub fn with_cooldown(
+        mut self,
+        cooldown: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> LimitTracker<'a, T> {
+        self.announcements.cooldown = cooldown;
+        self.announcements.clock = clock;
+        self
+    }
+
     pub fn set_value(&mut self, value: usize) {
         self.value = value;
 
-        let percentage_of_max = self.value as f64 / self.max as f64;
+        if let Some(event) = self.announcements.next(&self.policy, self.value, self.max) {
+            self.messenger.notify(&event);
+        }
+    }
+
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    /// The level of the highest threshold the value is at, `None` under the lowest one
+    pub fn level(&self) -> Option<usize> {
+        self.announcements.level
+    }
+
+    pub fn policy(&self) -> &ThresholdPolicy {
+        &self.policy
+    }
+}
 
-        if percentage_of_max >= 1.0 {
-            self.messenger.send("Error: You are over your quota!");
-        } else if percentage_of_max >= 0.9 {
-            self.messenger
-                .send("Urgent warning: You've used up over 90% of your quota!");
-        } else if percentage_of_max >= 0.75 {
-            self.messenger
-                .send("Warning: You've used up over 75% of your quota!");
+/// Adapts a shared `Messenger` to the events of a `SharedLimitTracker`
+struct SharedMessenger(Arc<dyn Messenger + Send + Sync>);
+
+impl Messenger for SharedMessenger {
+    fn send(&self, msg: &str) {
+        self.0.send(msg);
+    }
+}
+
+/// The value of a `SharedLimitTracker` and what it has announced, changed together under one lock
+struct Usage {
+    value: usize,
+    announcements: Announcements,
+}
+
+/// A `LimitTracker` that threads share, e.g. through an `Arc` as the counter of `ch16::mutexes`
+///     * it owns its messenger, and `add` and `set_value` take `&self`
+///     * each change of the value and its announcement happen under one `Mutex`
+///         * so every crossing is announced exactly once, in the order of the values, whatever the contention
+///         * the messenger is called with the lock held, it must not call back into the tracker
+pub struct SharedLimitTracker {
+    messenger: Arc<dyn EventMessenger + Send + Sync>,
+    max: usize,
+    policy: ThresholdPolicy,
+    usage: Mutex<Usage>,
+}
+
+impl SharedLimitTracker {
+    pub fn new(messenger: Arc<dyn Messenger + Send + Sync>, max: usize) -> SharedLimitTracker {
+        SharedLimitTracker::with_policy(messenger, max, ThresholdPolicy::default())
+    }
+
+    pub fn with_policy(
+        messenger: Arc<dyn Messenger + Send + Sync>,
+        max: usize,
+        policy: ThresholdPolicy,
+    ) -> SharedLimitTracker {
+        SharedLimitTracker::with_events(Arc::new(SharedMessenger(messenger)), max, policy)
+    }
+
+    /// A tracker that sends the `QuotaEvent`s themselves
+    pub fn with_events(
+        messenger: Arc<dyn EventMessenger + Send + Sync>,
+        max: usize,
+        policy: ThresholdPolicy,
+    ) -> SharedLimitTracker {
+        SharedLimitTracker {
+            messenger,
+            max,
+            policy,
+            usage: Mutex::new(Usage {
+                value: 0,
+                announcements: Announcements::new(),
+            }),
+        }
+    }
+
+    /// See `LimitTracker::with_cooldown`
+    pub fn with_cooldown(self, cooldown: Duration, clock: Arc<dyn Clock>) -> SharedLimitTracker {
+        {
+            let mut usage = self.usage.lock().unwrap();
+            usage.announcements.cooldown = cooldown;
+            usage.announcements.clock = clock;
+        }
+        self
+    }
+
+    pub fn set_value(&self, value: usize) {
+        self.update(|_| value);
+    }
+
+    /// Add `delta` to the value, saturating at `usize::MAX`, and return the new value
+    pub fn add(&self, delta: usize) -> usize {
+        self.update(|value| value.saturating_add(delta))
+    }
+
+    fn update(&self, change: impl FnOnce(usize) -> usize) -> usize {
+        let mut usage = self.usage.lock().unwrap();
+        usage.value = change(usage.value);
+        let value = usage.value;
+        if let Some(event) = usage.announcements.next(&self.policy, value, self.max) {
+            self.messenger.notify(&event);
+        }
+        value
+    }
+
+    pub fn value(&self) -> usize {
+        self.usage.lock().unwrap().value
+    }
+
+    /// The level of the highest threshold the value is at, `None` under the lowest one
+    pub fn level(&self) -> Option<usize> {
+        self.usage.lock().unwrap().announcements.level
+    }
+
+    pub fn policy(&self) -> &ThresholdPolicy {
+        &self.policy
+    }
+}
+
+/// A quota of `max` per `window`, e.g. 100 requests a minute, counted over the last `window` at any time
+///     * the usage is the count of the requests made in the window, against `max` as for `LimitTracker`
+///     * requests leave the window as the `clock` moves on, `refresh` announces the recovery it brings
+pub struct SlidingWindowTracker<'a, T: EventMessenger + ?Sized> {
+    messenger: &'a T,
+    max: usize,
+    window: Duration,
+    policy: ThresholdPolicy,
+    /// When each batch of requests was made and its count, oldest first
+    ///     * the count as added to `usage`, so taking it off again never overflows
+    requests: VecDeque<(Duration, usize)>,
+    usage: usize,
+    announcements: Announcements,
+}
+
+impl<'a, T> SlidingWindowTracker<'a, T>
+where
+    T: EventMessenger + ?Sized,
+{
+    pub fn new(
+        messenger: &'a T,
+        max: usize,
+        window: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> SlidingWindowTracker<'a, T> {
+        SlidingWindowTracker::with_policy(messenger, max, window, ThresholdPolicy::default(), clock)
+    }
+
+    /// A tracker with the thresholds of `policy` rather than the book's
+    ///     * panics on a zero window
+    pub fn with_policy(
+        messenger: &'a T,
+        max: usize,
+        window: Duration,
+        policy: ThresholdPolicy,
+        clock: Arc<dyn Clock>,
+    ) -> SlidingWindowTracker<'a, T> {
+        assert!(!window.is_zero(), "the window must not be zero");
+        let mut announcements = Announcements::new();
+        announcements.clock = clock;
+        SlidingWindowTracker {
+            messenger,
+            max,
+            window,
+            policy,
+            requests: VecDeque::new(),
+            usage: 0,
+            announcements,
+        }
+    }
+
+    /// See `LimitTracker::with_cooldown`, measured on the tracker's clock
+    pub fn with_cooldown(mut self, cooldown: Duration) -> SlidingWindowTracker<'a, T> {
+        self.announcements.cooldown = cooldown;
+        self
+    }
+
+    /// Count `count` requests made now, and return the usage of the window
+    pub fn record(&mut self, count: usize) -> usize {
+        let now = self.announcements.clock.now();
+        // - saturating at `usize::MAX`, only what was added leaves the window later
+        let added = count.min(usize::MAX - self.usage);
+        self.requests.push_back((now, added));
+        self.usage += added;
+        self.refresh()
+    }
+
+    /// Drop the requests older than the window, announce the change, and return the usage
+    pub fn refresh(&mut self) -> usize {
+        let now = self.announcements.clock.now();
+        // - a request made at `t` counts until `t + window`, excluded
+        while let Some(&(at, count)) = self.requests.front() {
+            if now < at + self.window {
+                break;
+            }
+            self.requests.pop_front();
+            self.usage -= count;
+        }
+        if let Some(event) = self.announcements.next(&self.policy, self.usage, self.max) {
+            self.messenger.notify(&event);
+        }
+        self.usage
+    }
+
+    /// The level of the highest threshold the usage is at, as of the last `record` or `refresh`
+    pub fn level(&self) -> Option<usize> {
+        self.announcements.level
+    }
+
+    pub fn policy(&self) -> &ThresholdPolicy {
+        &self.policy
+    }
+}
+
+/// A bucket of `capacity` tokens that refills one token every `refill_every`, e.g. 60 a minute every second
+///     * a request takes tokens, and is refused when too few are left
+///     * the usage is the count of tokens missing from the bucket, against `capacity` as for `LimitTracker`
+///         * so 100% is announced when the bucket is empty
+pub struct TokenBucketTracker<'a, T: EventMessenger + ?Sized> {
+    messenger: &'a T,
+    capacity: usize,
+    refill_every: Duration,
+    policy: ThresholdPolicy,
+    tokens: usize,
+    /// When the last token was added to the bucket, or when it was last seen full
+    refilled_at: Duration,
+    announcements: Announcements,
+}
+
+impl<'a, T> TokenBucketTracker<'a, T>
+where
+    T: EventMessenger + ?Sized,
+{
+    pub fn new(
+        messenger: &'a T,
+        capacity: usize,
+        refill_every: Duration,
+        clock: Arc<dyn Clock>,
+    ) -> TokenBucketTracker<'a, T> {
+        TokenBucketTracker::with_policy(
+            messenger,
+            capacity,
+            refill_every,
+            ThresholdPolicy::default(),
+            clock,
+        )
+    }
+
+    /// A tracker with the thresholds of `policy` rather than the book's
+    ///     * panics on a zero refill period
+    pub fn with_policy(
+        messenger: &'a T,
+        capacity: usize,
+        refill_every: Duration,
+        policy: ThresholdPolicy,
+        clock: Arc<dyn Clock>,
+    ) -> TokenBucketTracker<'a, T> {
+        assert!(
+            !refill_every.is_zero(),
+            "the refill period must not be zero"
+        );
+        let refilled_at = clock.now();
+        let mut announcements = Announcements::new();
+        announcements.clock = clock;
+        TokenBucketTracker {
+            messenger,
+            capacity,
+            refill_every,
+            policy,
+            tokens: capacity,
+            refilled_at,
+            announcements,
+        }
+    }
+
+    /// See `LimitTracker::with_cooldown`, measured on the tracker's clock
+    pub fn with_cooldown(mut self, cooldown: Duration) -> TokenBucketTracker<'a, T> {
+        self.announcements.cooldown = cooldown;
+        self
+    }
+
+    /// Take `count` tokens if there are that many, and tell whether the request may go on
+    pub fn take(&mut self, count: usize) -> bool {
+        self.refill();
+        let taken = self.tokens >= count;
+        if taken {
+            self.tokens -= count;
+        }
+        self.announce();
+        taken
+    }
+
+    /// Add the tokens refilled since the last time, announce the change, and return the usage
+    pub fn refresh(&mut self) -> usize {
+        self.refill();
+        self.announce();
+        self.capacity - self.tokens
+    }
+
+    /// The tokens left, as of the last `take` or `refresh`
+    pub fn tokens(&self) -> usize {
+        self.tokens
+    }
+
+    /// The level of the highest threshold the usage is at, as of the last `take` or `refresh`
+    pub fn level(&self) -> Option<usize> {
+        self.announcements.level
+    }
+
+    pub fn policy(&self) -> &ThresholdPolicy {
+        &self.policy
+    }
+
+    fn refill(&mut self) {
+        let now = self.announcements.clock.now();
+        let elapsed = now - self.refilled_at;
+        let periods = elapsed.as_nanos() / self.refill_every.as_nanos();
+        let missing = self.capacity - self.tokens;
+        if periods >= missing as u128 {
+            // - full, the time spent full does not count towards the next token
+            self.tokens = self.capacity;
+            self.refilled_at = now;
+        } else {
+            // - `periods` is less than `missing`, so it fits in `usize`
+            self.tokens += periods as usize;
+            // - the time since the last token added counts towards the next one
+            let rest = elapsed.as_nanos() % self.refill_every.as_nanos();
+            self.refilled_at = now - Duration::from_nanos(rest as u64);
+        }
+    }
+
+    fn announce(&mut self) {
+        let usage = self.capacity - self.tokens;
+        if let Some(event) = self.announcements.next(&self.policy, usage, self.capacity) {
+            self.messenger.notify(&event);
         }
     }
 }
@@ -41,7 +658,10 @@ where
 #[allow(unused)]
 mod tests {
     use super::*;
+    use crate::ch15::messengers::RecordingMessenger;
+    use crate::schedule::FastForward;
     use std::cell::RefCell;
+    use std::thread;
 
     struct MockMessenger {
         sent_messages: Vec<String>,
@@ -77,4 +697,421 @@ mod tests {
         // assert_eq!(mock_messenger.sent_messages.len(), 1);
         assert_eq!(mock_messenger.sent_messages_rc.borrow().len(), 1);
     }
+
+    #[test]
+    fn it_announces_each_threshold_once_when_reached() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        for value in [10, 75, 80, 90, 100, 250, 99] {
+            limit_tracker.set_value(value);
+        }
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            [
+                "Warning: You've used up over 75% of your quota!",
+                "Urgent warning: You've used up over 90% of your quota!",
+                "Error: You are over your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_fills_in_the_templates_of_a_custom_policy() {
+        let mock_messenger = MockMessenger::new();
+        // - added out of order, the policy sorts them
+        let policy = ThresholdPolicy::empty()
+            .threshold(80.0, "{value} of {max} used")
+            .threshold(29.0, "{percentage}% used");
+        let mut limit_tracker = LimitTracker::with_policy(&mock_messenger, 100, policy);
+
+        limit_tracker.set_value(28);
+        limit_tracker.set_value(29);
+        limit_tracker.set_value(80);
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            ["29% used", "80 of 100 used"]
+        );
+    }
+
+    #[test]
+    fn it_does_not_repeat_a_warning_for_the_same_value() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        for _ in 0..10 {
+            limit_tracker.set_value(80);
+        }
+
+        assert_eq!(mock_messenger.sent_messages_rc.borrow().len(), 1);
+        assert_eq!(limit_tracker.level(), Some(0));
+    }
+
+    fn recovering_policy() -> ThresholdPolicy {
+        ThresholdPolicy::empty()
+            .with(
+                Threshold::new(75.0, "over 75%")
+                    .recovery("back under 75%")
+                    .hysteresis(5.0),
+            )
+            .with(Threshold::new(90.0, "over 90%").recovery("back under 90%"))
+    }
+
+    #[test]
+    fn it_announces_recoveries_past_the_hysteresis_margin() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker =
+            LimitTracker::with_policy(&mock_messenger, 100, recovering_policy());
+
+        // - 72 and 70 are under 75 but within the margin of 5 points, 69 is not
+        for value in [80, 72, 70, 69, 76, 95, 89, 91, 50] {
+            limit_tracker.set_value(value);
+        }
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            [
+                "over 75%",
+                "back under 75%",
+                "over 75%",
+                "over 90%",
+                "back under 90%",
+                "over 90%",
+                // - one message for leaving both thresholds, about the lowest one
+                "back under 75%",
+            ]
+        );
+        assert_eq!(limit_tracker.level(), None);
+    }
+
+    #[test]
+    fn recoveries_without_a_template_are_silent() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        for value in [95, 10, 80] {
+            limit_tracker.set_value(value);
+        }
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            [
+                "Urgent warning: You've used up over 90% of your quota!",
+                "Warning: You've used up over 75% of your quota!",
+            ]
+        );
+        let policy = ThresholdPolicy::default();
+        assert_eq!(policy.event(0, Crossing::Recovered, 10, 100), None);
+        assert_eq!(policy.event(3, Crossing::Reached, 10, 100), None);
+    }
+
+    #[test]
+    fn it_drops_repeated_crossings_within_the_cooldown() {
+        let mock_messenger = MockMessenger::new();
+        let clock = Arc::new(FastForward::new());
+        let policy = ThresholdPolicy::empty()
+            .with(Threshold::new(75.0, "over 75%").recovery("back under 75%"))
+            .threshold(100.0, "over 100%");
+        let mut limit_tracker = LimitTracker::with_policy(&mock_messenger, 100, policy)
+            .with_cooldown(Duration::from_secs(60), clock.clone());
+
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(70);
+        // - dropped, `over 75%` was sent less than a minute ago
+        limit_tracker.set_value(80);
+        // - another threshold, not held back by the cooldown of the first
+        limit_tracker.set_value(100);
+        clock.sleep(Duration::from_secs(60));
+        limit_tracker.set_value(70);
+        limit_tracker.set_value(80);
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            [
+                "over 75%",
+                "back under 75%",
+                "over 100%",
+                "back under 75%",
+                "over 75%",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_announces_a_dropped_crossing_after_the_cooldown() {
+        let mock_messenger = MockMessenger::new();
+        let clock = Arc::new(FastForward::new());
+        let policy = ThresholdPolicy::empty()
+            .with(Threshold::new(75.0, "over 75%").recovery("back under 75%"))
+            .threshold(100.0, "over 100%");
+        let mut limit_tracker = LimitTracker::with_policy(&mock_messenger, 100, policy)
+            .with_cooldown(Duration::from_secs(60), clock.clone());
+
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(70);
+        // - dropped, and still the level when the cooldown is over
+        limit_tracker.set_value(80);
+        clock.sleep(Duration::from_secs(60));
+        limit_tracker.set_value(85);
+        assert_eq!(limit_tracker.level(), Some(0));
+        limit_tracker.set_value(70);
+        // - dropped, then undone within the cooldown, so there is nothing left to say
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(70);
+        clock.sleep(Duration::from_secs(60));
+        limit_tracker.set_value(60);
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            ["over 75%", "back under 75%", "over 75%", "back under 75%"]
+        );
+    }
+
+    #[test]
+    fn a_sliding_window_counts_the_requests_of_the_last_minute() {
+        let mock_messenger = MockMessenger::new();
+        let clock = Arc::new(FastForward::new());
+        let mut tracker = SlidingWindowTracker::with_policy(
+            &mock_messenger,
+            4,
+            Duration::from_secs(60),
+            recovering_policy(),
+            clock.clone(),
+        );
+
+        assert_eq!(tracker.record(3), 3);
+        clock.sleep(Duration::from_secs(30));
+        assert_eq!(tracker.record(1), 4);
+        // - the first 3 requests are a minute old, and leave the window
+        clock.sleep(Duration::from_secs(30));
+        assert_eq!(tracker.refresh(), 1);
+        assert_eq!(tracker.level(), None);
+        clock.sleep(Duration::from_secs(29));
+        assert_eq!(tracker.record(3), 4);
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            ["over 75%", "over 90%", "back under 75%", "over 90%"]
+        );
+    }
+
+    #[test]
+    fn a_sliding_window_saturates_and_drains() {
+        let mock_messenger = MockMessenger::new();
+        let clock = Arc::new(FastForward::new());
+        let mut tracker =
+            SlidingWindowTracker::new(&mock_messenger, 4, Duration::from_secs(60), clock.clone());
+
+        assert_eq!(tracker.record(usize::MAX - 1), usize::MAX - 1);
+        assert_eq!(tracker.record(5), usize::MAX);
+        clock.sleep(Duration::from_secs(30));
+        assert_eq!(tracker.record(usize::MAX), usize::MAX);
+        // - the first two batches leave the window with only what they added, the third added nothing
+        clock.sleep(Duration::from_secs(30));
+        assert_eq!(tracker.refresh(), 0);
+        clock.sleep(Duration::from_secs(30));
+        assert_eq!(tracker.refresh(), 0);
+    }
+
+    #[test]
+    fn a_token_bucket_refuses_requests_once_empty() {
+        let mock_messenger = MockMessenger::new();
+        let clock = Arc::new(FastForward::new());
+        let mut tracker =
+            TokenBucketTracker::new(&mock_messenger, 4, Duration::from_secs(15), clock.clone());
+
+        assert!(tracker.take(3));
+        assert!(!tracker.take(2));
+        assert!(tracker.take(1));
+        assert!(!tracker.take(1));
+        // - one token back every 15 seconds, the partial period carries over
+        clock.sleep(Duration::from_secs(20));
+        assert_eq!(tracker.refresh(), 3);
+        clock.sleep(Duration::from_secs(10));
+        assert_eq!(tracker.tokens(), 1);
+        assert_eq!(tracker.refresh(), 2);
+        // - refilled to the capacity, and no further
+        clock.sleep(Duration::from_secs(600));
+        assert_eq!(tracker.refresh(), 0);
+        assert!(tracker.take(3));
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            [
+                "Warning: You've used up over 75% of your quota!",
+                "Error: You are over your quota!",
+                "Warning: You've used up over 75% of your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn rate_trackers_apply_the_cooldown_on_their_clock() {
+        let mock_messenger = MockMessenger::new();
+        let clock = Arc::new(FastForward::new());
+        let mut tracker =
+            SlidingWindowTracker::new(&mock_messenger, 4, Duration::from_secs(10), clock.clone())
+                .with_cooldown(Duration::from_secs(60));
+
+        // - the window fills up and empties every 10 seconds, the warning is sent once a minute
+        for _ in 0..12 {
+            tracker.record(3);
+            clock.sleep(Duration::from_secs(10));
+            assert_eq!(tracker.refresh(), 0);
+        }
+
+        assert_eq!(mock_messenger.sent_messages_rc.borrow().len(), 2);
+    }
+
+    #[test]
+    fn a_shared_tracker_can_be_sent_and_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedLimitTracker>();
+    }
+
+    #[test]
+    fn concurrent_adds_announce_each_threshold_exactly_once() {
+        let recorder = Arc::new(RecordingMessenger::new());
+        let tracker = Arc::new(SharedLimitTracker::new(recorder.clone(), 8 * 1000));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let tracker = Arc::clone(&tracker);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        tracker.add(1);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(tracker.value(), 8000);
+        assert_eq!(tracker.level(), Some(2));
+        assert_eq!(
+            recorder.messages(),
+            [
+                "Warning: You've used up over 75% of your quota!",
+                "Urgent warning: You've used up over 90% of your quota!",
+                "Error: You are over your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn contended_crossings_are_announced_in_turn() {
+        let recorder = Arc::new(RecordingMessenger::new());
+        let policy = ThresholdPolicy::empty()
+            .with(Threshold::new(50.0, "over 50%").recovery("back under 50%"));
+        let tracker = Arc::new(SharedLimitTracker::with_policy(
+            recorder.clone(),
+            100,
+            policy,
+        ));
+
+        // - half the threads push the value over the threshold, the other half back under it
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let tracker = Arc::clone(&tracker);
+                thread::spawn(move || {
+                    for _ in 0..500 {
+                        tracker.set_value(if i % 2 == 0 { 60 } else { 40 });
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // - never two reached or two recovered in a row, whichever thread won the lock
+        let messages = recorder.messages();
+        assert!(!messages.is_empty());
+        for (i, message) in messages.iter().enumerate() {
+            let expected = if i % 2 == 0 {
+                "over 50%"
+            } else {
+                "back under 50%"
+            };
+            assert_eq!(message, expected);
+        }
+        let over = tracker.level() == Some(0);
+        assert_eq!(messages.len() % 2 == 1, over);
+    }
+
+    #[test]
+    fn a_shared_tracker_sends_events_to_an_event_messenger() {
+        let recorder = Arc::new(SharedEventRecorder::default());
+        let tracker =
+            SharedLimitTracker::with_events(recorder.clone(), 10, ThresholdPolicy::default());
+
+        assert_eq!(tracker.add(8), 8);
+        assert_eq!(tracker.add(usize::MAX), usize::MAX);
+
+        let events = recorder.events.lock().unwrap();
+        assert_eq!(
+            events.iter().map(|event| event.level).collect::<Vec<_>>(),
+            [0, 2]
+        );
+    }
+
+    #[derive(Default)]
+    struct SharedEventRecorder {
+        events: Mutex<Vec<QuotaEvent>>,
+    }
+
+    impl EventMessenger for SharedEventRecorder {
+        fn notify(&self, event: &QuotaEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    struct EventRecorder {
+        events: RefCell<Vec<QuotaEvent>>,
+    }
+
+    impl EventMessenger for EventRecorder {
+        fn notify(&self, event: &QuotaEvent) {
+            self.events.borrow_mut().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn it_notifies_event_messengers_with_typed_events() {
+        let recorder = EventRecorder {
+            events: RefCell::new(vec![]),
+        };
+        let mut limit_tracker = LimitTracker::new(&recorder, 200);
+
+        limit_tracker.set_value(183);
+
+        let events = recorder.events.borrow();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, 1);
+        assert_eq!(events[0].crossing, Crossing::Reached);
+        assert_eq!((events[0].value, events[0].max), (183, 200));
+        assert_eq!(events[0].percentage, 91.5);
+        assert_eq!(
+            events[0].to_string(),
+            "Urgent warning: You've used up over 90% of your quota!"
+        );
+    }
+
+    #[test]
+    fn a_trait_object_messenger_gets_the_formatted_message() {
+        let mock_messenger = MockMessenger::new();
+        let messenger: &dyn Messenger = &mock_messenger;
+        let mut limit_tracker = LimitTracker::new(messenger, 4);
+
+        limit_tracker.set_value(3);
+
+        assert_eq!(
+            *mock_messenger.sent_messages_rc.borrow(),
+            ["Warning: You've used up over 75% of your quota!"]
+        );
+    }
 }
@@ -0,0 +1,302 @@
+//! # Ready-made `Messenger`s for the trackers of `ch15::limit_tracker`
+//!     * `RecordingMessenger` keeps the messages, the `MockMessenger` of Ch15.5 behind a `Mutex` instead of a `RefCell`
+//!     * `WriteMessenger` writes each message as a line, e.g. to stderr with `WriteMessenger::stderr()`
+//!     * `FileMessenger` appends each message as a line to a file
+//!     * `ChannelMessenger` sends each message down an `mpsc` channel, see `ch16::messages`
+//!     * `FanOut` hands each event to several messengers
+//!     * all of them are `Send + Sync`, so they can also go in the `Arc` of a `SharedLimitTracker`
+//!     * `send` cannot fail, so a message that cannot be written or sent is dropped
+use crate::ch15::limit_tracker::{EventMessenger, Messenger, QuotaEvent};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Keeps every message it is sent, for tests and for reading them back later
+#[derive(Debug, Default)]
+pub struct RecordingMessenger {
+    messages: Mutex<Vec<String>>,
+}
+
+impl RecordingMessenger {
+    pub fn new() -> RecordingMessenger {
+        RecordingMessenger::default()
+    }
+
+    /// The messages sent so far, oldest first
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    /// Take the messages sent so far, leaving none
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.messages.lock().unwrap())
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Messenger for RecordingMessenger {
+    fn send(&self, msg: &str) {
+        self.messages.lock().unwrap().push(String::from(msg));
+    }
+}
+
+/// Writes each message as a line to a `Write`, flushed at once
+pub struct WriteMessenger<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> WriteMessenger<W> {
+    pub fn new(writer: W) -> WriteMessenger<W> {
+        WriteMessenger {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap()
+    }
+
+    fn write(&self, msg: &str) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{msg}")?;
+        writer.flush()
+    }
+}
+
+impl WriteMessenger<io::Stderr> {
+    pub fn stderr() -> WriteMessenger<io::Stderr> {
+        WriteMessenger::new(io::stderr())
+    }
+}
+
+impl<W: Write + Send> Messenger for WriteMessenger<W> {
+    fn send(&self, msg: &str) {
+        // - `send` has no way to report the error, see the module's notes
+        let _ = self.write(msg);
+    }
+}
+
+/// Appends each message as a line to a file, created if missing
+pub struct FileMessenger {
+    path: PathBuf,
+    writer: WriteMessenger<File>,
+}
+
+impl FileMessenger {
+    pub fn append_to(path: impl AsRef<Path>) -> io::Result<FileMessenger> {
+        let path = path.as_ref().to_path_buf();
+        // - `append` makes every write go to the end, even with other writers of the file
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(FileMessenger {
+            path,
+            writer: WriteMessenger::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Messenger for FileMessenger {
+    fn send(&self, msg: &str) {
+        self.writer.send(msg);
+    }
+}
+
+/// Sends each message down an `mpsc` channel, to be received on another thread
+pub struct ChannelMessenger {
+    sender: Sender<String>,
+}
+
+impl ChannelMessenger {
+    pub fn new(sender: Sender<String>) -> ChannelMessenger {
+        ChannelMessenger { sender }
+    }
+
+    /// A messenger and the receiving end of its channel
+    pub fn channel() -> (ChannelMessenger, Receiver<String>) {
+        let (sender, receiver) = mpsc::channel();
+        (ChannelMessenger::new(sender), receiver)
+    }
+}
+
+impl Messenger for ChannelMessenger {
+    fn send(&self, msg: &str) {
+        // - fails only once the receiver is dropped, when nobody is left to read the message
+        let _ = self.sender.send(String::from(msg));
+    }
+}
+
+/// Hands each event to several messengers, in the order they were added
+///     * an `EventMessenger` itself, so the typed events reach those that take them, and the others get the text
+#[derive(Default)]
+pub struct FanOut {
+    messengers: Vec<Arc<dyn EventMessenger + Send + Sync>>,
+}
+
+impl FanOut {
+    pub fn new() -> FanOut {
+        FanOut::default()
+    }
+
+    /// Add a messenger, e.g. `FanOut::new().with(Arc::new(WriteMessenger::stderr()))`
+    pub fn with(mut self, messenger: Arc<dyn EventMessenger + Send + Sync>) -> FanOut {
+        self.messengers.push(messenger);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.messengers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messengers.is_empty()
+    }
+}
+
+impl EventMessenger for FanOut {
+    fn notify(&self, event: &QuotaEvent) {
+        for messenger in &self.messengers {
+            messenger.notify(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ch15::limit_tracker::{LimitTracker, SharedLimitTracker, ThresholdPolicy};
+    use std::fs;
+    use std::thread;
+
+    #[test]
+    fn a_recording_messenger_keeps_the_messages_of_every_thread() {
+        let recorder = Arc::new(RecordingMessenger::new());
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let recorder = Arc::clone(&recorder);
+                thread::spawn(move || recorder.send(&format!("from thread {i}")))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut messages = recorder.messages();
+        messages.sort();
+        assert_eq!(
+            messages,
+            [
+                "from thread 0",
+                "from thread 1",
+                "from thread 2",
+                "from thread 3"
+            ]
+        );
+        assert_eq!(recorder.take().len(), 4);
+        assert!(recorder.is_empty());
+    }
+
+    #[test]
+    fn a_write_messenger_writes_one_line_per_message() {
+        let messenger = WriteMessenger::new(Vec::new());
+        let mut tracker = LimitTracker::new(&messenger, 100);
+
+        tracker.set_value(80);
+        tracker.set_value(100);
+
+        assert_eq!(
+            String::from_utf8(messenger.into_inner()).unwrap(),
+            "Warning: You've used up over 75% of your quota!\n\
+             Error: You are over your quota!\n"
+        );
+    }
+
+    #[test]
+    fn a_file_messenger_appends_to_the_file() {
+        let path = std::env::temp_dir().join(format!("quota-{}.log", std::process::id()));
+        fs::write(&path, "earlier line\n").unwrap();
+
+        {
+            let messenger = FileMessenger::append_to(&path).unwrap();
+            assert_eq!(messenger.path(), path);
+            messenger.send("first");
+            messenger.send("second");
+        }
+        let contents = fs::read_to_string(&path).unwrap();
+        // - a file is no directory, nothing can be opened under it
+        let under_a_file = FileMessenger::append_to(path.join("quota.log"));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents, "earlier line\nfirst\nsecond\n");
+        assert!(under_a_file.is_err());
+    }
+
+    #[test]
+    fn a_channel_messenger_sends_to_another_thread() {
+        let (messenger, receiver) = ChannelMessenger::channel();
+        let tracker = Arc::new(SharedLimitTracker::new(Arc::new(messenger), 10));
+
+        let producer = {
+            let tracker = Arc::clone(&tracker);
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    tracker.add(1);
+                }
+            })
+        };
+        producer.join().unwrap();
+        // - the tracker holds the only sender, dropping it ends the receiving loop
+        drop(tracker);
+
+        assert_eq!(
+            receiver.iter().collect::<Vec<_>>(),
+            [
+                "Warning: You've used up over 75% of your quota!",
+                "Urgent warning: You've used up over 90% of your quota!",
+                "Error: You are over your quota!",
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct LevelRecorder {
+        levels: Mutex<Vec<usize>>,
+    }
+
+    impl EventMessenger for LevelRecorder {
+        fn notify(&self, event: &QuotaEvent) {
+            self.levels.lock().unwrap().push(event.level);
+        }
+    }
+
+    #[test]
+    fn a_fan_out_hands_each_event_to_every_messenger() {
+        let first = Arc::new(RecordingMessenger::new());
+        let second = Arc::new(RecordingMessenger::new());
+        let levels = Arc::new(LevelRecorder::default());
+        let fan_out = FanOut::new()
+            .with(first.clone())
+            .with(second.clone())
+            .with(levels.clone());
+        assert_eq!(fan_out.len(), 3);
+        let policy = ThresholdPolicy::empty().threshold(50.0, "{percentage}% used");
+        let mut tracker = LimitTracker::with_policy(&fan_out, 10, policy);
+
+        tracker.set_value(6);
+
+        assert_eq!(first.messages(), ["60% used"]);
+        assert_eq!(second.messages(), ["60% used"]);
+        assert_eq!(*levels.levels.lock().unwrap(), [0]);
+    }
+}
@@ -1,6 +1,20 @@
 pub mod dref_trait;
 pub mod drop_trait;
 pub mod limit_tracker;
+pub mod messengers;
 pub mod pointers_to_heap;
 pub mod rc_pointers;
 pub mod refcell_pointers;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(pointers_to_heap::PointersToHeap {}),
+        Box::new(dref_trait::DerefTrait {}),
+        Box::new(drop_trait::DropTrait {}),
+        Box::new(rc_pointers::RCPointers {}),
+        Box::new(refcell_pointers::RefCellPointers {}),
+    ]
+}
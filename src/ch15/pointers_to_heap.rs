@@ -13,17 +13,19 @@
 //!     * resides on stack but
 //!     * points to data on heap, rather than stack
 //!     * no overhead or extra capabilities
+
+use std::io::{self, Write};
 #[derive(Debug)]
 #[allow(unused)]
 pub struct PointersToHeap {}
 
 #[allow(unused)]
 impl PointersToHeap {
-    pub fn print(&self) {
-        println!("\n======The note on pointers to heap======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on pointers to heap======")?;
         // Using a Box<T> to Store Data on the Heap
         let b = Box::new(5);
-        println!("\nb = {}", b);
+        outln!(out, "\nb = {}", b)?;
         // - after this point both of below are deallocated
         // - the pointer, b, on the stack
         // - and the pointee, 5, on the heap
@@ -37,5 +39,68 @@ impl PointersToHeap {
 
         use List::{Cons, Nil};
         let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`Box<T>` vs `std::unique_ptr<T>`",
+    cpp: "\
+#include <memory>
+
+struct List {
+    int value;
+    std::unique_ptr<List> next; // like `Cons(i32, Box<List>)`, null for `Nil`
+};
+
+int second(const List& list) {
+    return list.next->value; // undefined behaviour when `next` is null
+}
+
+std::unique_ptr<List> list() {
+    auto tail = std::make_unique<List>(List{2, nullptr});
+    return std::make_unique<List>(List{1, std::move(tail)});
+}
+",
+    pitfalls: &[
+        "a `unique_ptr` may be null, e.g. after a move, while a `Box<T>` always points to a value",
+        "the end of a list is a null pointer rather than a `Nil` variant the compiler makes `match` handle",
+    ],
+}];
+
+impl Lesson for PointersToHeap {
+    fn id(&self) -> &'static str {
+        "ch15::pointers_to_heap"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(15, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Using Box<T> to Point to Data on the Heap"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["smart-pointers"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::ownership", "ch6::define"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("pointers_to_heap.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
     }
 }
@@ -2,6 +2,8 @@
 //!     * Use `Rc<T>` to enable multiple ownership which keeps track of the number of references to a value
 //!         * to determine whether or not the value is still in use
 //!     * Cloning an `Rc<T>` Increases the Reference Count
+
+use std::io::{self, Write};
 #[derive(Debug)]
 #[allow(unused)]
 pub struct RCPointers {}
@@ -28,8 +30,11 @@ use List2::{Cons2, Nil2};
 
 #[allow(unused)]
 impl RCPointers {
-    pub fn print(&self) {
-        println!("\n======The note on reference counted smart pointer======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(
+            out,
+            "\n======The note on reference counted smart pointer======"
+        )?;
 
         // List b and c share List a so below code will not compile
         let a = Cons(5, Box::new(Cons(10, Box::new(Nil))));
@@ -38,24 +43,88 @@ impl RCPointers {
 
         // List2 b and c share a
         let a2 = Rc::new(Cons2(5, Rc::new(Cons2(10, Rc::new(Nil2)))));
-        println!("\ncount after creating a2 = {}", Rc::strong_count(&a2));
+        outln!(out, "\ncount after creating a2 = {}", Rc::strong_count(&a2))?;
         // - `Rc::clone` makes a copy of the `Rc<T>`, i.e., a
         let b2 = Cons2(3, Rc::clone(&a2));
-        println!("count after creating b2 = {}", Rc::strong_count(&a2));
+        outln!(out, "count after creating b2 = {}", Rc::strong_count(&a2))?;
         let c2 = Cons2(4, Rc::clone(&a2));
-        println!("count after creating c2 = {}", Rc::strong_count(&a2));
+        outln!(out, "count after creating c2 = {}", Rc::strong_count(&a2))?;
 
         {
             let d2 = Cons2(4, Rc::clone(&a2));
-            println!("count after creating c2 = {}", Rc::strong_count(&a2));
+            outln!(out, "count after creating c2 = {}", Rc::strong_count(&a2))?;
         }
-        println!(
+        outln!(
+            out,
             "count after c2 goes out of scope = {}",
             Rc::strong_count(&a2)
-        );
+        )?;
+
+        outln!(out, "\na2 is {:?}", a2)?;
+        outln!(out, "b2 is {:?}", b2)?;
+        outln!(out, "c2 is {:?}", c2)?;
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`Rc<T>` vs `std::shared_ptr<T>`",
+    cpp: "\
+#include <memory>
+
+struct List {
+    int value;
+    std::shared_ptr<List> next;
+};
+
+long count() {
+    auto a = std::make_shared<List>(List{5, std::make_shared<List>(List{10, nullptr})});
+    auto b = std::make_shared<List>(List{3, a}); // copying a `shared_ptr` is `Rc::clone`
+    auto c = std::make_shared<List>(List{4, a});
+    a->value = 6; // shared, yet mutable through any owner
+    return a.use_count(); // 3, like `Rc::strong_count(&a)`
+}
+",
+    pitfalls: &[
+        "`shared_ptr` counts atomically, at a cost even on one thread; Rust splits `Rc<T>` from `Arc<T>` and `Rc<T>` cannot cross threads",
+        "the shared value is mutable through every `shared_ptr` unless it is `shared_ptr<const T>`, `Rc<T>` only hands out `&T`",
+        "both leak on cycles, which `std::weak_ptr` and `Weak<T>` break",
+    ],
+}];
+
+impl Lesson for RCPointers {
+    fn id(&self) -> &'static str {
+        "ch15::rc_pointers"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(15, 4)
+    }
+
+    fn title(&self) -> &'static str {
+        "Rc<T>, the Reference Counted Smart Pointer"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["smart-pointers", "ownership"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch15::drop_trait"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("rc_pointers.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
 
-        println!("\na2 is {:?}", a2);
-        println!("b2 is {:?}", b2);
-        println!("c2 is {:?}", c2);
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
     }
 }
@@ -6,13 +6,75 @@
 //!             * `Rc<T>` allows only immutable borrows checked at compile time
 //!             * `RefCell<T>` allows immutable or mutable borrows checked at run time
 //!                 * so we can mutate value inside `RefCell<T>` even when it is immutable
+
+use std::io::{self, Write};
 #[derive(Debug)]
 #[allow(unused)]
 pub struct RefCellPointers {}
 
 impl RefCellPointers {
-    pub fn print(&self) {
-        println!("\n======The note on RefCell smart pointer======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on RefCell smart pointer======")?;
         // Interior Mutability: A mutable borrow to an Immutable Value
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`RefCell<T>` vs `mutable`",
+    cpp: "\
+#include <string>
+#include <vector>
+
+class MockMessenger {
+public:
+    void send(const std::string& message) const {
+        sent_messages.push_back(message); // allowed on a `const` object by `mutable`
+    }
+
+private:
+    mutable std::vector<std::string> sent_messages;
+};
+",
+    pitfalls: &[
+        "`mutable` is not checked at all, while `RefCell<T>` panics on a second `borrow_mut` while a borrow is alive",
+        "a reference into a `mutable` member, e.g. to `sent_messages[0]`, can dangle once `send` reallocates the vector",
+    ],
+}];
+
+impl Lesson for RefCellPointers {
+    fn id(&self) -> &'static str {
+        "ch15::refcell_pointers"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(15, 5)
+    }
+
+    fn title(&self) -> &'static str {
+        "RefCell<T> and the Interior Mutability Pattern"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["smart-pointers", "borrowing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch15::rc_pointers"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("refcell_pointers.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
     }
 }
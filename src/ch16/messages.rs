@@ -1,7 +1,10 @@
 //! # Ch16.2 - Using Message Passing to Transfer Data Between Threads
 
-use std::sync::mpsc;
-use std::thread;
+use crate::lesson::SharedOutput;
+// - `thread` and `mpsc` are `crate::schedule`: its `spawn`, `sleep` and `channel` are `std`'s, unless `run --record`/`--replay`/`--fast-forward` schedules the threads
+use crate::schedule as mpsc;
+use crate::schedule as thread;
+use std::io::{self, Write};
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -10,8 +13,10 @@ pub struct Messages {}
 
 #[allow(unused)]
 impl Messages {
-    pub fn print(&self) {
-        println!("\n======The note on messages between threads======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on messages between threads======")?;
+        // - spawned threads cannot borrow `out`, so a thread that writes is handed its own `out.writer()`
+        let mut out = SharedOutput::new(out);
         // Create a channel using `mpsc::channel` function
         // - `mpsc` stands for multiple producer, single consumer,i.e.,
         // - a channel can have multiple sending ends/transmitter but only one receiving end/receiver
@@ -19,9 +24,10 @@ impl Messages {
         let (tx, rx) = mpsc::channel();
 
         // Moving a transmitter, `tx` to a spawned thread and sending "hi"
+        let mut thread_out = out.writer();
         thread::spawn(move || {
             let val = String::from("hi");
-            println!("\nSending: {} from a spawned thread", val);
+            outln!(thread_out, "\nSending: {} from a spawned thread", val).unwrap();
             tx.send(val).unwrap();
             // - after sending, val is unusable in this scope so below code does not compile
             // println!("After sending, val is {} in this spawned thread", val);
@@ -35,7 +41,7 @@ impl Messages {
         // - an `Ok` holding the value/message received, an `Error` if there is not any this time.
         // - we could write loop of `try_recv` to check message
         let received = rx.recv().unwrap();
-        println!("Got: {} in main thread", received);
+        outln!(out, "Got: {} in main thread", received)?;
 
         // Sending Multiple Values and Seeing the Receiver Waiting
         let (tx, rx) = mpsc::channel();
@@ -55,9 +61,9 @@ impl Messages {
 
         // - using receiver `rx` as iterator, not calling `recv` method
         // - main thread wait for each value sent from spawned thread
-        println!("");
+        outln!(out)?;
         for received in rx {
-            println!("Got: {}", received);
+            outln!(out, "Got: {}", received)?;
         }
 
         // Creating Multiple Producers by Cloning the Transmitter
@@ -94,9 +100,42 @@ impl Messages {
             }
         });
 
-        println!("");
+        outln!(out)?;
         for received in rx {
-            println!("Got: {}", received);
+            outln!(out, "Got: {}", received)?;
         }
+        out.flush()
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Messages {
+    fn id(&self) -> &'static str {
+        "ch16::messages"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(16, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Using Message Passing to Transfer Data Between Threads"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["concurrency"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch16::threads"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("messages.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
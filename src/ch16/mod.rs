@@ -1,3 +1,15 @@
+#![allow(clippy::assign_op_pattern)]
 pub mod messages;
 pub mod mutexes;
 pub mod threads;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(threads::Threads {}),
+        Box::new(messages::Messages {}),
+        Box::new(mutexes::Mutexes {}),
+    ]
+}
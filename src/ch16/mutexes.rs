@@ -25,6 +25,7 @@
 //!         * `Mutex<T>` is both `Send` and `Sync`
 //!         * `MutexGuard<'a, T>`, that is returned by `Mutex::lock` is `Sync` if `T: Sync` but not `Send`
 
+use std::io::{self, Write};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
@@ -34,8 +35,8 @@ pub struct Mutexes {}
 
 #[allow(unused)]
 impl Mutexes {
-    pub fn print(&self) {
-        println!("\n======The note on mutex======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on mutex======")?;
         // Create a mutex
         let m = Mutex::new(5);
         {
@@ -51,7 +52,7 @@ impl Mutexes {
             *num = 6;
         }
 
-        println!("m = {:?}", m);
+        outln!(out, "m = {:?}", m)?;
 
         // Sharing Mutex<T> Between Multiple Threads
         // - a `Mutex` obj that multiple threads will access and mutate
@@ -60,6 +61,7 @@ impl Mutexes {
         // - a `Rc` over `Mutex` obj will enable multiple ownerships so future use would be available
         // - but Rust complains about unsafety with sending a `Rc` obj between threads
         // - because `Rc` does not implement `Send` trait
+        // - the rejection (E0277) is checked by `tests/compile_fail/ch16_rc_across_threads.rs`
         // let counter = Rc::new(Mutex::new(0));
         // - finally a `Arc<T>` is a type like `Rc<T>` that is safe to use in concurrent situations.
         // - a for atomic, see details in `std::sync::atomic`
@@ -84,17 +86,110 @@ impl Mutexes {
         }
 
         // - if `count` is simply a `Mutex` obj, below code will not compile because it was moved to the closure in `thread::spawn`
-        println!("Result: {}", *counter.lock().unwrap());
+        outln!(out, "Result: {}", *counter.lock().unwrap())?;
 
         // Quiz
         // - `Arc<T>` is still not safe for concurent situations if its data contains reference
         // - so below code will not compile
 
+        // - the rejection (E0597) is checked by `tests/compile_fail/ch16_arc_of_reference.rs`
         // let s = String::from("Hello world");
         // let a = Arc::new(&s);
         // let a2 = Arc::clone(&a);
         // let t = thread::spawn(move || a2.len());
         // let len = t.join().unwrap();
         // println!("{} {}", a, len);
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::Quiz;
+
+const QUIZZES: &[Quiz] = &[Quiz {
+    question: "What happens with this program?",
+    snippet: "\
+let s = String::from(\"Hello world\");
+let a = Arc::new(&s);
+let a2 = Arc::clone(&a);
+let t = thread::spawn(move || a2.len());
+let len = t.join().unwrap();
+println!(\"{} {}\", a, len);",
+    choices: &[
+        "It prints `Hello world 11`",
+        "It does not compile, `s` does not live long enough",
+        "It does not compile, `Arc<&String>` cannot be cloned",
+        "It compiles but may panic when the thread reads `s`",
+    ],
+    answer: 1,
+    explanation: "\
+`Arc` shares ownership of its contents, here a `&String`, not of `s` itself;
+`thread::spawn` requires a `'static` closure, and a borrow of the local `s` is not `'static` (E0597).",
+}];
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`Mutex<T>` vs `std::mutex` next to the data",
+    cpp: "\
+#include <mutex>
+#include <thread>
+#include <vector>
+
+int count() {
+    int counter = 0;
+    std::mutex m; // guards `counter` by convention only
+    std::vector<std::thread> handles;
+    for (int i = 0; i < 10; ++i) {
+        handles.emplace_back([&] {
+            std::lock_guard<std::mutex> guard(m);
+            ++counter;
+        });
+    }
+    for (auto& handle : handles) handle.join();
+    return counter; // nothing stops an access without the lock, e.g. here before the joins
+}
+",
+    pitfalls: &[
+        "the mutex and the data it guards are separate, `Mutex<T>` owns the data so it is reachable only through `lock`",
+        "the lambda captures `counter` by reference, Rust requires `Arc` for data shared with threads that could outlive it",
+        "forgetting the `lock_guard` is a data race the compiler accepts, forgetting `lock` in Rust does not compile",
+    ],
+}];
+
+impl Lesson for Mutexes {
+    fn id(&self) -> &'static str {
+        "ch16::mutexes"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(16, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Shared-State Concurrency"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["concurrency", "smart-pointers"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch15::rc_pointers", "ch16::threads"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("mutexes.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn quizzes(&self) -> &'static [Quiz] {
+        QUIZZES
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
     }
 }
@@ -2,7 +2,10 @@
 //! ## Rust standard library uses 1:1 model of thread implementation
 //!     * i.e., a program uses one operating syytem thread per one language thread
 //!     * there are crates that implement other models of threading
-use std::thread;
+use crate::lesson::SharedOutput;
+// - `thread` is `crate::schedule`: its `spawn` and `sleep` are `std::thread`'s, unless `run --record`/`--replay`/`--fast-forward` schedules the threads
+use crate::schedule as thread;
+use std::io::{self, Write};
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -11,44 +14,50 @@ pub struct Threads {}
 
 #[allow(unused)]
 impl Threads {
-    pub fn print(&self) {
-        println!("\n======The note on threads======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on threads======")?;
+        // - spawned threads cannot borrow `out`, so each one is handed its own `out.writer()`
+        let mut out = SharedOutput::new(out);
         // Creating a New Thread with `std::thread::spawn`
         // - takes a closure containing the code we want to run in the new thread
         // - caveats
         // - main threading ending stops the spawned thread, prematurely sometime
         // - no guarantee on the order in which threads run, or run at all
         // - i.e., run in new thread
-        thread::spawn(|| {
+        let mut thread_out = out.writer();
+        thread::spawn(move || -> io::Result<()> {
             for i in 1..10 {
-                println!("hi number {} from the spawned thread!", i);
+                outln!(thread_out, "hi number {} from the spawned thread!", i)?;
                 thread::sleep(Duration::from_millis(1));
             }
+            Ok(())
         });
         // - run in main thread
         for i in 1..5 {
-            println!("hi number {} from the main thread!", i);
+            outln!(out, "hi number {} from the main thread!", i)?;
             thread::sleep(Duration::from_millis(1));
         }
         // Waiting for All Threads to Finish Using `join` Handles
         // - return value of `thread::spawn` is a `JoinHandle` type
         // - when we call `join` method on it, it will wait for its thread to finish
         // - by blocking the thread that is currently running, i.e., preventing it from performing or exiting
-        println!("");
-        let handle = thread::spawn(|| {
+        outln!(out)?;
+        let mut thread_out = out.writer();
+        let handle = thread::spawn(move || -> io::Result<()> {
             for i in 1..10 {
-                println!("hi number {} from the spawned thread!", i);
+                outln!(thread_out, "hi number {} from the spawned thread!", i)?;
                 thread::sleep(Duration::from_millis(1));
             }
+            Ok(())
         });
 
         for i in 1..5 {
-            println!("hi number {} from the main thread!", i);
+            outln!(out, "hi number {} from the main thread!", i)?;
             thread::sleep(Duration::from_millis(1));
         }
 
         // - `join()` here is blocking main threading from performing work or exiting until the thread represented by `handle` finishes
-        handle.join().unwrap();
+        handle.join().unwrap()?;
 
         // Using `move` Closures with Threads
         // - closures take ownership of values from environment
@@ -61,11 +70,16 @@ impl Threads {
         //     println!("Here's a vector: {:?}", v);
         // });
 
+        let mut thread_out = out.writer();
         let handle = thread::spawn(move || {
-            println!("\nHere's a vector taken from main thread: {:?}", v);
+            outln!(
+                thread_out,
+                "\nHere's a vector taken from main thread: {:?}",
+                v
+            )
         });
 
-        handle.join().unwrap();
+        handle.join().unwrap()?;
 
         // Quiz
         // - when i32 is taken ownership, its content is copied so in this case, n will be 2 at the end
@@ -78,6 +92,64 @@ impl Threads {
         });
         n = n + 1;
         t.join().unwrap().join().unwrap();
-        println!("{n}");
+        outln!(out, "{n}")?;
+        out.flush()
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+use crate::quiz::Quiz;
+
+const QUIZZES: &[Quiz] = &[Quiz {
+    question: "What does this program print?",
+    snippet: "\
+let mut n = 1;
+let t = thread::spawn(move || {
+    n = n + 1;
+    thread::spawn(move || {
+        n = n + 1;
+    })
+});
+n = n + 1;
+t.join().unwrap().join().unwrap();
+println!(\"{n}\");",
+    choices: &["1", "2", "3", "It does not compile, `n` is moved into the closure"],
+    answer: 1,
+    explanation: "\
+`i32` is `Copy`, so each `move` closure gets its own copy of `n`;
+the increments inside the threads change those copies, and only `n = n + 1` on the main thread touches the printed `n`.",
+}];
+
+impl Lesson for Threads {
+    fn id(&self) -> &'static str {
+        "ch16::threads"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(16, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Using Threads to Run Code Simultaneously"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["concurrency", "closures"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch13::closures"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("threads.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn quizzes(&self) -> &'static [Quiz] {
+        QUIZZES
     }
 }
@@ -1,5 +1,7 @@
 //! Ch17.1 - Using Trait Objects That Allow for Values of Different Types
 
+use std::io::{self, Write};
+
 // Create a public trait
 #[allow(unused)]
 pub trait Draw {
@@ -69,8 +71,8 @@ pub struct DynTraits{
 
 #[allow(unused)]
 impl DynTraits{
-    pub fn print(&self) {
-        println!("\n======The note on dyn traits======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on dyn traits======")?;
     // Using trait
         let screen = Screen {
             components: vec![
@@ -95,6 +97,7 @@ impl DynTraits{
     // Using trait
         // - in this situation, compile does not know components accepts any type that implement `Draw`
         // - because we are define components outside a `Screen` so compile loses that information
+        // - the rejection (E0308) is checked by `tests/compile_fail/ch17_untyped_components.rs`
         // let components = vec![
         //     Box::new(SelectBox {
         //         width: 75,
@@ -155,5 +158,73 @@ impl DynTraits{
         screen2.run();
         let screen3 = Screen {components: components3};
         screen3.run();
+        Ok(())
+    }
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "trait objects vs virtual functions",
+    cpp: "\
+#include <memory>
+#include <vector>
+
+class Draw {
+public:
+    virtual ~Draw() = default; // without it, deleting through `Draw*` is undefined behaviour
+    virtual void draw() const = 0;
+};
+
+class Button : public Draw {
+public:
+    void draw() const override {}
+};
+
+void run() {
+    std::vector<std::unique_ptr<Draw>> components; // like `Vec<Box<dyn Draw>>`
+    components.push_back(std::make_unique<Button>());
+    for (const auto& component : components) component->draw();
+}
+",
+    pitfalls: &[
+        "the vtable pointer lives in every C++ object, Rust keeps it in the fat `&dyn Draw` pointer so `Button` stays a plain struct",
+        "a class must inherit from `Draw` up front, a Rust trait can be implemented for a type after the fact",
+        "a base class without a virtual destructor compiles, `Box<dyn Draw>` always drops the concrete type",
+    ],
+}];
+
+impl Lesson for DynTraits {
+    fn id(&self) -> &'static str {
+        "ch17::dyn_traits"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(17, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Using Trait Objects That Allow for Values of Different Types"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["traits", "trait-objects"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch10::traits", "ch15::pointers_to_heap"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("dyn_traits.rs")
     }
-}
\ No newline at end of file
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
+    }
+}
@@ -1 +1,11 @@
-pub mod dyn_traits;
\ No newline at end of file
+#![allow(dead_code)]
+pub mod dyn_traits;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(dyn_traits::DynTraits {}),
+    ]
+}
@@ -0,0 +1,23 @@
+#[allow(unused_variables, clippy::useless_vec)]
+pub mod places;
+pub mod playground;
+#[allow(clippy::manual_unwrap_or, clippy::manual_unwrap_or_default)]
+pub mod refutability;
+#[allow(
+    dead_code,
+    clippy::manual_range_patterns,
+    clippy::match_single_binding,
+    clippy::redundant_pattern_matching
+)]
+pub mod syntax;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(places::Places {}),
+        Box::new(refutability::Refutability {}),
+        Box::new(syntax::Syntax {}),
+    ]
+}
@@ -0,0 +1,111 @@
+//! # Ch18.1 - All the Places Patterns Can Be Used
+//!     * a pattern matches the shape of a value, made of literals, destructured arrays, enums, structs or tuples, variables, wildcards and placeholders
+//!     * `match` arms: `match VALUE { PATTERN => EXPRESSION, ... }`
+//!         * the arms together must be exhaustive, `_` matches anything left and binds nothing
+//!     * conditional `if let` expressions, mixed freely with `else if` and `else if let`
+//!         * the compiler does not check `if let` chains for exhaustiveness
+//!     * `while let` conditional loops, run as long as the pattern matches
+//!     * `for` loops, the pattern follows `for`, e.g. `for (index, value) in v.iter().enumerate()`
+//!     * `let` statements: `let PATTERN = EXPRESSION;`, so `let x = 5;` is a pattern that binds everything to `x`
+//!         * the pattern must have as many elements as the tuple, otherwise E0308
+//!     * function and closure parameters are patterns too, e.g. `fn print_coordinates(&(x, y): &(i32, i32))`
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub struct Places {}
+
+impl Places {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on the places of patterns======")?;
+        // Conditional `if let` expressions
+        let favorite_color: Option<&str> = None;
+        let is_tuesday = false;
+        let age: Result<u8, _> = "34".parse();
+
+        // - `if let Ok(age) = age` shadows `age`, the new `age` is only valid inside the braces
+        // - so `if let Ok(age) = age && age > 30` was not allowed before the let chains of Rust 2024
+        if let Some(color) = favorite_color {
+            outln!(out, "Using your favorite color, {color}, as the background")?;
+        } else if is_tuesday {
+            outln!(out, "Tuesday is green day!")?;
+        } else if let Ok(age) = age {
+            if age > 30 {
+                outln!(out, "Using purple as the background color")?;
+            } else {
+                outln!(out, "Using orange as the background color")?;
+            }
+        } else {
+            outln!(out, "Using blue as the background color")?;
+        }
+
+        // `while let` conditional loops
+        let mut stack = Vec::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        // - `pop` returns `None` once the stack is empty, which ends the loop
+        while let Some(top) = stack.pop() {
+            outln!(out, "{top}")?;
+        }
+
+        // `for` loops
+        let v = vec!['a', 'b', 'c'];
+        // - `enumerate` yields `(index, value)` tuples, destructured by the pattern
+        for (index, value) in v.iter().enumerate() {
+            outln!(out, "{value} is at index {index}")?;
+        }
+
+        // `let` statements
+        let (x, y, z) = (1, 2, 3);
+        outln!(out, "x = {x}, y = {y}, z = {z}")?;
+        // - too few elements in the pattern is a mismatched types error (E0308)
+        // let (x, y) = (1, 2, 3);
+        // - `..` ignores the rest, see `ch18::syntax`
+        let (x, ..) = (1, 2, 3);
+
+        // Function parameters
+        // - `&(x, y)` matches a reference to a tuple and binds the values it points to
+        fn print_coordinates(&(x, y): &(i32, i32), out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "Current location: ({x}, {y})")
+        }
+        let point = (3, 5);
+        print_coordinates(&point, out)?;
+
+        // - closures take patterns as well
+        let sum: i32 = [(1, 2), (3, 4)].iter().map(|&(a, b)| a * b).sum();
+        outln!(out, "Sum of the products: {sum}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Places {
+    fn id(&self) -> &'static str {
+        "ch18::places"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(18, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "All the Places Patterns Can Be Used"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["pattern-matching"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch6::match_control_flow", "ch13::iterators"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("places.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
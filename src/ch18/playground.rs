@@ -0,0 +1,1648 @@
+//! # Pattern playground
+//!     * `evaluate` takes a value and the arms of a `match`, and reports which arm matches, what it binds, and which arms never can
+//!         * e.g. the value `Location::Range(0, 5)` and the arms of `ch6::match_control_flow`
+//!     * values and patterns are written as in Rust, over the primitives and the types of the notes, see `TYPES`
+//!         * integers are `i64`s, so `0..=255` is not exhaustive even where the book's value is a `u8`
+//!         * patterns: `_`, bindings, `name @ pattern`, literals, `a..=b`, `p | q`, and `..` in tuples, variants and structs
+//!         * an arm may have a guard, e.g. `Some(x) if x % 2 == 0`, computing with integers and comparing
+//!     * an arm is unreachable when the arms above it match every value it matches, rustc's `unreachable pattern` warning
+//!         * decided as rustc does, by asking whether the arm is "useful": is there a value it matches that no arm above does?
+//!         * an arm with a guard never covers anything, the compiler cannot know what its guard says
+//!         * asking the same about a `_` after the last arm tells whether the match is exhaustive, rustc's E0004
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A constructor of one of the playground's types: a variant of an enum, or a struct
+#[derive(Debug, PartialEq)]
+pub struct Ctor {
+    /// The type it builds, e.g. `Coin`
+    pub ty: &'static str,
+    /// How it is written, e.g. `Coin::Quarter`
+    pub path: &'static str,
+    /// Names and types of the fields; tuple fields are named `0`, `1`, ... and `T` is `Option`'s parameter
+    pub fields: &'static [(&'static str, &'static str)],
+}
+
+impl Ctor {
+    /// Whether the fields have names, as in `Point { x, y }`
+    fn named(&self) -> bool {
+        self.fields
+            .first()
+            .is_some_and(|(name, _)| name.parse::<usize>().is_err())
+    }
+}
+
+/// Every type besides the primitives, from `ch6`, `ch18` and the standard library
+#[rustfmt::skip]
+pub const TYPES: &[Ctor] = &[
+    Ctor { ty: "UsState", path: "UsState::Alabama", fields: &[] },
+    Ctor { ty: "UsState", path: "UsState::Alaska", fields: &[] },
+    Ctor { ty: "Coin", path: "Coin::Penny", fields: &[] },
+    Ctor { ty: "Coin", path: "Coin::Nickel", fields: &[] },
+    Ctor { ty: "Coin", path: "Coin::Dime", fields: &[] },
+    Ctor { ty: "Coin", path: "Coin::Quarter", fields: &[("0", "UsState")] },
+    Ctor { ty: "Location", path: "Location::Point", fields: &[("0", "i64")] },
+    Ctor { ty: "Location", path: "Location::Range", fields: &[("0", "i64"), ("1", "i64")] },
+    Ctor { ty: "Option", path: "Some", fields: &[("0", "T")] },
+    Ctor { ty: "Option", path: "None", fields: &[] },
+    Ctor { ty: "Point", path: "Point", fields: &[("x", "i64"), ("y", "i64")] },
+    Ctor { ty: "Color", path: "Color::Rgb", fields: &[("0", "i64"), ("1", "i64"), ("2", "i64")] },
+    Ctor { ty: "Color", path: "Color::Hsv", fields: &[("0", "i64"), ("1", "i64"), ("2", "i64")] },
+    Ctor { ty: "Message", path: "Message::Quit", fields: &[] },
+    Ctor { ty: "Message", path: "Message::Move", fields: &[("x", "i64"), ("y", "i64")] },
+    Ctor { ty: "Message", path: "Message::Write", fields: &[("0", "&str")] },
+    Ctor { ty: "Message", path: "Message::ChangeColor", fields: &[("0", "Color")] },
+];
+
+fn find_ctor(path: &str) -> Result<&'static Ctor, String> {
+    if let Some(ctor) = TYPES.iter().find(|ctor| ctor.path == path) {
+        return Ok(ctor);
+    }
+    match path.split_once("::") {
+        Some((ty, variant)) if TYPES.iter().any(|ctor| ctor.ty == ty) => {
+            Err(format!("no variant `{variant}` in `{ty}`"))
+        }
+        _ => {
+            let mut types: Vec<&str> = TYPES.iter().map(|ctor| ctor.ty).collect();
+            types.dedup();
+            Err(format!(
+                "unknown type or variant `{path}`, the playground knows {}",
+                types.join(", ")
+            ))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Char(char),
+    Bool(bool),
+    Str(String),
+    Tuple(Vec<Value>),
+    /// A variant or a struct, with its fields in declaration order
+    Adt(&'static Ctor, Vec<Value>),
+}
+
+/// `a, b, c`
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{n}"),
+            Value::Char(c) => write!(f, "{c:?}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Str(s) => write!(f, "{s:?}"),
+            Value::Tuple(items) => {
+                write!(f, "(")?;
+                write_list(f, items)?;
+                if items.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Value::Adt(ctor, fields) if fields.is_empty() => write!(f, "{}", ctor.path),
+            Value::Adt(ctor, fields) if ctor.named() => {
+                write!(f, "{} {{ ", ctor.path)?;
+                for (i, ((name, _), value)) in ctor.fields.iter().zip(fields).enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: {value}")?;
+                }
+                write!(f, " }}")
+            }
+            Value::Adt(ctor, fields) => {
+                write!(f, "{}(", ctor.path)?;
+                write_list(f, fields)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Types, as far as the playground needs them
+#[derive(Debug, Clone, PartialEq)]
+enum Ty {
+    Int,
+    Char,
+    Bool,
+    Str,
+    Tuple(Vec<Ty>),
+    /// A type of `TYPES`, with `Option`'s parameter when it is known
+    Adt(&'static str, Option<Box<Ty>>),
+    /// The `T` of a `None`
+    Unknown,
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ty::Int => write!(f, "i64"),
+            Ty::Char => write!(f, "char"),
+            Ty::Bool => write!(f, "bool"),
+            Ty::Str => write!(f, "&str"),
+            Ty::Tuple(items) => {
+                write!(f, "(")?;
+                write_list(f, items)?;
+                if items.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Ty::Adt(name, Some(param)) => write!(f, "{name}<{param}>"),
+            Ty::Adt(name, None) if *name == "Option" => write!(f, "Option<_>"),
+            Ty::Adt(name, None) => write!(f, "{name}"),
+            Ty::Unknown => write!(f, "_"),
+        }
+    }
+}
+
+fn type_of(value: &Value) -> Ty {
+    match value {
+        Value::Int(_) => Ty::Int,
+        Value::Char(_) => Ty::Char,
+        Value::Bool(_) => Ty::Bool,
+        Value::Str(_) => Ty::Str,
+        Value::Tuple(items) => Ty::Tuple(items.iter().map(type_of).collect()),
+        Value::Adt(ctor, fields) => {
+            let param = ctor
+                .fields
+                .iter()
+                .zip(fields)
+                .find(|((_, ty), _)| *ty == "T")
+                .map(|(_, value)| Box::new(type_of(value)));
+            Ty::Adt(ctor.ty, param)
+        }
+    }
+}
+
+/// The type of the `i`th field of `ctor`, in a value of type `Option<param>` for `Some`
+fn field_ty(ctor: &Ctor, i: usize, param: &Option<Box<Ty>>) -> Ty {
+    match ctor.fields[i].1 {
+        "i64" => Ty::Int,
+        "char" => Ty::Char,
+        "bool" => Ty::Bool,
+        "&str" => Ty::Str,
+        "T" => param.as_deref().cloned().unwrap_or(Ty::Unknown),
+        name => Ty::Adt(name, None),
+    }
+}
+
+/// Whether a value of type `found` can stand where `expected` is
+fn fits(found: &Ty, expected: &Ty) -> bool {
+    match (found, expected) {
+        (_, Ty::Unknown) | (Ty::Unknown, _) => true,
+        (Ty::Tuple(a), Ty::Tuple(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| fits(a, b))
+        }
+        (Ty::Adt(a, pa), Ty::Adt(b, pb)) => {
+            a == b
+                && match (pa, pb) {
+                    (Some(pa), Some(pb)) => fits(pa, pb),
+                    _ => true,
+                }
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn mismatch(expected: &Ty, found: impl fmt::Display) -> String {
+    format!("mismatched types: expected `{expected}`, found `{found}`")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Unsigned, a `-` before it is read with the literal so that `i64::MIN` fits
+    Int(u64),
+    Char(char),
+    Str(String),
+    Ident(String),
+    Punct(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Int(n) => write!(f, "{n}"),
+            Token::Char(c) => write!(f, "{c:?}"),
+            Token::Str(s) => write!(f, "{s:?}"),
+            Token::Ident(id) => write!(f, "{id}"),
+            Token::Punct(p) => write!(f, "{p}"),
+        }
+    }
+}
+
+/// Longest first, so that `..=` is not read as `..` and `=`
+const PUNCTS: [&str; 27] = [
+    "..=", "::", "..", "==", "!=", "<=", ">=", "&&", "||", "=>", "(", ")", "{", "}", ",", ":", "|",
+    "@", "<", ">", "+", "-", "*", "/", "%", "!", "&",
+];
+
+/// The character after a `\`
+fn unescape(c: Option<&char>) -> Result<char, String> {
+    match c {
+        Some('n') => Ok('\n'),
+        Some('t') => Ok('\t'),
+        Some('r') => Ok('\r'),
+        Some('0') => Ok('\0'),
+        Some(c @ ('\\' | '\'' | '"')) => Ok(*c),
+        Some(c) => Err(format!("unknown escape `\\{c}`")),
+        None => Err(String::from("unterminated escape")),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().filter(|&&c| c != '_').collect();
+            let n = digits
+                .parse()
+                .map_err(|_| format!("`{digits}` does not fit an i64"))?;
+            tokens.push(Token::Int(n));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '\'' {
+            let (c, len) = match chars.get(i + 1) {
+                Some('\\') => (unescape(chars.get(i + 2))?, 2),
+                Some(&c) => (c, 1),
+                None => return Err(String::from("unterminated char")),
+            };
+            if chars.get(i + 1 + len) != Some(&'\'') {
+                return Err(String::from("a char is one character between `'`s"));
+            }
+            tokens.push(Token::Char(c));
+            i += len + 2;
+        } else if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    Some('"') => break,
+                    Some('\\') => {
+                        s.push(unescape(chars.get(i + 1))?);
+                        i += 2;
+                    }
+                    Some(&c) => {
+                        s.push(c);
+                        i += 1;
+                    }
+                    None => return Err(String::from("unterminated string")),
+                }
+            }
+            tokens.push(Token::Str(s));
+            i += 1;
+        } else {
+            let rest: String = chars[i..].iter().take(3).collect();
+            match PUNCTS.iter().find(|punct| rest.starts_with(*punct)) {
+                Some(punct) => {
+                    tokens.push(Token::Punct(punct));
+                    i += punct.len();
+                }
+                None => return Err(format!("unexpected `{c}`")),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// A pattern as written, before it is checked against the type of the value
+#[derive(Debug, Clone)]
+enum Ast {
+    Wild,
+    /// `..` in a list of fields
+    Rest,
+    Binding(String, Option<Box<Ast>>),
+    Lit(Value),
+    Range(Value, Value),
+    Or(Vec<Ast>),
+    Tuple(Vec<Ast>),
+    /// A unit variant, e.g. `None`
+    Path(String),
+    TupleStruct(String, Vec<Ast>),
+    /// Named fields, and whether `..` leaves the others out
+    Struct(String, Vec<(String, Ast)>, bool),
+}
+
+/// A guard's expression
+#[derive(Debug, Clone)]
+enum Expr {
+    Lit(Value),
+    Var(String),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+/// Binary operators from the loosest to the tightest
+const LEVELS: [&[&str]; 5] = [
+    &["||"],
+    &["&&"],
+    &["==", "!=", "<", "<=", ">", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+/// How deep patterns and guards may nest
+///     * well within the 2 MiB stack of a spawned thread, e.g. a test's, where 30k nested parentheses would overflow it
+const MAX_DEPTH: usize = 128;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Parser, String> {
+        Ok(Parser {
+            tokens: tokenize(source)?,
+            pos: 0,
+            depth: 0,
+        })
+    }
+
+    /// One level deeper, an error past `MAX_DEPTH`
+    fn deeper(&mut self) -> Result<(), String> {
+        if self.depth == MAX_DEPTH {
+            return Err(format!("nested more than {MAX_DEPTH} deep"));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Run `parse` one level deeper
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Parser) -> Result<T, String>,
+    ) -> Result<T, String> {
+        self.deeper()?;
+        let parsed = parse(self);
+        self.depth -= 1;
+        parsed
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Punct(p)) if *p == punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Ident(id)) if id == keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Punct(p)) if p == punct => Ok(()),
+            Some(token) => Err(format!("expected `{punct}`, found `{token}`")),
+            None => Err(format!("expected `{punct}`, found the end")),
+        }
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(format!("unexpected `{token}`")),
+        }
+    }
+
+    /// `p | q | ...`
+    fn pattern(&mut self) -> Result<Ast, String> {
+        self.eat("|");
+        let mut alternatives = vec![self.single()?];
+        while self.eat("|") {
+            alternatives.push(self.single()?);
+        }
+        Ok(match alternatives.len() {
+            1 => alternatives.pop().unwrap(),
+            _ => Ast::Or(alternatives),
+        })
+    }
+
+    /// Every nested pattern goes through here, so this is where the depth is kept
+    fn single(&mut self) -> Result<Ast, String> {
+        self.nested(Parser::single_at_depth)
+    }
+
+    fn single_at_depth(&mut self) -> Result<Ast, String> {
+        let token = self.next();
+        match token {
+            Some(Token::Punct("..")) => Ok(Ast::Rest),
+            Some(Token::Punct("(")) => {
+                let (mut items, trailing_comma) = self.list(")")?;
+                // - `(p)` is `p`, `(p,)` is a tuple of one
+                if items.len() == 1 && !trailing_comma && !matches!(items[0], Ast::Rest) {
+                    Ok(items.pop().unwrap())
+                } else {
+                    Ok(Ast::Tuple(items))
+                }
+            }
+            Some(Token::Ident(id)) if id == "_" => Ok(Ast::Wild),
+            // - `ref` and `mut` change how a binding binds, not what it matches
+            Some(Token::Ident(id)) if id == "ref" || id == "mut" => self.single(),
+            Some(Token::Ident(id)) if id != "true" && id != "false" => {
+                let mut path = id;
+                while self.eat("::") {
+                    match self.next() {
+                        Some(Token::Ident(segment)) => {
+                            path.push_str("::");
+                            path.push_str(&segment);
+                        }
+                        _ => return Err(format!("expected a name after `{path}::`")),
+                    }
+                }
+                // - a lowercase name binds, like rustc we tell bindings from unit variants by the case
+                if !path.contains("::") && path.starts_with(|c: char| c.is_lowercase() || c == '_')
+                {
+                    let sub = match self.eat("@") {
+                        true => Some(Box::new(self.single()?)),
+                        false => None,
+                    };
+                    Ok(Ast::Binding(path, sub))
+                } else if self.eat("(") {
+                    Ok(Ast::TupleStruct(path, self.list(")")?.0))
+                } else if self.eat("{") {
+                    let (fields, rest) = self.fields()?;
+                    Ok(Ast::Struct(path, fields, rest))
+                } else {
+                    Ok(Ast::Path(path))
+                }
+            }
+            token => {
+                let low = self.literal(token)?;
+                if self.eat("..=") {
+                    let token = self.next();
+                    Ok(Ast::Range(low, self.literal(token)?))
+                } else {
+                    Ok(Ast::Lit(low))
+                }
+            }
+        }
+    }
+
+    fn literal(&mut self, token: Option<Token>) -> Result<Value, String> {
+        match token {
+            Some(Token::Int(n)) => i64::try_from(n)
+                .map(Value::Int)
+                .map_err(|_| format!("`{n}` does not fit an i64")),
+            Some(Token::Punct("-")) => match self.next() {
+                Some(Token::Int(n)) => 0i64
+                    .checked_sub_unsigned(n)
+                    .map(Value::Int)
+                    .ok_or_else(|| format!("`-{n}` does not fit an i64")),
+                _ => Err(String::from("expected a number after `-`")),
+            },
+            Some(Token::Char(c)) => Ok(Value::Char(c)),
+            Some(Token::Str(s)) => Ok(Value::Str(s)),
+            Some(Token::Ident(id)) if id == "true" || id == "false" => {
+                Ok(Value::Bool(id == "true"))
+            }
+            Some(token) => Err(format!("expected a pattern, found `{token}`")),
+            None => Err(String::from("expected a pattern, found the end")),
+        }
+    }
+
+    /// Patterns up to `close`, and whether the last one is followed by a comma
+    fn list(&mut self, close: &str) -> Result<(Vec<Ast>, bool), String> {
+        let mut items = Vec::new();
+        let mut trailing_comma = false;
+        while !self.eat(close) {
+            items.push(self.pattern()?);
+            trailing_comma = self.eat(",");
+            if !trailing_comma {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok((items, trailing_comma))
+    }
+
+    /// `x: p, y, ..}`, the `{` already read
+    fn fields(&mut self) -> Result<(Vec<(String, Ast)>, bool), String> {
+        let mut fields = Vec::new();
+        loop {
+            if self.eat("}") {
+                return Ok((fields, false));
+            }
+            if self.eat("..") {
+                self.expect("}")?;
+                return Ok((fields, true));
+            }
+            let name = match self.next() {
+                Some(Token::Ident(name)) => name,
+                _ => return Err(String::from("expected a field name")),
+            };
+            // - `Point { x, .. }` is short for `Point { x: x, .. }`
+            let pattern = match self.eat(":") {
+                true => self.pattern()?,
+                false => Ast::Binding(name.clone(), None),
+            };
+            fields.push((name, pattern));
+            if !self.eat(",") {
+                self.expect("}")?;
+                return Ok((fields, false));
+            }
+        }
+    }
+
+    fn expr(&mut self, level: usize) -> Result<Expr, String> {
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let depth = self.depth;
+        let mut left = self.expr(level + 1)?;
+        while let Some(op) = LEVELS[level]
+            .iter()
+            .find(|op| matches!(self.peek(), Some(Token::Punct(p)) if p == *op))
+        {
+            self.pos += 1;
+            // - `a + b + c` is `(a + b) + c`, every operator nests what is on its left one level deeper
+            self.deeper()?;
+            let right = self.expr(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    /// Every nested expression goes through here, so this is where the depth is kept
+    fn unary(&mut self) -> Result<Expr, String> {
+        self.nested(Parser::unary_at_depth)
+    }
+
+    fn unary_at_depth(&mut self) -> Result<Expr, String> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        // - `-5` is a literal, as in a pattern, so that `-9223372036854775808` fits
+        if !matches!(self.tokens.get(self.pos + 1), Some(Token::Int(_))) && self.eat("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.expr(0)?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        match self.next() {
+            Some(Token::Ident(id)) if id != "true" && id != "false" => Ok(Expr::Var(id)),
+            token => Ok(Expr::Lit(self.literal(token)?)),
+        }
+    }
+}
+
+/// Replace the `..` of `items` with as many `_` as it stands for
+fn expand_rest(items: Vec<Ast>, arity: usize, what: &str) -> Result<Vec<Ast>, String> {
+    let rests = items
+        .iter()
+        .filter(|item| matches!(item, Ast::Rest))
+        .count();
+    let written = items.len() - rests;
+    match rests {
+        0 if written == arity => Ok(items),
+        1 if written <= arity => {
+            let mut expanded = Vec::with_capacity(arity);
+            for item in items {
+                match item {
+                    Ast::Rest => expanded.extend((written..arity).map(|_| Ast::Wild)),
+                    item => expanded.push(item),
+                }
+            }
+            Ok(expanded)
+        }
+        0 | 1 => Err(format!(
+            "{what} has {arity} fields, the pattern has {written}"
+        )),
+        _ => Err(String::from(
+            "`..` can only be used once per list of fields",
+        )),
+    }
+}
+
+/// The fields of `Path { ... }` in declaration order, `_` for those left out with `..`
+fn order_fields(
+    ctor: &Ctor,
+    mut given: Vec<(String, Ast)>,
+    rest: bool,
+) -> Result<Vec<Ast>, String> {
+    if !ctor.named() && !ctor.fields.is_empty() {
+        return Err(format!(
+            "`{}` has unnamed fields, write `{}(..)`",
+            ctor.path, ctor.path
+        ));
+    }
+    for (i, (name, _)) in given.iter().enumerate() {
+        if !ctor.fields.iter().any(|(field, _)| field == name) {
+            return Err(format!("`{}` has no field named `{name}`", ctor.path));
+        }
+        if given[..i].iter().any(|(other, _)| other == name) {
+            return Err(format!("field `{name}` is given more than once"));
+        }
+    }
+    ctor.fields
+        .iter()
+        .map(
+            |(field, _)| match given.iter().position(|(name, _)| name == field) {
+                Some(i) => Ok(given.swap_remove(i).1),
+                None if rest => Ok(Ast::Wild),
+                None => Err(format!(
+                    "`{}` needs its field `{field}`, or `..`",
+                    ctor.path
+                )),
+            },
+        )
+        .collect()
+}
+
+/// The value an `Ast` written without any pattern syntax stands for
+fn value_of(ast: Ast) -> Result<Value, String> {
+    let (ctor, fields) = match ast {
+        Ast::Lit(value) => return Ok(value),
+        Ast::Tuple(items) => {
+            return items
+                .into_iter()
+                .map(value_of)
+                .collect::<Result<_, _>>()
+                .map(Value::Tuple)
+        }
+        Ast::Binding(name, _) => {
+            return Err(format!(
+                "`{name}` is not a value, the value has no variables"
+            ))
+        }
+        Ast::Path(path) => (find_ctor(&path)?, vec![]),
+        Ast::TupleStruct(path, items) => (find_ctor(&path)?, items),
+        Ast::Struct(path, _, true) => {
+            return Err(format!(
+                "a value of `{path}` needs all its fields, not `..`"
+            ))
+        }
+        Ast::Struct(path, fields, false) => {
+            let ctor = find_ctor(&path)?;
+            (ctor, order_fields(ctor, fields, false)?)
+        }
+        _ => {
+            return Err(String::from(
+                "`_`, `..`, ranges and `|` make patterns, not values",
+            ))
+        }
+    };
+    if fields.len() != ctor.fields.len() {
+        return Err(format!(
+            "`{}` has {} fields, the value has {}",
+            ctor.path,
+            ctor.fields.len(),
+            fields.len()
+        ));
+    }
+    let fields: Vec<Value> = fields.into_iter().map(value_of).collect::<Result<_, _>>()?;
+    for (i, field) in fields.iter().enumerate() {
+        let expected = field_ty(ctor, i, &None);
+        if !fits(&type_of(field), &expected) {
+            return Err(mismatch(&expected, field));
+        }
+    }
+    Ok(Value::Adt(ctor, fields))
+}
+
+/// A pattern checked against the type of the value, with every `..` expanded
+#[derive(Debug, Clone)]
+enum Pattern {
+    Wild,
+    Binding(String, Option<Box<Pattern>>),
+    /// An integer, a char, a bool or a string
+    Lit(Value),
+    Range(Value, Value),
+    Or(Vec<Pattern>),
+    Tuple(Vec<Pattern>),
+    Adt(&'static Ctor, Vec<Pattern>),
+}
+
+static WILD: Pattern = Pattern::Wild;
+
+/// The names `pattern` binds, in order
+fn bindings(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Binding(name, sub) => {
+            names.push(name.clone());
+            if let Some(sub) = sub {
+                bindings(sub, names);
+            }
+        }
+        // - every alternative binds the same names, see `check`
+        Pattern::Or(alternatives) => bindings(&alternatives[0], names),
+        Pattern::Tuple(items) | Pattern::Adt(_, items) => {
+            items.iter().for_each(|item| bindings(item, names))
+        }
+        Pattern::Wild | Pattern::Lit(_) | Pattern::Range(..) => {}
+    }
+}
+
+fn check(ast: Ast, ty: &Ty) -> Result<Pattern, String> {
+    match ast {
+        Ast::Wild => Ok(Pattern::Wild),
+        Ast::Rest => Err(String::from("`..` can only be used in a list of fields")),
+        Ast::Binding(name, sub) => {
+            let sub = sub.map(|sub| check(*sub, ty).map(Box::new)).transpose()?;
+            Ok(Pattern::Binding(name, sub))
+        }
+        Ast::Lit(value) => match fits(&type_of(&value), ty) {
+            true => Ok(Pattern::Lit(value)),
+            false => Err(mismatch(ty, value)),
+        },
+        Ast::Range(low, high) => {
+            let ordered = match (&low, &high) {
+                (Value::Int(low), Value::Int(high)) => low <= high,
+                (Value::Char(low), Value::Char(high)) => low <= high,
+                _ => return Err(format!("`{low}..={high}` needs two integers or two chars")),
+            };
+            if !ordered {
+                return Err(format!(
+                    "`{low}..={high}` is empty, its lower bound is above the upper one"
+                ));
+            }
+            match fits(&type_of(&low), ty) {
+                true => Ok(Pattern::Range(low, high)),
+                false => Err(mismatch(ty, format!("{low}..={high}"))),
+            }
+        }
+        Ast::Or(alternatives) => {
+            // - the alternatives share one type, the first to tell what `ty` leaves unknown sets it for the others
+            let mut ty = ty.clone();
+            let mut checked = Vec::new();
+            for alternative in alternatives {
+                let alternative = check(alternative, &ty)?;
+                ty = narrow(&ty, &alternative);
+                checked.push(alternative);
+            }
+            let alternatives = checked;
+            let names = |pattern: &Pattern| {
+                let mut names = Vec::new();
+                bindings(pattern, &mut names);
+                names.sort();
+                names
+            };
+            let first = names(&alternatives[0]);
+            if alternatives[1..]
+                .iter()
+                .any(|alternative| names(alternative) != first)
+            {
+                return Err(String::from(
+                    "every alternative of a `|` must bind the same variables",
+                ));
+            }
+            Ok(Pattern::Or(alternatives))
+        }
+        Ast::Tuple(items) => match ty {
+            Ty::Tuple(tys) => {
+                let items = expand_rest(items, tys.len(), "the tuple")?;
+                let items = items.into_iter().zip(tys).map(|(item, ty)| check(item, ty));
+                Ok(Pattern::Tuple(items.collect::<Result<_, _>>()?))
+            }
+            Ty::Unknown if items.iter().any(|item| matches!(item, Ast::Rest)) => Err(String::from(
+                "cannot tell how long this tuple is, the value does not say",
+            )),
+            Ty::Unknown => {
+                let items = items.into_iter().map(|item| check(item, &Ty::Unknown));
+                Ok(Pattern::Tuple(items.collect::<Result<_, _>>()?))
+            }
+            _ => Err(mismatch(ty, "a tuple")),
+        },
+        Ast::Path(path) => {
+            let ctor = find_ctor(&path)?;
+            check_adt(ctor, expand_rest(vec![], ctor.fields.len(), ctor.path)?, ty)
+        }
+        Ast::TupleStruct(path, items) => {
+            let ctor = find_ctor(&path)?;
+            if ctor.named() {
+                return Err(format!(
+                    "`{path}` has named fields, write `{path} {{ .. }}`"
+                ));
+            }
+            check_adt(ctor, expand_rest(items, ctor.fields.len(), ctor.path)?, ty)
+        }
+        Ast::Struct(path, fields, rest) => {
+            let ctor = find_ctor(&path)?;
+            check_adt(ctor, order_fields(ctor, fields, rest)?, ty)
+        }
+    }
+}
+
+fn check_adt(ctor: &'static Ctor, items: Vec<Ast>, ty: &Ty) -> Result<Pattern, String> {
+    let param = match ty {
+        Ty::Adt(name, param) if *name == ctor.ty => param.clone(),
+        Ty::Unknown => None,
+        _ => return Err(mismatch(ty, ctor.path)),
+    };
+    let items = items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| check(item, &field_ty(ctor, i, &param)));
+    Ok(Pattern::Adt(ctor, items.collect::<Result<_, _>>()?))
+}
+
+/// `ty` with what `pattern` tells of its unknowns, e.g. `Option<char>` for `Option<_>` and `Some('a')`
+fn narrow(ty: &Ty, pattern: &Pattern) -> Ty {
+    match (ty, pattern) {
+        (_, Pattern::Binding(_, Some(sub))) => narrow(ty, sub),
+        (_, Pattern::Or(alternatives)) => alternatives
+            .iter()
+            .fold(ty.clone(), |ty, alternative| narrow(&ty, alternative)),
+        (Ty::Unknown, Pattern::Lit(value) | Pattern::Range(value, _)) => type_of(value),
+        (Ty::Unknown, Pattern::Tuple(items)) => Ty::Tuple(
+            items
+                .iter()
+                .map(|item| narrow(&Ty::Unknown, item))
+                .collect(),
+        ),
+        (Ty::Tuple(tys), Pattern::Tuple(items)) => Ty::Tuple(
+            tys.iter()
+                .zip(items)
+                .map(|(ty, item)| narrow(ty, item))
+                .collect(),
+        ),
+        (Ty::Unknown | Ty::Adt(..), Pattern::Adt(ctor, items)) => {
+            let mut param = match ty {
+                Ty::Adt(_, param) => param.as_deref().cloned().unwrap_or(Ty::Unknown),
+                _ => Ty::Unknown,
+            };
+            // - only a field of type `T` can tell something `ctor` does not
+            for ((_, field), item) in ctor.fields.iter().zip(items) {
+                if *field == "T" {
+                    param = narrow(&param, item);
+                }
+            }
+            let param = (param != Ty::Unknown).then(|| Box::new(param));
+            Ty::Adt(ctor.ty, param)
+        }
+        _ => ty.clone(),
+    }
+}
+
+/// Whether `pattern` matches `value`, pushing what it binds onto `bound`
+fn matches(pattern: &Pattern, value: &Value, bound: &mut Vec<(String, Value)>) -> bool {
+    match (pattern, value) {
+        (Pattern::Wild, _) => true,
+        (Pattern::Binding(name, sub), _) => {
+            let len = bound.len();
+            bound.push((name.clone(), value.clone()));
+            let matched = sub.as_ref().is_none_or(|sub| matches(sub, value, bound));
+            if !matched {
+                bound.truncate(len);
+            }
+            matched
+        }
+        (Pattern::Lit(literal), _) => literal == value,
+        (Pattern::Range(low, high), _) => match (low, high, value) {
+            (Value::Int(low), Value::Int(high), Value::Int(n)) => low <= n && n <= high,
+            (Value::Char(low), Value::Char(high), Value::Char(c)) => low <= c && c <= high,
+            _ => false,
+        },
+        (Pattern::Or(alternatives), _) => alternatives.iter().any(|alternative| {
+            let len = bound.len();
+            let matched = matches(alternative, value, bound);
+            if !matched {
+                bound.truncate(len);
+            }
+            matched
+        }),
+        (Pattern::Tuple(items), Value::Tuple(values)) => {
+            items.len() == values.len()
+                && items
+                    .iter()
+                    .zip(values)
+                    .all(|(item, value)| matches(item, value, bound))
+        }
+        (Pattern::Adt(ctor, items), Value::Adt(other, values)) => {
+            ctor.path == other.path
+                && items
+                    .iter()
+                    .zip(values)
+                    .all(|(item, value)| matches(item, value, bound))
+        }
+        _ => false,
+    }
+}
+
+/// The variables `expr` reads
+fn variables<'e>(expr: &'e Expr, names: &mut Vec<&'e str>) {
+    match expr {
+        Expr::Lit(_) => {}
+        Expr::Var(name) => names.push(name),
+        Expr::Not(e) | Expr::Neg(e) => variables(e, names),
+        Expr::Binary(_, left, right) => {
+            variables(left, names);
+            variables(right, names);
+        }
+    }
+}
+
+fn eval(expr: &Expr, bound: &[(String, Value)]) -> Result<Value, String> {
+    match expr {
+        Expr::Lit(value) => Ok(value.clone()),
+        Expr::Var(name) => bound
+            .iter()
+            .find(|(bound, _)| bound == name)
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| format!("cannot find `{name}`")),
+        Expr::Not(e) => match eval(e, bound)? {
+            Value::Bool(b) => Ok(Value::Bool(!b)),
+            value => Err(format!("cannot apply `!` to `{value}`")),
+        },
+        Expr::Neg(e) => match eval(e, bound)? {
+            Value::Int(n) => n
+                .checked_neg()
+                .map(Value::Int)
+                .ok_or_else(|| String::from("attempt to negate with overflow")),
+            value => Err(format!("cannot apply `-` to `{value}`")),
+        },
+        Expr::Binary(op @ ("&&" | "||"), left, right) => {
+            let operand = |e: &Expr| match eval(e, bound)? {
+                Value::Bool(b) => Ok(b),
+                value => Err(format!("cannot apply `{op}` to `{value}`")),
+            };
+            // - `&&` and `||` only evaluate their right side when they need it
+            Ok(Value::Bool(match *op {
+                "&&" => operand(left)? && operand(right)?,
+                _ => operand(left)? || operand(right)?,
+            }))
+        }
+        Expr::Binary(op, left, right) => {
+            let (left, right) = (eval(left, bound)?, eval(right, bound)?);
+            let cannot = || format!("cannot apply `{op}` to `{left}` and `{right}`");
+            if let (Value::Int(a), Value::Int(b)) = (&left, &right) {
+                let arithmetic = match *op {
+                    "+" => Some(a.checked_add(*b)),
+                    "-" => Some(a.checked_sub(*b)),
+                    "*" => Some(a.checked_mul(*b)),
+                    "/" => Some(a.checked_div(*b)),
+                    "%" => Some(a.checked_rem(*b)),
+                    _ => None,
+                };
+                if let Some(result) = arithmetic {
+                    return result.map(Value::Int).ok_or_else(|| {
+                        format!("`{left} {op} {right}` overflows or divides by zero")
+                    });
+                }
+            }
+            let ordering = match (&left, &right) {
+                (Value::Int(a), Value::Int(b)) => a.cmp(b),
+                (Value::Char(a), Value::Char(b)) => a.cmp(b),
+                (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+                (Value::Str(a), Value::Str(b)) => a.cmp(b),
+                _ if matches!(*op, "==" | "!=") && type_of(&left) == type_of(&right) => {
+                    match left == right {
+                        true => Ordering::Equal,
+                        // - structured values are only compared for equality
+                        false => Ordering::Less,
+                    }
+                }
+                _ => return Err(cannot()),
+            };
+            Ok(Value::Bool(match *op {
+                "==" => ordering == Ordering::Equal,
+                "!=" => ordering != Ordering::Equal,
+                "<" => ordering == Ordering::Less,
+                "<=" => ordering != Ordering::Greater,
+                ">" => ordering == Ordering::Greater,
+                ">=" => ordering != Ordering::Less,
+                _ => return Err(cannot()),
+            }))
+        }
+    }
+}
+
+/// What a pattern can start with: a constructor, or a range of integers or chars
+///     * ranges are held as `i128`s, which fit every `i64` plus one, and every `char`
+#[derive(Debug, Clone, PartialEq)]
+enum Con {
+    Tuple(usize),
+    Adt(&'static Ctor),
+    Bool(bool),
+    Str(String),
+    Range { chars: bool, low: i128, high: i128 },
+}
+
+impl Con {
+    fn arity(&self) -> usize {
+        match self {
+            Con::Tuple(n) => *n,
+            Con::Adt(ctor) => ctor.fields.len(),
+            _ => 0,
+        }
+    }
+
+    fn covers(&self, piece: &Con) -> bool {
+        match (self, piece) {
+            (
+                Con::Range { chars, low, high },
+                Con::Range {
+                    chars: c,
+                    low: l,
+                    high: h,
+                },
+            ) => chars == c && low <= l && h <= high,
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// `pattern` seen through its bindings: `x` is `_` and `x @ p` is `p`
+fn strip(pattern: &Pattern) -> &Pattern {
+    match pattern {
+        Pattern::Binding(_, None) => &WILD,
+        Pattern::Binding(_, Some(sub)) => strip(sub),
+        pattern => pattern,
+    }
+}
+
+/// The constructor `pattern` starts with, `None` for a wildcard
+fn con_of(pattern: &Pattern) -> Option<Con> {
+    let range = |low: &Value, high: &Value| match (low, high) {
+        (Value::Char(low), Value::Char(high)) => Con::Range {
+            chars: true,
+            low: *low as i128,
+            high: *high as i128,
+        },
+        (Value::Int(low), Value::Int(high)) => Con::Range {
+            chars: false,
+            low: *low as i128,
+            high: *high as i128,
+        },
+        _ => unreachable!("`check` only lets integer and char ranges through"),
+    };
+    match strip(pattern) {
+        Pattern::Wild | Pattern::Binding(..) | Pattern::Or(_) => None,
+        Pattern::Tuple(items) => Some(Con::Tuple(items.len())),
+        Pattern::Adt(ctor, _) => Some(Con::Adt(ctor)),
+        Pattern::Lit(Value::Bool(b)) => Some(Con::Bool(*b)),
+        Pattern::Lit(Value::Str(s)) => Some(Con::Str(s.clone())),
+        Pattern::Lit(value) => Some(range(value, value)),
+        Pattern::Range(low, high) => Some(range(low, high)),
+    }
+}
+
+/// The fields of `pattern` when it matches every value built by `con`, `None` when it matches none of them
+///     * `con` is never a range that `pattern`'s range only partly covers, see `split`
+fn specialize<'p>(pattern: &'p Pattern, con: &Con) -> Option<Vec<&'p Pattern>> {
+    match strip(pattern) {
+        Pattern::Wild => Some(vec![&WILD; con.arity()]),
+        Pattern::Tuple(items) | Pattern::Adt(_, items) if con_of(pattern).as_ref() == Some(con) => {
+            Some(items.iter().collect())
+        }
+        pattern => match con_of(pattern) {
+            Some(own) if !matches!(own, Con::Tuple(_) | Con::Adt(_)) && own.covers(con) => {
+                Some(vec![])
+            }
+            _ => None,
+        },
+    }
+}
+
+/// `ranges` cut at every bound of the ranges in `seen`, so that each piece is inside or outside each of them
+fn split(chars: bool, ranges: &[(i128, i128)], seen: &[Con]) -> Vec<Con> {
+    let mut cuts: Vec<i128> = seen
+        .iter()
+        .filter_map(|con| match con {
+            Con::Range {
+                chars: c,
+                low,
+                high,
+            } if *c == chars => Some([*low, *high + 1]),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    cuts.sort();
+    cuts.dedup();
+    let mut pieces = Vec::new();
+    for &(low, high) in ranges {
+        let mut start = low;
+        for &cut in cuts.iter().filter(|&&cut| low < cut && cut <= high) {
+            pieces.push(Con::Range {
+                chars,
+                low: start,
+                high: cut - 1,
+            });
+            start = cut;
+        }
+        pieces.push(Con::Range {
+            chars,
+            low: start,
+            high,
+        });
+    }
+    pieces
+}
+
+/// Every constructor of the type `seen` belongs to, if the patterns use each of them; `None` if some is left out
+fn all_cons(seen: &[Con]) -> Option<Vec<Con>> {
+    let all = match seen.first()? {
+        Con::Tuple(n) => vec![Con::Tuple(*n)],
+        Con::Adt(ctor) => TYPES
+            .iter()
+            .filter(|c| c.ty == ctor.ty)
+            .map(Con::Adt)
+            .collect(),
+        Con::Bool(_) => vec![Con::Bool(false), Con::Bool(true)],
+        // - there are always more strings than the patterns name
+        Con::Str(_) => return None,
+        Con::Range { chars, .. } => {
+            // - the surrogates, `0xD800..=0xDFFF`, are not `char`s
+            let domain: &[(i128, i128)] = match chars {
+                true => &[(0, 0xD7FF), (0xE000, 0x10FFFF)],
+                false => &[(i64::MIN as i128, i64::MAX as i128)],
+            };
+            split(*chars, domain, seen)
+        }
+    };
+    all.iter()
+        .all(|con| seen.iter().any(|seen| seen.covers(con)))
+        .then_some(all)
+}
+
+/// Every pattern of `rows` whose first pattern is an `|`, replaced by a row per alternative
+fn expand_or<'p>(rows: &[Vec<&'p Pattern>]) -> Vec<Vec<&'p Pattern>> {
+    let mut expanded = Vec::new();
+    for row in rows {
+        match strip(row[0]) {
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    let mut row = row.clone();
+                    row[0] = alternative;
+                    expanded.extend(expand_or(&[row]));
+                }
+            }
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+/// Whether some list of values matches the patterns of `q` and none of the `rows`
+///     * the rows and `q` are lists of patterns side by side, e.g. the fields of a tuple
+///     * the first column is split by constructor: the rows that start with `Some(p)`, or a wildcard,
+///       keep `p`, and so does `q`, those starting with `None` drop out; then the same on what is left
+fn useful(rows: &[Vec<&Pattern>], q: &[&Pattern]) -> bool {
+    let Some((&head, rest)) = q.split_first() else {
+        // - no column left: the empty list of values matches `q`, and the rows if there are any
+        return rows.is_empty();
+    };
+    let head = strip(head);
+    if let Pattern::Or(alternatives) = head {
+        return alternatives.iter().any(|alternative| {
+            let mut q = vec![alternative];
+            q.extend(rest);
+            useful(rows, &q)
+        });
+    }
+    let rows = expand_or(rows);
+    let seen: Vec<Con> = rows.iter().filter_map(|row| con_of(row[0])).collect();
+    let cons = match con_of(head) {
+        Some(Con::Range { chars, low, high }) => split(chars, &[(low, high)], &seen),
+        Some(con) => vec![con],
+        None => match all_cons(&seen) {
+            Some(cons) => cons,
+            None => {
+                // - the values of a constructor no row starts with only match the rows starting with a wildcard
+                let rows: Vec<Vec<&Pattern>> = rows
+                    .iter()
+                    .filter(|row| matches!(strip(row[0]), Pattern::Wild))
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+                return useful(&rows, rest);
+            }
+        },
+    };
+    cons.iter().any(|con| {
+        let rows: Vec<Vec<&Pattern>> = rows
+            .iter()
+            .filter_map(|row| {
+                let mut fields = specialize(row[0], con)?;
+                fields.extend(&row[1..]);
+                Some(fields)
+            })
+            .collect();
+        let mut q = specialize(head, con).expect("`con` is one of `head`'s");
+        q.extend(rest);
+        useful(&rows, &q)
+    })
+}
+
+/// What happened to an arm when the value was matched
+#[derive(Debug, PartialEq)]
+pub enum ArmOutcome {
+    /// The arm was taken, binding these variables
+    Matched(Vec<(String, Value)>),
+    /// The pattern matched, binding these variables, but the guard was false
+    GuardFalse(Vec<(String, Value)>),
+    NoMatch,
+    /// An arm above was taken
+    NotTried,
+}
+
+#[derive(Debug)]
+pub struct ArmReport {
+    pub source: String,
+    /// Whether some value could reach this arm
+    pub reachable: bool,
+    pub outcome: ArmOutcome,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub value: Value,
+    pub arms: Vec<ArmReport>,
+    /// Whether every value of the type matches some arm
+    pub exhaustive: bool,
+}
+
+impl Report {
+    /// Index of the arm taken
+    pub fn matched(&self) -> Option<usize> {
+        self.arms
+            .iter()
+            .position(|arm| matches!(arm.outcome, ArmOutcome::Matched(_)))
+    }
+
+    /// Indices of the arms that no value can reach
+    pub fn unreachable(&self) -> Vec<usize> {
+        (0..self.arms.len())
+            .filter(|&i| !self.arms[i].reachable)
+            .collect()
+    }
+}
+
+/// `, binds x = 1, y = 2`
+fn describe_bindings(bound: &[(String, Value)]) -> String {
+    let bound: Vec<String> = bound
+        .iter()
+        .map(|(name, value)| format!("{name} = {value}"))
+        .collect();
+    match bound.is_empty() {
+        true => String::new(),
+        false => format!(", binds {}", bound.join(", ")),
+    }
+}
+
+/// The match as code, with what happened to each arm as a comment
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "match {} {{", self.value)?;
+        let width = self
+            .arms
+            .iter()
+            .map(|arm| arm.source.chars().count())
+            .max()
+            .unwrap_or(0);
+        for arm in &self.arms {
+            let outcome = match &arm.outcome {
+                ArmOutcome::Matched(bound) => format!("matches{}", describe_bindings(bound)),
+                ArmOutcome::GuardFalse(bound) => format!(
+                    "the pattern matches{}, the guard is false",
+                    describe_bindings(bound)
+                ),
+                ArmOutcome::NoMatch => String::from("does not match"),
+                ArmOutcome::NotTried => String::from("not tried"),
+            };
+            let reachable = match arm.reachable {
+                true => "",
+                false => "unreachable, ",
+            };
+            writeln!(
+                f,
+                "    {:width$} => ...  // {reachable}{outcome}",
+                arm.source
+            )?;
+        }
+        write!(f, "}}")?;
+        if self.matched().is_none() {
+            write!(f, "\n// no arm matches {}", self.value)?;
+        }
+        if !self.exhaustive {
+            write!(
+                f,
+                "\n// not exhaustive: some values match no arm, rustc rejects such a match (E0004)"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse `arm`, i.e. a pattern with an optional `if` guard, for values of type `ty`
+fn parse_arm(arm: &str, ty: &Ty) -> Result<(Pattern, Option<Expr>), String> {
+    let mut parser = Parser::new(arm)?;
+    let pattern = check(parser.pattern()?, ty)?;
+    let guard = match parser.eat_keyword("if") {
+        true => Some(parser.expr(0)?),
+        false => None,
+    };
+    parser.end()?;
+
+    let mut names = Vec::new();
+    bindings(&pattern, &mut names);
+    if let Some(i) = (1..names.len()).find(|&i| names[..i].contains(&names[i])) {
+        return Err(format!(
+            "`{}` is bound more than once in the same pattern",
+            names[i]
+        ));
+    }
+    if let Some(guard) = &guard {
+        let mut used = Vec::new();
+        variables(guard, &mut used);
+        if let Some(name) = used
+            .iter()
+            .find(|name| !names.iter().any(|bound| bound == *name))
+        {
+            return Err(format!(
+                "the guard reads `{name}`, which the pattern does not bind"
+            ));
+        }
+    }
+    Ok((pattern, guard))
+}
+
+pub fn parse_value(value: &str) -> Result<Value, String> {
+    let mut parser = Parser::new(value)?;
+    let ast = parser.pattern()?;
+    parser.end()?;
+    value_of(ast)
+}
+
+/// Match `value` against `arms`, each a pattern with an optional guard, e.g. `Some(x) if x > 5`
+pub fn evaluate(value: &str, arms: &[&str]) -> Result<Report, String> {
+    let value = parse_value(value).map_err(|e| format!("value `{value}`: {e}"))?;
+    // - the arms share one type, the first to tell what the value leaves unknown sets it for the others
+    //     * e.g. `Some('a')` makes the `T` of `None` a `char`, and a later `Some(1)` a mismatch
+    let mut ty = type_of(&value);
+    let mut parsed = Vec::new();
+    for (i, arm) in arms.iter().enumerate() {
+        let (pattern, guard) =
+            parse_arm(arm, &ty).map_err(|e| format!("arm {} `{arm}`: {e}", i + 1))?;
+        ty = narrow(&ty, &pattern);
+        parsed.push((pattern, guard));
+    }
+
+    // - the unguarded patterns so far, as rows of one column
+    let mut covering: Vec<Vec<&Pattern>> = Vec::new();
+    let mut reports = Vec::new();
+    let mut taken = false;
+    for (i, ((pattern, guard), arm)) in parsed.iter().zip(arms).enumerate() {
+        let reachable = useful(&covering, &[pattern]);
+        if guard.is_none() {
+            covering.push(vec![pattern]);
+        }
+        let mut bound = Vec::new();
+        let outcome = if taken {
+            ArmOutcome::NotTried
+        } else if !matches(pattern, &value, &mut bound) {
+            ArmOutcome::NoMatch
+        } else {
+            let passes = match guard {
+                None => true,
+                Some(guard) => {
+                    match eval(guard, &bound).map_err(|e| format!("arm {}: {e}", i + 1))? {
+                        Value::Bool(b) => b,
+                        other => {
+                            return Err(format!(
+                                "arm {}: the guard is `{other}`, not a `bool`",
+                                i + 1
+                            ))
+                        }
+                    }
+                }
+            };
+            taken = passes;
+            match passes {
+                true => ArmOutcome::Matched(bound),
+                false => ArmOutcome::GuardFalse(bound),
+            }
+        };
+        reports.push(ArmReport {
+            source: arm.trim().to_string(),
+            reachable,
+            outcome,
+        });
+    }
+    let exhaustive = !useful(&covering, &[&WILD]);
+    Ok(Report {
+        value,
+        arms: reports,
+        exhaustive,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_unreachable_arm_of_ch6() {
+        let report = evaluate(
+            "Location::Range(0, 5)",
+            &[
+                "Location::Point(_)",
+                "Location::Range(_, n)",
+                "Location::Range(0, _)",
+                "_",
+            ],
+        )
+        .unwrap();
+        assert_eq!(report.matched(), Some(1));
+        assert_eq!(
+            report.arms[1].outcome,
+            ArmOutcome::Matched(vec![(String::from("n"), Value::Int(5))])
+        );
+        assert_eq!(report.unreachable(), vec![2, 3]);
+        assert!(report.exhaustive);
+    }
+
+    #[test]
+    fn binds_nested_variants() {
+        let report = evaluate(
+            "Coin::Quarter(UsState::Alaska)",
+            &[
+                "Coin::Penny",
+                "Coin::Quarter(UsState::Alabama)",
+                "Coin::Quarter(state)",
+                "Coin::Nickel | Coin::Dime",
+            ],
+        )
+        .unwrap();
+        assert_eq!(report.matched(), Some(2));
+        assert_eq!(report.arms[1].outcome, ArmOutcome::NoMatch);
+        assert_eq!(report.arms[3].outcome, ArmOutcome::NotTried);
+        assert!(report.unreachable().is_empty());
+        assert!(report.exhaustive);
+        assert!(report.to_string().contains(
+            "Coin::Quarter(state)            => ...  // matches, binds state = UsState::Alaska\n"
+        ));
+    }
+
+    #[test]
+    fn guards_do_not_cover() {
+        let report = evaluate(
+            "Some(4)",
+            &["Some(x) if x % 2 == 1", "Some(x) if x % 2 == 0", "None"],
+        )
+        .unwrap();
+        assert_eq!(report.matched(), Some(1));
+        assert!(matches!(report.arms[0].outcome, ArmOutcome::GuardFalse(_)));
+        // - without an unguarded `Some(_)`, rustc cannot tell that the guards cover every number
+        assert!(!report.exhaustive);
+    }
+
+    #[test]
+    fn splits_ranges() {
+        let report = evaluate(
+            "3",
+            &[
+                "-9223372036854775807..=-1",
+                "0",
+                "1..=9",
+                "5..=7",
+                "10..=9223372036854775807",
+            ],
+        )
+        .unwrap();
+        assert_eq!(report.unreachable(), vec![3]);
+        assert!(!report.exhaustive, "i64::MIN is missing");
+        let report = evaluate(
+            "-9223372036854775808",
+            &[
+                "-9223372036854775808..=0",
+                "1..=9223372036854775807",
+                "n if n == -9223372036854775808",
+            ],
+        )
+        .unwrap();
+        assert_eq!(report.matched(), Some(0));
+        assert_eq!(report.unreachable(), vec![2]);
+        assert!(report.exhaustive);
+        let report = evaluate("'c'", &["'a'..='j'", "'k'..='z'", "'b' | 'y'", "_"]).unwrap();
+        assert_eq!(report.unreachable(), vec![2]);
+        assert_eq!(report.matched(), Some(0));
+    }
+
+    #[test]
+    fn destructures_structs_and_tuples() {
+        let report = evaluate(
+            "((3, 10), Point { x: 3, y: -10 })",
+            &[
+                "((feet, ..), Point { x: 0, .. })",
+                "(_, Point { y: y @ -10..=-1, x })",
+                "(.., Point { x, y })",
+            ],
+        )
+        .unwrap();
+        assert_eq!(report.matched(), Some(1));
+        assert_eq!(
+            report.arms[1].outcome,
+            ArmOutcome::Matched(vec![
+                (String::from("x"), Value::Int(3)),
+                (String::from("y"), Value::Int(-10))
+            ])
+        );
+        assert!(report.exhaustive);
+        let report = evaluate(
+            "(true, false)",
+            &["(true, _)", "(_, true)", "(false, false)", "(false, _)"],
+        )
+        .unwrap();
+        assert_eq!(report.unreachable(), vec![3]);
+    }
+
+    #[test]
+    fn reports_non_exhaustive_matches() {
+        let report = evaluate(
+            "Message::Quit",
+            &["Message::Quit", "Message::Move { .. }", "Message::Write(_)"],
+        )
+        .unwrap();
+        assert!(!report.exhaustive);
+        assert!(report.to_string().ends_with("(E0004)"));
+        let report = evaluate(
+            "Message::ChangeColor(Color::Hsv(0, 160, 255))",
+            &["Message::ChangeColor(Color::Rgb(..))"],
+        )
+        .unwrap();
+        assert_eq!(report.matched(), None);
+        assert!(report
+            .to_string()
+            .contains("\n// no arm matches Message::ChangeColor(Color::Hsv(0, 160, 255))\n"));
+    }
+
+    #[test]
+    fn rejects_what_rustc_rejects() {
+        let error = |value: &str, arm: &str| evaluate(value, &[arm]).unwrap_err();
+        assert!(error("Coin::Penny", "Location::Point(_)")
+            .contains("expected `Coin`, found `Location::Point`"));
+        assert!(error("Point { x: 1, y: 2 }", "Point { x }").contains("needs its field `y`"));
+        assert!(error("Point { x: 1, y: 2 }", "Point { x, z, .. }").contains("no field named `z`"));
+        assert!(error("UsState::Texas", "_").contains("no variant `Texas` in `UsState`"));
+        assert!(error("Some(1)", "Some(x) | None").contains("same variables"));
+        assert!(error("(1, 2)", "(a, a)").contains("bound more than once"));
+        assert!(error("(1, 2)", "(a, b, c)").contains("the tuple has 2 fields"));
+        assert!(error("Some(1)", "Some(x) if y > 0").contains("does not bind"));
+        assert!(error("5", "7..=3").contains("is empty"));
+        assert!(error("Location::Point('a')", "_").contains("expected `i64`"));
+        assert!(evaluate(
+            "None",
+            &[
+                "Some(0..=9223372036854775807)",
+                "Some(-9223372036854775808..=-1)",
+                "Some('a')",
+                "None"
+            ]
+        )
+        .unwrap_err()
+        .starts_with("arm 3 `Some('a')`: mismatched types: expected `i64`"));
+        assert!(error("None", "Some(Some('a')) | Some(Some(1))").contains("expected `char`"));
+        assert!(error("9223372036854775808", "_").contains("does not fit an i64"));
+        assert!(error("-9223372036854775809", "_").contains("does not fit an i64"));
+        let deep = format!("{}1{}", "(".repeat(30_000), ")".repeat(30_000));
+        assert!(error(&deep, "_").contains("nested more than"));
+        assert!(error("Some(1)", &format!("Some(x) if {deep} > 0")).contains("nested more than"));
+        let long = vec!["x"; 30_000].join(" + ");
+        assert!(error("Some(1)", &format!("Some(x) if {long} > 0")).contains("nested more than"));
+    }
+}
@@ -0,0 +1,92 @@
+//! # Ch18.2 - Refutability: Whether a Pattern Might Fail to Match
+//!     * irrefutable patterns match any possible value, e.g. `x` in `let x = 5;`
+//!     * refutable patterns can fail to match some value, e.g. `Some(x)` against a `None`
+//!     * `let` statements, function parameters and `for` loops only accept irrefutable patterns
+//!         * the program could not go on meaningfully when the value does not match
+//!         * `let Some(x) = some_option_value;` is refused with E0005, "refutable pattern in local binding"
+//!     * `if let` and `while let` accept both, but warn about an irrefutable one as it might as well be a `let`
+//!     * `let ... else` takes a refutable pattern, its `else` block must diverge, i.e. have the type `!`
+//!     * `match` arms are refutable except the last one, which has to match whatever is left
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub struct Refutability {}
+
+impl Refutability {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on refutability======")?;
+        let some_option_value: Option<i32> = None;
+
+        // - `None` is a value `Some(x)` does not cover, so this `let` is rejected (E0005)
+        // - the rejection is checked by `tests/compile_fail/ch18_refutable_let.rs`
+        // let Some(x) = some_option_value;
+
+        // - with `if let`, the code just skips the block when the pattern does not match
+        if let Some(x) = some_option_value {
+            outln!(out, "{x}")?;
+        } else {
+            outln!(
+                out,
+                "some_option_value is None, the `if let` block is skipped"
+            )?;
+        }
+
+        // - an irrefutable pattern in `if let` only gets a warning, `irrefutable_let_patterns`
+        // if let x = 5 {
+        //     println!("{x}");
+        // };
+
+        // `let ... else`
+        // - binds like `let` when the pattern matches, runs the `else` block otherwise
+        fn describe(value: Option<i32>, out: &mut dyn Write) -> io::Result<()> {
+            let Some(x) = value else {
+                // - the `else` block must not fall through, here it returns
+                return outln!(out, "nothing to describe");
+            };
+            outln!(out, "x is {x}")
+        }
+        describe(Some(3), out)?;
+        describe(None, out)?;
+
+        // - a `match` needs its arms to be exhaustive, without the `None` arm it is rejected (E0004)
+        // - the rejection is checked by `tests/compile_fail/ch18_non_exhaustive_match.rs`
+        let n = match some_option_value {
+            Some(x) => x,
+            None => 0,
+        };
+        outln!(out, "n = {n}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Refutability {
+    fn id(&self) -> &'static str {
+        "ch18::refutability"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(18, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Refutability: Whether a Pattern Might Fail to Match"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["pattern-matching", "enums"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch18::places"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("refutability.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
@@ -0,0 +1,241 @@
+//! # Ch18.3 - Pattern Syntax
+//!     * literals, e.g. `1 => ...`
+//!     * named variables are irrefutable, and shadow variables of the same name outside the `match`
+//!     * `|` matches one of several patterns, `1 | 2 => ...`
+//!     * `..=` matches an inclusive range, of numbers or `char`s only, e.g. `1..=5`, `'a'..='j'`
+//!     * destructuring
+//!         * structs: `let Point { x: a, y: b } = p;`, or `let Point { x, y } = p;` binding `x` and `y`
+//!         * literals inside test some fields while binding the others, `Point { x, y: 0 }`
+//!         * enums: the pattern follows the variant's definition, `Message::Move { x, y }`, `Message::Write(text)`
+//!         * nested structs and enums, `Message::ChangeColor(Color::Hsv(h, s, v))`
+//!         * structs and tuples mixed, `let ((feet, inches), Point { x, y }) = ((3, 10), Point { x: 3, y: -10 });`
+//!     * ignoring
+//!         * `_` ignores a whole value, also as a function parameter, and never binds, so it does not move the value
+//!         * `_x` binds but silences the unused variable warning
+//!         * `..` ignores the remaining parts, at most once per tuple, `(first, .., last)`
+//!     * match guards: an `if` condition after the pattern, `Some(x) if x % 2 == 0`
+//!         * the guard applies to the whole `|` pattern, `4 | 5 | 6 if y` is `(4 | 5 | 6) if y`
+//!         * the compiler does not look into guards when checking exhaustiveness
+//!     * `@` bindings test a value and bind it at once, `id @ 3..=7`
+//!     * `ch18::playground` reports which arm of a `match` takes a value and which arms can never be taken
+use crate::ch18::playground;
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub struct Syntax {}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+enum Color {
+    Rgb(i32, i32, i32),
+    Hsv(i32, i32, i32),
+}
+
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(Color),
+}
+
+impl Syntax {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on pattern syntax======")?;
+        // Matching literals
+        let x = 1;
+        match x {
+            1 => outln!(out, "one")?,
+            2 => outln!(out, "two")?,
+            _ => outln!(out, "anything")?,
+        }
+
+        // Matching named variables
+        let x = Some(5);
+        let y = 10;
+        match x {
+            Some(50) => outln!(out, "Got 50")?,
+            // - a new `y` shadowing the outer one, it matches any value inside `Some`
+            Some(y) => outln!(out, "Matched, y = {y}")?,
+            _ => outln!(out, "Default case, x = {x:?}")?,
+        }
+        outln!(out, "at the end: x = {x:?}, y = {y}")?;
+
+        // Multiple patterns and ranges
+        let x = 5;
+        match x {
+            1 | 2 => outln!(out, "one or two")?,
+            3..=5 => outln!(out, "three through five")?,
+            _ => outln!(out, "something else")?,
+        }
+        let x = 'c';
+        match x {
+            'a'..='j' => outln!(out, "early ASCII letter")?,
+            'k'..='z' => outln!(out, "late ASCII letter")?,
+            _ => outln!(out, "something else")?,
+        }
+
+        // Destructuring structs
+        let p = Point { x: 0, y: 7 };
+        let Point { x: a, y: b } = p;
+        outln!(out, "a = {a}, b = {b}")?;
+        // - the shorthand binds variables named after the fields
+        let Point { x, y } = p;
+        outln!(out, "x = {x}, y = {y}")?;
+        match p {
+            Point { x, y: 0 } => outln!(out, "On the x axis at {x}")?,
+            Point { x: 0, y } => outln!(out, "On the y axis at {y}")?,
+            Point { x, y } => outln!(out, "On neither axis: ({x}, {y})")?,
+        }
+
+        // Destructuring enums, and nested enums
+        let messages = [
+            Message::ChangeColor(Color::Hsv(0, 160, 255)),
+            Message::Move { x: 3, y: -1 },
+            Message::Write(String::from("hello")),
+            Message::Quit,
+        ];
+        for msg in &messages {
+            match msg {
+                Message::Quit => outln!(out, "The Quit variant has no data to destructure.")?,
+                Message::Move { x, y } => outln!(
+                    out,
+                    "Move in the x direction {x} and in the y direction {y}"
+                )?,
+                Message::Write(text) => outln!(out, "Text message: {text}")?,
+                Message::ChangeColor(Color::Rgb(r, g, b)) => {
+                    outln!(out, "Change color to red {r}, green {g}, and blue {b}")?
+                }
+                Message::ChangeColor(Color::Hsv(h, s, v)) => {
+                    outln!(out, "Change color to hue {h}, saturation {s}, value {v}")?
+                }
+            }
+        }
+
+        // Destructuring structs and tuples
+        let ((feet, inches), Point { x, y }) = ((3, 10), Point { x: 3, y: -10 });
+        outln!(out, "feet = {feet}, inches = {inches}, x = {x}, y = {y}")?;
+
+        // Ignoring values
+        // - `_` as a parameter, e.g. to implement a trait whose signature needs it
+        fn foo(_: i32, y: i32) -> i32 {
+            y
+        }
+        let mut setting_value = Some(5);
+        let new_setting_value = Some(10);
+        match (setting_value, new_setting_value) {
+            (Some(_), Some(_)) => outln!(out, "Can't overwrite an existing customized value")?,
+            _ => setting_value = new_setting_value,
+        }
+        outln!(out, "setting is {setting_value:?}")?;
+        let numbers = (2, 4, 8, 16, 32);
+        match numbers {
+            (first, _, third, _, fifth) => outln!(out, "Some numbers: {first}, {third}, {fifth}")?,
+        }
+        match numbers {
+            (first, .., last) => outln!(out, "Some numbers: {first}, {last}")?,
+        }
+        // - `..` twice is ambiguous, which elements would `second` be?
+        // let (.., second, ..) = numbers;
+
+        // - `_` never binds, so `s` keeps its `String`; `Some(_s)` would move it and `s` could not be printed after
+        let s = Some(String::from("Hello!"));
+        if let Some(_) = s {
+            outln!(out, "found a string")?;
+        }
+        outln!(out, "{s:?}")?;
+
+        // Match guards
+        let num = Some(4);
+        match num {
+            Some(x) if x % 2 == 0 => outln!(out, "The number {x} is even")?,
+            Some(x) => outln!(out, "The number {x} is odd")?,
+            None => (),
+        }
+        // - a guard can read the outer `y`, where a pattern `Some(y)` would shadow it
+        let x = Some(10);
+        let y = 10;
+        match x {
+            Some(50) => outln!(out, "Got 50")?,
+            Some(n) if n == y => outln!(out, "Matched, n = {n}")?,
+            _ => outln!(out, "Default case, x = {x:?}")?,
+        }
+        let x = 4;
+        let y = false;
+        match x {
+            4 | 5 | 6 if y => outln!(out, "yes")?,
+            _ => outln!(out, "no")?,
+        }
+
+        // `@` bindings
+        enum Hello {
+            Message { id: i32 },
+        }
+        let msg = Hello::Message { id: 5 };
+        match msg {
+            Hello::Message {
+                id: id_variable @ 3..=7,
+            } => outln!(out, "Found an id in range: {id_variable}")?,
+            // - the range is tested, but no variable holds the id
+            Hello::Message { id: 10..=12 } => outln!(out, "Found an id in another range")?,
+            Hello::Message { id } => outln!(out, "Found some other id: {id}")?,
+        }
+
+        // The pattern playground
+        // - the match of `ch6::match_control_flow`, where `Location::Range(0, _)` can never be reached
+        let report = playground::evaluate(
+            "Location::Range(0, 5)",
+            &[
+                "Location::Point(_)",
+                "Location::Range(_, n)",
+                "Location::Range(0, _)",
+                "_",
+            ],
+        );
+        outln!(out, "{}", report.map_err(io::Error::other)?)?;
+        let report = playground::evaluate(
+            "Coin::Quarter(UsState::Alaska)",
+            &[
+                "Coin::Penny",
+                "Coin::Quarter(UsState::Alabama)",
+                "Coin::Quarter(state)",
+            ],
+        );
+        outln!(out, "{}", report.map_err(io::Error::other)?)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Syntax {
+    fn id(&self) -> &'static str {
+        "ch18::syntax"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(18, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Pattern Syntax"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["pattern-matching", "enums", "structs"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch18::refutability", "ch5::define_instantiate"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("syntax.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
@@ -8,14 +8,19 @@
 //!         * Name of each enum variant is an initializer function, can be used as function pointers
 //!     * Returning Closure
 //!         * closures can not be returned direcly, compiler does not know how much space to allocate for closure
+
+use std::io::{self, Write};
 #[derive(Debug)]
 #[allow(unused)]
 pub struct AdvancedFnClosure {}
 
 #[allow(unused)]
 impl AdvancedFnClosure {
-    pub fn print(&self) {
-        println!("\n======The note on advanced functions and closures======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(
+            out,
+            "\n======The note on advanced functions and closures======"
+        )?;
         // Function pointer
         fn add_one(x: i32) -> i32 {
             x + 1
@@ -26,7 +31,7 @@ impl AdvancedFnClosure {
         }
 
         let answer = do_twice(add_one, 5);
-        println!("\nThe answer is: {}", answer);
+        outln!(out, "\nThe answer is: {}", answer)?;
 
         // Function pointer and closure as arugment to function
         let list_of_numbers = vec![1, 2, 3];
@@ -35,12 +40,12 @@ impl AdvancedFnClosure {
 
         // - providing a closure
         let list_of_strings: Vec<String> = list_of_numbers.iter().map(|i| i.to_string()).collect();
-        println!("\nlist_of_strings is {:?}", list_of_strings);
+        outln!(out, "\nlist_of_strings is {:?}", list_of_strings)?;
 
         // - providing a function, `to_string` from `ToString` trait
         let list_of_strings2: Vec<String> =
             list_of_numbers.iter().map(ToString::to_string).collect();
-        println!("list_of_strings2 is {:?}", list_of_strings2);
+        outln!(out, "list_of_strings2 is {:?}", list_of_strings2)?;
 
         // Use enum variant as function pointer
         #[derive(Debug)]
@@ -51,7 +56,7 @@ impl AdvancedFnClosure {
 
         // - convert `u32` to `Value(u32)`
         let list_of_statuses: Vec<Status> = (0u32..20).map(Status::Value).collect();
-        println!("\nlist_of_statuses is {:?}", list_of_statuses);
+        outln!(out, "\nlist_of_statuses is {:?}", list_of_statuses)?;
 
         // Return a closre
         // - below code will not compile
@@ -60,5 +65,38 @@ impl AdvancedFnClosure {
         fn returns_closure2() -> Box<dyn Fn(i32) -> i32> {
             Box::new(|x| x + 1)
         }
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for AdvancedFnClosure {
+    fn id(&self) -> &'static str {
+        "ch19::advanced_fn_closure"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(19, 4)
+    }
+
+    fn title(&self) -> &'static str {
+        "Advanced Functions and Closures"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["closures", "functional"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch13::closures", "ch17::dyn_traits"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("advanced_fn_closure.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
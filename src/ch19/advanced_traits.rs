@@ -12,6 +12,7 @@
 //!         * using newtype pattern to get around this restriction, i.e. a tupe struct with one field as wrapper around the target type
 
 use std::fmt;
+use std::io::{self, Write};
 use std::ops::Add;
 
 // Use associated type in trait definiton and implementation
@@ -65,12 +66,12 @@ impl Add<Meters> for Millimeters {
 
 // Using fully qualified name to avoid ambiguity
 trait Pilot {
-    fn fly(&self);
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()>;
     fn name() -> String;
 }
 
 trait Wizard {
-    fn fly(&self);
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()>;
     fn name() -> String;
 }
 
@@ -78,8 +79,8 @@ struct Human;
 
 // - Tow traits that have `fly` method are implemented on `Human`
 impl Pilot for Human {
-    fn fly(&self) {
-        println!("This is your captain speaking.");
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "This is your captain speaking.")
     }
     fn name() -> String {
         String::from("Captain")
@@ -87,8 +88,8 @@ impl Pilot for Human {
 }
 
 impl Wizard for Human {
-    fn fly(&self) {
-        println!("Up!");
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "Up!")
     }
     fn name() -> String {
         String::from("Master")
@@ -97,8 +98,8 @@ impl Wizard for Human {
 
 // - a `fly` method is implemented on `HUman` directly
 impl Human {
-    fn fly(&self) {
-        println!("*waving arms furiously*");
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "*waving arms furiously*")
     }
     fn name() -> String {
         String::from("Mr")
@@ -109,15 +110,15 @@ impl Human {
 // - use `OutlinePrint: fmt::Display` to indicate that `OutlinePrint` only works for types that implement `Display`
 // - similar to trait bound
 trait OutlinePrint: fmt::Display {
-    fn outline_print(&self) {
+    fn outline_print(&self, out: &mut dyn Write) -> io::Result<()> {
         // - `to_string()` is a method in `Display` trait
         let output = self.to_string();
         let len = output.len();
-        println!("{}", "*".repeat(len + 4));
-        println!("*{}*", " ".repeat(len + 2));
-        println!("* {} *", output);
-        println!("*{}*", " ".repeat(len + 2));
-        println!("{}", "*".repeat(len + 4));
+        outln!(out, "{}", "*".repeat(len + 4))?;
+        outln!(out, "*{}*", " ".repeat(len + 2))?;
+        outln!(out, "* {} *", output)?;
+        outln!(out, "*{}*", " ".repeat(len + 2))?;
+        outln!(out, "{}", "*".repeat(len + 4))
     }
 }
 // - implement `Display` on `Point`
@@ -144,8 +145,8 @@ pub struct AdvancedTraits {}
 
 #[allow(unused)]
 impl AdvancedTraits {
-    pub fn print(&self) {
-        println!("\n======The note on AdvancedTraits======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on AdvancedTraits======")?;
 
         // Check `+` operator for `Point` instance
         assert_eq!(
@@ -157,15 +158,64 @@ impl AdvancedTraits {
         // - otherwise, Rust defaults to use the directly implemented methods, not those from traits
         let person = Human;
         // - methods that take `self` or `&self` as parameter
-        person.fly();
-        Pilot::fly(&person);
-        Wizard::fly(&person);
+        person.fly(out)?;
+        Pilot::fly(&person, out)?;
+        Wizard::fly(&person, out)?;
         // - methods that do not take `self` or `&self` as parameter
-        println!("person's Human name is: {}", Human::name());
-        println!("person's Pilot name is: {}", <Human as Pilot>::name());
-        println!("person's Wizard name is: {}", <Human as Wizard>::name());
+        outln!(out, "person's Human name is: {}", Human::name())?;
+        outln!(out, "person's Pilot name is: {}", <Human as Pilot>::name())?;
+        outln!(
+            out,
+            "person's Wizard name is: {}",
+            <Human as Wizard>::name()
+        )?;
         // Implement Display for Wrapper
         let w = Wrapper(vec![String::from("hello"), String::from("world")]);
-        println!("\nw = {}", w);
+        outln!(out, "\nw = {}", w)?;
+        Ok(())
+    }
+}
+
+use crate::exercise::Exercise;
+use crate::lesson::{Lesson, Section};
+
+const EXERCISES: &[Exercise] = &[Exercise {
+    name: "ch19_my_iterator",
+    task: "Implement `MyIterator` for `Counter`, yielding 1 to 5",
+    stub: include_str!("../../exercises/ch19_my_iterator/stub.rs"),
+    checks: include_str!("../../exercises/ch19_my_iterator/checks.rs"),
+}];
+
+impl Lesson for AdvancedTraits {
+    fn id(&self) -> &'static str {
+        "ch19::advanced_traits"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(19, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Advanced Traits"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["traits"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch17::dyn_traits"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("advanced_traits.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
     }
 }
@@ -14,19 +14,21 @@
 //!         * to work with DST, Rust provides the `Sized` trait to determine whether or not a type's size is known at compile time
 //!             * automatically implemented for everything whose size is known at compile time
 //!
+
+use std::io::{self, Write};
 #[derive(Debug)]
 #[allow(unused)]
 pub struct AdvancedTypes {}
 
 #[allow(unused)]
 impl AdvancedTypes {
-    pub fn print(&self) {
-        println!("\n======The note on advanced types======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on advanced types======")?;
         // Creating Type Synonyms with Type Aliases
         type Kilometers = i32;
         let x: i32 = 5;
         let y: Kilometers = 5;
-        println!("\nx + y = {}", x + y);
+        outln!(out, "\nx + y = {}", x + y)?;
         // - to reduce repeatition of long code
         type Thunk = Box<dyn Fn() + Send + 'static>;
         let f: Thunk = Box::new(|| println!("hi"));
@@ -52,6 +54,7 @@ impl AdvancedTypes {
         // Dynamicall Sized Types
         // - size of `str` can not be known at compile time so does compiler complain
 
+        // - the rejection (E0277) is checked by `tests/compile_fail/ch19_unsized_str.rs`
         // let s1: str = "Hello there!";
         // let s2: str = "How's it going?";
 
@@ -66,5 +69,38 @@ impl AdvancedTypes {
         // fn generic<T: Sized>(t: T) {
         //     // --snip--
         // }
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for AdvancedTypes {
+    fn id(&self) -> &'static str {
+        "ch19::advanced_types"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(19, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Advanced Types"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["traits", "generics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch17::dyn_traits"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("advanced_types.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -13,6 +13,8 @@
 //!             * custom drive, attibute-like, and function-like
 //!         * when creating procedural macros, the definitions must reside in their own crate with a special crate type
 
+use std::io::{self, Write};
+
 // Define a declarative macro
 // - `#[macro_export]` annotation indicates that this macro should be made available whenever
 // - the crate in which this macro is defined is brought into scope
@@ -45,7 +47,40 @@ pub struct Macros {}
 
 #[allow(unused)]
 impl Macros {
-    pub fn print(&self) {
-        println!("\n======The note on macros======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on macros======")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Macros {
+    fn id(&self) -> &'static str {
+        "ch19::macros"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(19, 5)
+    }
+
+    fn title(&self) -> &'static str {
+        "Macros"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["macros"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch8::vectors"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("macros.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
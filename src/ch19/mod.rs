@@ -1,5 +1,19 @@
+#![allow(dead_code, clippy::missing_safety_doc, clippy::useless_vec)]
 pub mod advanced_fn_closure;
 pub mod advanced_traits;
 pub mod advanced_types;
 pub mod macros;
 pub mod unsafe_rust;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(unsafe_rust::UnsafeRust {}),
+        Box::new(advanced_traits::AdvancedTraits {}),
+        Box::new(advanced_types::AdvancedTypes {}),
+        Box::new(advanced_fn_closure::AdvancedFnClosure {}),
+        Box::new(macros::Macros {}),
+    ]
+}
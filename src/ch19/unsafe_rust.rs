@@ -16,6 +16,8 @@
 //!             * are allowed to be null
 //!             * don't implement any automatic cleanup
 
+use std::io::{self, Write};
+
 static HELLO_WORLD: &str = "Hello, world!";
 static mut COUNTER: u32 = 0;
 
@@ -35,8 +37,8 @@ pub struct UnsafeRust {}
 
 #[allow(unused)]
 impl UnsafeRust {
-    pub fn print(&self) {
-        println!("\n======The note on unsafe Rust======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on unsafe Rust======")?;
         // Raw pointer from ref
         // - create in safe code
         let mut num = 5;
@@ -44,8 +46,8 @@ impl UnsafeRust {
         let r2 = &mut num as *mut i32;
         // - we must dereference a raw pointer in a `unsafe` block
         unsafe {
-            println!("\nr1 is {}", *r1);
-            println!("r2 is {}", *r2);
+            outln!(out, "\nr1 is {}", *r1)?;
+            outln!(out, "r2 is {}", *r2)?;
         }
 
         // Calling an Unsafe Function or Method
@@ -62,12 +64,12 @@ impl UnsafeRust {
 
         // - functions imported from other languanges are always unsafe
         unsafe {
-            println!("Absolute value of -3 according to C: {}", abs(-3));
+            outln!(out, "Absolute value of -3 according to C: {}", abs(-3))?;
         }
 
         // Accessing or Modifying a Mutable Static Variable
         // - `HELLO_WORLD` is an immutable static variable
-        println!("name is: {}", HELLO_WORLD);
+        outln!(out, "name is: {}", HELLO_WORLD)?;
 
         // - `COUNTER` is a mutable static variable
         // - modify it in unsafe code
@@ -81,7 +83,42 @@ impl UnsafeRust {
 
         unsafe {
             // - access it in unsafe code
-            println!("COUNTER: {}", COUNTER);
+            // - copy it out first, formatting it in place would create a shared reference to a mutable static
+            let counter = COUNTER;
+            outln!(out, "COUNTER: {}", counter)?;
         }
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for UnsafeRust {
+    fn id(&self) -> &'static str {
+        "ch19::unsafe_rust"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(19, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Unsafe Rust"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["unsafe"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::reference_borrow", "ch15::pointers_to_heap"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("unsafe_rust.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -0,0 +1,230 @@
+//! # Ch2 - Programming a Guessing Game
+//!     * the book takes the secret number from the `rand` crate, here `Rng` is a small generator of our own
+//!         * xorshift64*, i.e. a few shifts and xors of a 64-bit state and a multiplication, fine for games, not for secrets
+//!         * the same seed gives the same numbers, so a game can be replayed and tested
+//!     * `read_line` appends to a `String`, `trim` drops the `\n` before `parse`
+//!         * `let guess: u32 = ...` shadows the `String` named `guess`, and tells `parse` which type to produce
+//!     * `cmp` returns an `Ordering`: `Less`, `Greater` or `Equal`, and `match` must cover the three
+//!     * `Err(_) => continue` gives up on a line that is not a number
+//!         * `continue` has the type `!`, which coerces to any type, so this arm fits a `match` of `u32`s, see `ch19::advanced_types`
+//!     * `loop` repeats until the `break` of the winning arm
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug)]
+pub struct GuessingGame {}
+
+/// The seed of the lesson's game, so that its output is the same every run
+const SEED: u64 = 2;
+
+/// A seeded pseudo-random generator, xorshift64*
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // - a zero state would only ever give zeros, so the seed is scrambled first (splitmix64)
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Rng {
+            state: if z == 0 { 1 } else { z },
+        }
+    }
+
+    /// Seeded with the current time, a different game every run
+    pub fn from_time() -> Rng {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Rng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A number of `range`, each as likely as the others
+    pub fn gen_range(&mut self, range: RangeInclusive<u32>) -> u32 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = u64::from(high - low) + 1;
+        // - numbers past the last whole multiple of `span` are drawn again, `%` would favor the small ones
+        let zone = u64::MAX - u64::MAX % span;
+        loop {
+            let n = self.next_u64();
+            if n < zone {
+                return low + (n % span) as u32;
+            }
+        }
+    }
+}
+
+/// How a game ended
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// Found after this many guesses, the lines that were not numbers aside
+    Won(u32),
+    /// The input ended before the secret number was found
+    GaveUp,
+}
+
+/// Play a game reading the guesses from `input`
+///     * `echo` writes each guess after its prompt, as a terminal would, for input that is not typed, e.g. a script
+pub fn play(
+    secret_number: u32,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    echo: bool,
+) -> io::Result<Outcome> {
+    outln!(out, "Guess the number!")?;
+    let mut guesses = 0;
+    loop {
+        outln!(out, "Please input your guess.")?;
+
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            outln!(
+                out,
+                "No more guesses, the secret number was {secret_number}"
+            )?;
+            return Ok(Outcome::GaveUp);
+        }
+        if echo {
+            outln!(out, "{}", guess.trim_end())?;
+        }
+
+        // - the `Err` arm never produces a `u32`, it has the type `!`
+        let guess: u32 = match guess.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                outln!(out, "`{}` is not a number", guess.trim())?;
+                continue;
+            }
+        };
+        guesses += 1;
+
+        outln!(out, "You guessed: {guess}")?;
+
+        match guess.cmp(&secret_number) {
+            Ordering::Less => outln!(out, "Too small!")?,
+            Ordering::Greater => outln!(out, "Too big!")?,
+            Ordering::Equal => {
+                outln!(out, "You win!")?;
+                break;
+            }
+        }
+    }
+    Ok(Outcome::Won(guesses))
+}
+
+impl GuessingGame {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on the guessing game======")?;
+        let secret_number = Rng::new(SEED).gen_range(1..=100);
+        // - a scripted game: one guess that is not a number, one too small, one too big, then the right one
+        let script = format!("fifty\n1\n100\n{secret_number}\n");
+        let outcome = play(secret_number, &mut script.as_bytes(), out, true)?;
+        outln!(out, "{outcome:?}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for GuessingGame {
+    fn id(&self) -> &'static str {
+        "ch2::guessing_game"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(2, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Programming a Guessing Game"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["basics", "io", "pattern-matching"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch1::main"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("guessing_game.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(secret_number: u32, script: &str) -> (Outcome, String) {
+        let mut out = Vec::new();
+        let outcome = play(secret_number, &mut script.as_bytes(), &mut out, false).unwrap();
+        (outcome, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn stays_in_range_and_covers_it() {
+        let mut rng = Rng::new(0);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let n = rng.gen_range(1..=10);
+            assert!((1..=10).contains(&n));
+            seen[n as usize - 1] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(rng.gen_range(7..=7), 7);
+    }
+
+    #[test]
+    fn counts_the_guesses_to_win() {
+        let (outcome, out) = game(42, "50\n25\n42\n99\n");
+        assert_eq!(outcome, Outcome::Won(3));
+        assert!(out.contains("You guessed: 50\nToo big!\n"));
+        assert!(out.contains("You guessed: 25\nToo small!\n"));
+        assert!(out.ends_with("You guessed: 42\nYou win!\n"));
+    }
+
+    #[test]
+    fn skips_what_is_not_a_number() {
+        let (outcome, out) = game(7, "seven\n-7\n\n 7 \n");
+        assert_eq!(outcome, Outcome::Won(1));
+        assert!(out.contains("`seven` is not a number\n"));
+        assert!(out.contains("`-7` is not a number\n"));
+    }
+
+    #[test]
+    fn gives_up_at_the_end_of_the_input() {
+        let (outcome, out) = game(7, "1\n2");
+        assert_eq!(outcome, Outcome::GaveUp);
+        assert!(out.ends_with("No more guesses, the secret number was 7\n"));
+    }
+}
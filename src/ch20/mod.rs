@@ -0,0 +1,12 @@
+pub mod thread_pool;
+pub mod web_server;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(web_server::WebServer {}),
+        Box::new(thread_pool::ThreadPools {}),
+    ]
+}
@@ -0,0 +1,270 @@
+//! # Ch20.2 - Turning Our Single-Threaded Server into a Multithreaded Server
+//!     * a thread per request would let anyone exhaust the machine by sending many requests, a pool caps the threads
+//!     * `ThreadPool` keeps a fixed number of `Worker`s, each a thread waiting for `Job`s
+//!         * jobs go through one `mpsc` channel, whose single `Receiver` the workers share as `Arc<Mutex<Receiver<Job>>>`
+//!         * `Arc` for several owners, `Mutex` so that only one worker takes a job at a time, see `ch16::mutexes`
+//!     * `execute` takes the same kind of closure as `thread::spawn`, `FnOnce() + Send + 'static`
+//! # Ch20.3 - Graceful Shutdown and Cleanup
+//!     * dropping the pool drops the sender, so every worker's `recv` returns an error once the queued jobs are done
+//!     * `Drop` then joins each worker, so a job in progress is finished rather than cut short
+use crate::lesson::SharedOutput;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+#[derive(Debug)]
+pub struct ThreadPools {}
+
+/// A job is a closure run once by whichever worker receives it
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Where the workers report what they do, e.g. a `ThreadWriter`
+type Log = Box<dyn Write + Send>;
+
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    // - an `Option` so that `drop` can drop the sender before joining the workers
+    sender: Option<mpsc::Sender<Job>>,
+    log: Option<Log>,
+}
+
+/// Returned by `ThreadPool::build` for a pool of no thread
+#[derive(Debug, PartialEq)]
+pub struct PoolCreationError;
+
+impl fmt::Display for PoolCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a thread pool needs at least one thread")
+    }
+}
+
+impl std::error::Error for PoolCreationError {}
+
+impl ThreadPool {
+    /// Create a new ThreadPool
+    ///     * `size` is the number of threads in the pool
+    ///     * panics if `size` is zero, see `build` for a `Result` instead
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0);
+        ThreadPool::start(size, None::<io::Sink>)
+    }
+
+    pub fn build(size: usize) -> Result<ThreadPool, PoolCreationError> {
+        if size == 0 {
+            return Err(PoolCreationError);
+        }
+        Ok(ThreadPool::start(size, None::<io::Sink>))
+    }
+
+    /// A pool whose workers write what they do to `log`, as the book's `println!`s do
+    pub fn with_log<W>(size: usize, log: W) -> ThreadPool
+    where
+        W: Write + Send + Clone + 'static,
+    {
+        assert!(size > 0);
+        ThreadPool::start(size, Some(log))
+    }
+
+    fn start<W>(size: usize, log: Option<W>) -> ThreadPool
+    where
+        W: Write + Send + Clone + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        // - `with_capacity` preallocates, we know there will be exactly `size` workers
+        let mut workers = Vec::with_capacity(size);
+        for id in 0..size {
+            let log = log.clone().map(|log| Box::new(log) as Log);
+            workers.push(Worker::new(id, Arc::clone(&receiver), log));
+        }
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+            log: log.map(|log| Box::new(log) as Log),
+        }
+    }
+
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job = Box::new(f);
+        self.sender.as_ref().unwrap().send(job).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // - closes the channel, workers finish the queued jobs and then leave their loop
+        drop(self.sender.take());
+        for worker in &mut self.workers {
+            if let Some(log) = &mut self.log {
+                let _ = writeln!(log, "Shutting down worker {}", worker.id);
+            }
+            // - `take` moves the handle out of the `Option`, as `join` takes ownership of it
+            if let Some(thread) = worker.thread.take() {
+                thread.join().unwrap();
+            }
+        }
+    }
+}
+
+struct Worker {
+    id: usize,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>, mut log: Option<Log>) -> Worker {
+        let thread = thread::spawn(move || loop {
+            // - the lock guard is a temporary of this statement, so the lock is released before the job runs
+            //     * `while let Ok(job) = receiver.lock().unwrap().recv()` would hold it for the whole job
+            let message = receiver.lock().unwrap().recv();
+            match message {
+                Ok(job) => {
+                    if let Some(log) = &mut log {
+                        let _ = writeln!(log, "Worker {id} got a job; executing.");
+                    }
+                    job();
+                }
+                Err(_) => {
+                    if let Some(log) = &mut log {
+                        let _ = writeln!(log, "Worker {id} disconnected; shutting down.");
+                    }
+                    break;
+                }
+            }
+        });
+        Worker {
+            id,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl ThreadPools {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on thread pools======")?;
+        let mut out = SharedOutput::new(out);
+        // - which worker gets which job varies from run to run
+        let pool = ThreadPool::with_log(2, out.writer());
+        let (tx, rx) = mpsc::channel();
+        for n in 1..=4 {
+            let tx = tx.clone();
+            pool.execute(move || tx.send(n * n).unwrap());
+        }
+        drop(tx);
+        // - every job sends once, so `rx` ends when the four senders are dropped
+        let mut squares: Vec<i32> = rx.iter().collect();
+        squares.sort();
+        outln!(out, "Squares computed by the pool: {squares:?}")?;
+        // - graceful shutdown, the workers finish and are joined
+        drop(pool);
+        out.flush()
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for ThreadPools {
+    fn id(&self) -> &'static str {
+        "ch20::thread_pool"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(20, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Turning Our Single-Threaded Server into a Multithreaded Server"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["concurrency", "smart-pointers"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &[
+            "ch20::web_server",
+            "ch16::messages",
+            "ch16::mutexes",
+            "ch15::drop_trait",
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("thread_pool.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn runs_every_job_before_shutting_down() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(3);
+        for _ in 0..10 {
+            let done = Arc::clone(&done);
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(5));
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        // - dropping the pool waits for the queued jobs
+        drop(pool);
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn runs_jobs_in_parallel() {
+        let pool = ThreadPool::new(2);
+        let (tx, rx) = mpsc::channel();
+        // - the first job waits for the second, which only works with two workers
+        let (go_tx, go_rx) = mpsc::channel::<()>();
+        let first = tx.clone();
+        pool.execute(move || {
+            go_rx.recv().unwrap();
+            first.send("first").unwrap();
+        });
+        pool.execute(move || {
+            tx.send("second").unwrap();
+            go_tx.send(()).unwrap();
+        });
+        assert_eq!(rx.iter().take(2).collect::<Vec<_>>(), ["second", "first"]);
+    }
+
+    #[test]
+    fn refuses_an_empty_pool() {
+        assert_eq!(ThreadPool::build(0).err(), Some(PoolCreationError));
+        assert!(ThreadPool::build(1).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics_on_an_empty_pool() {
+        ThreadPool::new(0);
+    }
+
+    #[test]
+    fn logs_the_shutdown() {
+        let mut buf = Vec::new();
+        let mut shared = SharedOutput::new(&mut buf);
+        let pool = ThreadPool::with_log(2, shared.writer());
+        drop(pool);
+        shared.flush().unwrap();
+        let log = String::from_utf8(buf).unwrap();
+        for id in 0..2 {
+            assert!(log.contains(&format!("Shutting down worker {id}\n")));
+            assert!(log.contains(&format!("Worker {id} disconnected; shutting down.\n")));
+        }
+    }
+}
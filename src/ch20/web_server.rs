@@ -0,0 +1,202 @@
+//! # Ch20.1 - Building a Single-Threaded Web Server
+//!     * HTTP runs over TCP, `TcpListener::bind` listens on an address and `incoming` yields a `TcpStream` per connection
+//!         * port 0 asks the OS for any free port, `local_addr` tells which one it picked
+//!     * a request is text: a request line `GET / HTTP/1.1`, headers, an empty line, and an optional body
+//!         * each line ends with CRLF, `\r\n`
+//!     * a response is a status line `HTTP/1.1 200 OK`, headers, an empty line, and the body
+//!         * `Content-Length` tells the client where the body ends
+//!     * `handle_connection` only matches the request line, `/` gets `hello.txt` and anything else the 404 page
+//!     * `/sleep` answers after a while, a single thread would keep every other client waiting meanwhile
+//!         * so `Server` hands each connection to a `ThreadPool`, see `ch20::thread_pool`
+use crate::ch20::thread_pool::ThreadPool;
+use std::io::{self, prelude::*, BufReader};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct WebServer {}
+
+pub const HELLO: &str = include_str!("hello.txt");
+pub const NOT_FOUND: &str = include_str!("404.html");
+
+/// How long `/sleep` takes to answer by default, as in the book
+pub const SLEEP: Duration = Duration::from_secs(5);
+
+pub struct Server {
+    listener: TcpListener,
+    pool: ThreadPool,
+    sleep: Duration,
+}
+
+impl Server {
+    /// Listen on `addr` and serve with `threads` workers
+    ///     * e.g. `127.0.0.1:7878`, or `127.0.0.1:0` for any free port
+    pub fn bind<A: ToSocketAddrs>(addr: A, threads: usize) -> io::Result<Server> {
+        let pool = ThreadPool::build(threads)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            pool,
+            sleep: SLEEP,
+        })
+    }
+
+    /// Make `/sleep` answer after `sleep` rather than `SLEEP`
+    pub fn with_sleep(mut self, sleep: Duration) -> Server {
+        self.sleep = sleep;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serve connections, only the first `limit` of them if any, then shut down
+    ///     * returns once the pool has been dropped, i.e. every accepted connection was answered
+    pub fn serve(self, limit: Option<usize>) -> io::Result<()> {
+        let sleep = self.sleep;
+        let incoming = self.listener.incoming();
+        // - `take` is how the book stops after two requests to show the graceful shutdown
+        for stream in incoming.take(limit.unwrap_or(usize::MAX)) {
+            let stream = stream?;
+            self.pool.execute(move || {
+                // - a client that hangs up early is its own problem, the server goes on
+                let _ = handle_connection(stream, sleep);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Read one request from `stream` and write the response
+pub fn handle_connection(mut stream: TcpStream, sleep: Duration) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // - the headers are read but ignored, up to the empty line that ends them
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+    let response = respond(request_line.trim_end(), sleep);
+    stream.write_all(response.as_bytes())
+}
+
+/// The whole response to a request line, e.g. `GET / HTTP/1.1`
+pub fn respond(request_line: &str, sleep: Duration) -> String {
+    const TEXT: &str = "text/plain; charset=utf-8";
+    const HTML: &str = "text/html; charset=utf-8";
+    let (status_line, content_type, contents) = match request_line {
+        "GET / HTTP/1.1" => ("HTTP/1.1 200 OK", TEXT, HELLO),
+        "GET /sleep HTTP/1.1" => {
+            thread::sleep(sleep);
+            ("HTTP/1.1 200 OK", TEXT, HELLO)
+        }
+        _ => ("HTTP/1.1 404 NOT FOUND", HTML, NOT_FOUND),
+    };
+    let length = contents.len();
+    // - `Connection: close`, as the stream is dropped after one response
+    format!(
+        "{status_line}\r\nContent-Length: {length}\r\nContent-Type: {content_type}\r\nConnection: close\r\n\r\n{contents}"
+    )
+}
+
+impl WebServer {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on the web server======")?;
+        let server = Server::bind("127.0.0.1:0", 2)?;
+        let addr = server.local_addr()?;
+        // - two requests, then `serve` returns and the pool shuts down
+        let serving = thread::spawn(move || server.serve(Some(2)));
+        let paths = ["/", "/missing"];
+        let mut connected = 0;
+        let mut requests = || -> io::Result<()> {
+            for path in paths {
+                let mut stream = TcpStream::connect(addr)?;
+                connected += 1;
+                write!(stream, "GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n")?;
+                let mut response = String::new();
+                stream.read_to_string(&mut response)?;
+                let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+                outln!(out, "GET {path} -> {}", head.lines().next().unwrap_or(""))?;
+                outln!(
+                    out,
+                    "{} bytes, first line: {}",
+                    body.len(),
+                    body.lines().next().unwrap_or("")
+                )?;
+            }
+            Ok(())
+        };
+        let requested = requests();
+        // - a failed request must not leave `serve` blocked in `accept`, so the connections it still waits for are made empty
+        for _ in connected..paths.len() {
+            let _ = TcpStream::connect(addr);
+        }
+        let served = serving.join().unwrap();
+        requested.and(served)
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for WebServer {
+    fn id(&self) -> &'static str {
+        "ch20::web_server"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(20, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Building a Single-Threaded Web Server"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["io", "concurrency"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch9::results", "ch16::threads"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("web_server.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serves_hello_at_the_root() {
+        let response = respond("GET / HTTP/1.1", Duration::ZERO);
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(&format!("\r\nContent-Length: {}\r\n", HELLO.len())));
+        assert!(response.ends_with(&format!("\r\n\r\n{HELLO}")));
+    }
+
+    #[test]
+    fn serves_the_404_page_otherwise() {
+        for request_line in [
+            "GET /missing HTTP/1.1",
+            "POST / HTTP/1.1",
+            "GET / HTTP/1.0",
+            "",
+        ] {
+            let response = respond(request_line, Duration::ZERO);
+            assert!(
+                response.starts_with("HTTP/1.1 404 NOT FOUND\r\n"),
+                "{request_line}"
+            );
+            assert!(response.ends_with(NOT_FOUND));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 // Ch3.5 - Control Flow
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct ControlFlow{
 }
@@ -16,19 +17,19 @@ pub struct ControlFlow{
 
 impl ControlFlow{
 #[allow(unused_variables)]
-    pub fn print(&self) {
-        println!("\n======The note of control flow======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of control flow======")?;
     // if expression
         let number = 3;
     
         if number % 4 == 0 {
-            println!("number is divisible by 4");
+            outln!(out, "number is divisible by 4")?;
         } else if number % 3 == 0 {
-            println!("number is divisible by 3");
+            outln!(out, "number is divisible by 3")?;
         } else if number % 2 == 0 {
-            println!("number is divisible by 2");
+            outln!(out, "number is divisible by 2")?;
         } else {
-            println!("number is not divisible by 4, 3, or 2");
+            outln!(out, "number is not divisible by 4, 3, or 2")?;
         }
 
         // - condition must be a bool type
@@ -44,10 +45,10 @@ impl ControlFlow{
         let condition = true;
             // - in the {}, there must be expressions, i.e. return values
         let number = if condition { 5 } else { 6 };
-        println!("The value of if expression is: {number}");
+        outln!(out, "The value of if expression is: {number}")?;
             // - in below if expression, no value will be assigned to number
         let _number = if condition { 5; } else { 6; };
-        println!("The value of if expression is: {:?}", _number);
+        outln!(out, "The value of if expression is: {:?}", _number)?;
             // - return value types in branches must be the same
                 // - .e.g, below statements will compile
         // let _number = if condition { 5 } else { "six" };
@@ -67,18 +68,18 @@ impl ControlFlow{
                 break counter * 2;
             }
         };
-        println!("The result is expected to be 20: {result}");
+        outln!(out, "The result is expected to be 20: {result}")?;
             // - use `continue` to discard remaining code in current iteration and go on to next
             // - use loop labels to disambiguate multiple lopps
                 // - by default `break` and `continue` apply to innermost loop
         let mut count = 0;
                 // - label outer loop as `conting_up`
         'counting_up: loop {
-            println!("count = {count}");
+            outln!(out, "count = {count}")?;
             let mut remaining = 10;
     
             loop {
-                println!("remaining = {remaining}");
+                outln!(out, "remaining = {remaining}")?;
                 if remaining == 9 {
                     break;
                 }
@@ -91,26 +92,59 @@ impl ControlFlow{
     
             count += 1;
         }
-        println!("End count = {count}");
+        outln!(out, "End count = {count}")?;
 
         // Conditional loop with `while`
         let mut number = 3;
         while number != 0 {
-            println!("{number}!");
+            outln!(out, "{number}!")?;
             number -= 1;
         }
-        println!("\nLIFTOFF!!!");
+        outln!(out, "\nLIFTOFF!!!")?;
         // Loop over a collection
         let a = [10, 20, 30, 40, 50];
         for element in a {
-            println!("the value is {element}");
+            outln!(out, "the value is {element}")?;
         }
             // - use range as the collection
                 // - (1..4) means [1,3) in math
                 // - .rev() change the range to reverse order
         for number in (1..4).rev() {
-            println!("{number}!");
+            outln!(out, "{number}!")?;
         }
-        println!("LIFTOFF!!!");
+        outln!(out, "LIFTOFF!!!")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for ControlFlow {
+    fn id(&self) -> &'static str {
+        "ch3::control_flow"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(3, 5)
+    }
+
+    fn title(&self) -> &'static str {
+        "Control Flow"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["basics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch3::functions"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("control_flow.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -1,12 +1,13 @@
 // Ch3.2 - Data Types
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct DataTypes {
 }
 impl DataTypes {
 #[allow(unused_variables)]
 
-    pub fn print(&self) {
-        println!("\n======The note of data types======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of data types======")?;
         // Integer
             // - default to i32
             // - signed vs unsigned
@@ -40,8 +41,8 @@ impl DataTypes {
             // - Unpack a tuple
                 // - tup is not destructed after unpacking
         let (x, y, z) = tup;
-        println!("\nThe value of y is: {y}");
-        println!("The values of tup is: {:?}", tup);
+        outln!(out, "\nThe value of y is: {y}")?;
+        outln!(out, "The values of tup is: {:?}", tup)?;
         let x: (i32, f64, u8) = (500, 6.4, 1);
             // - use dot(.) operator to access individual elements
                 // - read or write/mutate
@@ -51,10 +52,10 @@ impl DataTypes {
 
                 // - tup must be mutable
         let mut x: (i32, i32) = (1, 2);
-        println!("\nThe values of x is: {:?}", x);
+        outln!(out, "\nThe values of x is: {:?}", x)?;
         x.0 = 0;
         x.1 += 5;
-        println!("After mutation, the values of x is: {:?}", x);
+        outln!(out, "After mutation, the values of x is: {:?}", x)?;
 
         // Array
             // - fixed length, unlike in c++
@@ -75,6 +76,38 @@ impl DataTypes {
         let second = a[1];
                     // - e.g., compiler will raise error for below statement 
         // let to_panic = a[10];
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for DataTypes {
+    fn id(&self) -> &'static str {
+        "ch3::data_types"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(3, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Data Types"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["basics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch3::var_mutability"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("data_types.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
-} 
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
 // Ch3.3 - Functions
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct Functions{
 }
@@ -7,13 +8,13 @@ impl Functions{
     // Function 
         // - function name should be snake case, i.e., lower case, with underscore connecting words
         // - must declare parameter type
-    pub fn print(&self) {
-        println!("\n======The note of functions======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of functions======")?;
 
-        print_labeled_measurement(5, 'h');
+        print_labeled_measurement(5, 'h', out)?;
         
-        fn print_labeled_measurement(value: i32, unit_label: char) {
-            println!("\nThe measurement is: {value}{unit_label}");
+        fn print_labeled_measurement(value: i32, unit_label: char, out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "\nThe measurement is: {value}{unit_label}")
         }
     // Statement and expression
         // - statements are instructions that perform some action and do not return a value
@@ -39,16 +40,16 @@ impl Functions{
                 let x = 3;
                 x + 1
             };
-            println!("\nThe value of y is: {y}");
+            outln!(out, "\nThe value of y is: {y}")?;
     
     // Functions with return value
         // five has no parameter, return 5 which is the last expression.
         fn five() -> i32 {5}
         let x = five();
-        println!("\nThe value returned by five() is: {x}");
+        outln!(out, "\nThe value returned by five() is: {x}")?;
 
         let x = plus_one(5);
-        println!("The value returned by plus_one(5) is: {x}");
+        outln!(out, "The value returned by plus_one(5) is: {x}")?;
         
         fn plus_one(x: i32) -> i32 {
             // this is an expression which determines return value
@@ -56,5 +57,38 @@ impl Functions{
             // this is a statment, return no value, so will the function
             // x +1;
         }
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Functions {
+    fn id(&self) -> &'static str {
+        "ch3::functions"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(3, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Functions"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["basics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch3::data_types"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("functions.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
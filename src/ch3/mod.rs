@@ -1,4 +1,23 @@
+#![allow(
+    clippy::empty_line_after_doc_comments,
+    clippy::empty_line_after_outer_attr,
+    clippy::identity_op,
+    clippy::let_unit_value,
+    clippy::no_effect
+)]
 pub mod data_types;
 pub mod var_mutability;
 pub mod functions;
 pub mod control_flow;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(var_mutability::VarMutability {}),
+        Box::new(data_types::DataTypes {}),
+        Box::new(functions::Functions {}),
+        Box::new(control_flow::ControlFlow {}),
+    ]
+}
@@ -1,4 +1,5 @@
 // Ch3.1 - Variable and Mutability    
+use std::io::{self, Write};
 const ONE_HOUR_IN_SECONDS: u32 = 60 * 60 * 1;
 
 #[derive(Debug)]
@@ -7,18 +8,18 @@ pub struct VarMutability {
 
 impl VarMutability {
 
-    pub fn print(&self) {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
 
-        println!("\n======The note of varible and mutability======");
+        outln!(out, "\n======The note of varible and mutability======")?;
         // By default a var is imutable 
         // let x = 5;
             // - unliess explicitly make it
         let mut x = 5;
-        println!("\nThe value of x is: {x}");
+        outln!(out, "\nThe value of x is: {x}")?;
         x = 6;
             // - but x's type is not immutable
         // x = "x";
-        println!("The value of x is: {x}");
+        outln!(out, "The value of x is: {x}")?;
 
         // Constant is not just immutable
             // - use `const` key word for declaration and definition
@@ -27,9 +28,9 @@ impl VarMutability {
             // - must be set to const expression
                 // - not result from expression that is evaluated at run-time
         const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
-        println!("\nThere are: {THREE_HOURS_IN_SECONDS} seconds in three hours.");
+        outln!(out, "\nThere are: {THREE_HOURS_IN_SECONDS} seconds in three hours.")?;
         const TWO_HOURS_IN_SECONDS: u32 = ONE_HOUR_IN_SECONDS * 2;
-        println!("There are: {TWO_HOURS_IN_SECONDS} seconds in two hours.");
+        outln!(out, "There are: {TWO_HOURS_IN_SECONDS} seconds in two hours.")?;
 
         // Shadowing
             // - declaring a new var with same name shadows the previously defined one
@@ -40,15 +41,47 @@ impl VarMutability {
         {
                 // y with value of 6 is shadowed by y = y * 1
             let y = y * 2;
-            println!("\nThe value of y in the inner scope is: {y}");
+            outln!(out, "\nThe value of y in the inner scope is: {y}")?;
                 // y in this inner scope ceases to exist after this point
         }
-        println!("The value of y is: {y}");
+        outln!(out, "The value of y is: {y}")?;
             // - we can shadow with a different type
         let spaces = "   ";
-        println!("\nThe value of spaces is: {spaces}");
+        outln!(out, "\nThe value of spaces is: {spaces}")?;
         let spaces = spaces.len();
-        println!("The value of spaces after shadowing is: {spaces}");
+        outln!(out, "The value of spaces after shadowing is: {spaces}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for VarMutability {
+    fn id(&self) -> &'static str {
+        "ch3::var_mutability"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(3, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Variables and Mutability"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["basics"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch1::main"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("var_mutability.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
-}
\ No newline at end of file
+}
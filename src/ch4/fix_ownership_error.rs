@@ -1,11 +1,12 @@
 // Ch4.3 - Fix Ownership Error
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct FixOwnershipError{
 }
 
 impl FixOwnershipError{
-    pub fn print(&self) {
-        println!("\n======The note of Fix Ownership Error======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of Fix Ownership Error======")?;
 
     // Fixing an Unsafe Program: Returning a Reference to the Stack
         // - below is an unsafe program, i.e., compiler does allow returning a ref to local variable
@@ -22,7 +23,7 @@ impl FixOwnershipError{
             s
         }
         let res1 = return_a_string1();
-        println!("\nResult of return_a_string1() is: {res1}");
+        outln!(out, "\nResult of return_a_string1() is: {res1}")?;
 
         // - remedy 2 - return a string literal
             // - which lives forever but only applies if we intend not to change the string so heap allocation is not necessary
@@ -30,7 +31,7 @@ impl FixOwnershipError{
             "Hello world"
         }
         let res2 = return_a_string2();
-        println!("Result of return_a_string2() is: {res2}");
+        outln!(out, "Result of return_a_string2() is: {res2}")?;
 
         // - remedy 3 - defer borrow-checking to runtime by using garbage collection
             // - more details in ch15
@@ -40,7 +41,7 @@ impl FixOwnershipError{
             Rc::clone(&s)
         }
         let res3 = return_a_string3();
-        println!("Result of return_a_string3() is: {res3}");
+        outln!(out, "Result of return_a_string3() is: {res3}")?;
 
         // - remody 4 - have the caller provide a slot to put the string
             // - using mutable ref
@@ -49,7 +50,7 @@ impl FixOwnershipError{
         }
         let mut output = String::new();
         return_a_string4(&mut output);
-        println!("Result of return_a_string4() is: {output}");
+        outln!(out, "Result of return_a_string4() is: {output}")?;
 
     // Fixing an Unsafe Program: Not Enough Permissions
         // - below is an unsafe program trying to achieve ideally: ["Ferris", "Jr."] => "Ferris Jr. Esq."
@@ -73,8 +74,8 @@ impl FixOwnershipError{
 
         let mut name = vec![String::from("Ferris")];
         let full = stringify_name_with_title1(&mut name);
-        println!("\nResult of stringify_name_with_title1 is: {}", full);
-        println!("After stringify_name_with_title1 name is: {:?}", name);
+        outln!(out, "\nResult of stringify_name_with_title1 is: {}", full)?;
+        outln!(out, "After stringify_name_with_title1 name is: {:?}", name)?;
         
         // - remedy 2 - take ownership of name
             // - and return the ownership
@@ -86,8 +87,8 @@ impl FixOwnershipError{
         }
             // - old name is unusable afterward unless we return it and assign to the same variable name
         let (name, full) = stringify_name_with_title2(name);
-        println!("\nResult of stringify_name_with_title2 is: {}", full);
-        println!("After stringify_name_with_title2 name is: {:?}", name);
+        outln!(out, "\nResult of stringify_name_with_title2 is: {}", full)?;
+        outln!(out, "After stringify_name_with_title2 name is: {:?}", name)?;
         
         // - remedy 3 - change parameter name to immutable ref
             // - and clone it in function scope
@@ -99,9 +100,9 @@ impl FixOwnershipError{
             full
         }
         let full = stringify_name_with_title3(&name);
-        println!("\nResult of stringify_name_with_title3 is: {}", full);
+        outln!(out, "\nResult of stringify_name_with_title3 is: {}", full)?;
             // - name is unchanged
-        println!("After stringify_name_with_title3 name is: {:?}", name);
+        outln!(out, "After stringify_name_with_title3 name is: {:?}", name)?;
         
     // Fixing an Unsafe Program: Aliasing and Mutating a Data Structure
         // - below is an unsafe program because
@@ -109,6 +110,7 @@ impl FixOwnershipError{
             // - thus rejected by compiler
             // - key signt to fix this problem is shorten the lifetime of largest to not overlap with dst.push()
 
+        // - the rejection (E0502) is checked by `tests/compile_fail/ch4_add_big_strings.rs`
         // fn add_big_strings(dst: &mut Vec<String>, src: &[String]) {
         //     let largest: &String = dst.iter().max_by_key(|s| s.len()).unwrap();
         //     for s in src {
@@ -132,7 +134,7 @@ impl FixOwnershipError{
         let mut dst: Vec<String> = vec!["a".into(), "bc".into(), "def".into()];
         let src: Vec<String> = vec!["ghil".into(), "klm".into(), "nopq".into()];
         add_big_strings1(&mut dst, &src);
-        println!("\nAfter add_big_strings1, the dst is: {:?}", dst);
+        outln!(out, "\nAfter add_big_strings1, the dst is: {:?}", dst)?;
 
         // - remedy 2 - complete comparison before mutation
         fn add_big_strings2(dst: &mut Vec<String>, src: &[String]) {
@@ -142,7 +144,7 @@ impl FixOwnershipError{
         }
         let src: Vec<String> = vec!["rstu".into(), "vwxyz".into(), "123".into()];
         add_big_strings2(&mut dst, &src);
-        println!("After add_big_strings2, the dst is: {:?}", dst);
+        outln!(out, "After add_big_strings2, the dst is: {:?}", dst)?;
 
         // - remedy 2 - copy largest len and compare
             // - since we do not need the content of String ,just their length
@@ -156,7 +158,7 @@ impl FixOwnershipError{
         }
         let src: Vec<String> = vec!["456789".into(), "01".into(), "123".into()];
         add_big_strings3(&mut dst, &src);
-        println!("After add_big_strings3, the dst is: {:?}", dst);
+        outln!(out, "After add_big_strings3, the dst is: {:?}", dst)?;
 
     // Fixing an Unsafe Program: Copying vs. Moving out of a Collection
         // - if a value does not own heap data, then it can be copied without a move
@@ -167,13 +169,13 @@ impl FixOwnershipError{
 
         // - below program works because var is at the stock
             // - becasue i32 has Copy trait
-        fn move_out_of_collection() {
+        fn move_out_of_collection(out: &mut dyn Write) -> io::Result<()> {
             let v: Vec<i32> = vec![0, 1, 2];
             let n_ref: &i32 = &v[0];
             let n: i32 = *n_ref;
-            println!("\nAfter assign *n_ref to n, n is {n}");
+            outln!(out, "\nAfter assign *n_ref to n, n is {n}")
         }
-        move_out_of_collection();
+        move_out_of_collection(out)?;
 
         // - below program does not work becase String is in heap
             // - and String does not have a Copy trait so *s_ref is trying to take ownership from v 
@@ -190,29 +192,29 @@ impl FixOwnershipError{
             // - immutable ref
         let v: Vec<String>= vec!["abc".into(), "def".into(), "ghi".into()];
         let s_ref = &v[0];
-        println!("s_ref is immutable ref to {s_ref}");
+        outln!(out, "s_ref is immutable ref to {s_ref}")?;
             // - clone
         let mut s = v[0].clone();
         s.push('!'); 
-        println!("s is mutable String:{s}");
+        outln!(out, "s is mutable String:{s}")?;
             // - Vec::remove
         let mut v: Vec<String>= vec!["abc".into(), "def".into(), "ghi".into()];
         let mut s = v.remove(0);
         s.push('!'); 
-        println!("s is mutable String:{s}");
-        println!("After v.remove(), v is now: {:?}", v);
+        outln!(out, "s is mutable String:{s}")?;
+        outln!(out, "After v.remove(), v is now: {:?}", v)?;
     
     // Fixing a Safe Program:Mutating Different Tuple Fields
         // - below safe_mutate() works
-        fn safe_mutate() {
+        fn safe_mutate(out: &mut dyn Write) -> io::Result<()> {
             let mut name: (String, String) = ("Ferris".into(), "Rustacean".into());
             // - this assignment borrows name.0 and name but not name.1
             let first = &name.0;
             // - we can mutate name.1
             name.1.push_str(", Esq.");
-            println!("\nAfter name.1.push_str(), first is {first}, name.1 is {}", name.1);
+            outln!(out, "\nAfter name.1.push_str(), first is {first}, name.1 is {}", name.1)
         }
-        safe_mutate();
+        safe_mutate(out)?;
         // - below safe_mutate() does not work
         fn safe_mutate1() {
             fn get_first(name: &(String, String)) -> &String {
@@ -230,13 +232,13 @@ impl FixOwnershipError{
 
     // Fixing a Safe Program: Mutating Different Array Elements
         // - below program works
-        fn mut_ref_arr() {
+        fn mut_ref_arr(out: &mut dyn Write) -> io::Result<()> {
             let mut a = [0,1,2,3];
             let x = &mut a[1];
             *x += 1;
-            println!("\nAfter *x += 1, a is now {a:?}");
+            outln!(out, "\nAfter *x += 1, a is now {a:?}")
         }
-        mut_ref_arr();
+        mut_ref_arr(out)?;
         // - below program does not work
             // - Rust's borrow checker does not contain different paths for a[0], a[1], and so on. 
             // - it uses a single path a[_] that represents all indexes of a because Rust cannot always determine the value of an index
@@ -252,15 +254,48 @@ impl FixOwnershipError{
         // }
 
         // - workaround use standard library, e.g., slice::split_at_mut
-        fn mut_ref_arr2() {
+        fn mut_ref_arr2(out: &mut dyn Write) -> io::Result<()> {
             let mut a = [0, 1, 2, 3];
             // - split_at_mut divides one mutable slice into two, at provided index
             let (a_l, a_r) = a.split_at_mut(2);
             let x = &mut a_l[1];
             let y = &a_r[0];
             *x += *y;
-            println!("After *x += *y, a is now {a:?}");
+            outln!(out, "After *x += *y, a is now {a:?}")
         }
-        mut_ref_arr2();
+        mut_ref_arr2(out)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for FixOwnershipError {
+    fn id(&self) -> &'static str {
+        "ch4::fix_ownership_error"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(4, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Fixing Ownership Errors"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["ownership", "borrowing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::reference_borrow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("fix_ownership_error.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
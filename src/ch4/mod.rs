@@ -1,4 +1,26 @@
+#![allow(
+    clippy::borrowed_box,
+    clippy::empty_line_after_doc_comments,
+    clippy::explicit_auto_deref,
+    clippy::let_and_return,
+    clippy::needless_borrow,
+    clippy::ptr_arg,
+    clippy::redundant_slicing,
+    clippy::unnecessary_mut_passed
+)]
 pub mod ownership;
 pub mod reference_borrow;
 pub mod fix_ownership_error;
 pub mod slice_type;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(ownership::Ownership {}),
+        Box::new(reference_borrow::ReferenceBorrow {}),
+        Box::new(fix_ownership_error::FixOwnershipError {}),
+        Box::new(slice_type::SliceType {}),
+    ]
+}
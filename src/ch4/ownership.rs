@@ -1,11 +1,12 @@
 // Ch4.1 - What Is Ownership?
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct Ownership{
 }
 
 impl Ownership{
-    pub fn print(&self) {
-        println!("\n======The note of ownership======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of ownership======")?;
 
     // Variable Live in the Stack
         // - variables live in frames
@@ -14,11 +15,11 @@ impl Ownership{
             // - After a function returns, Rust deallocates the function's frame, aka, freeing, dropping
             // - the most recent frame added is always next frame freed
         let a = 5;
-        println!("a is now: {a}");
+        outln!(out, "a is now: {a}")?;
         let mut b = a;
-        println!("After asigned to b, a is now: {a}, b is {b}");
+        outln!(out, "After asigned to b, a is now: {a}, b is {b}")?;
         b += 1;
-        println!("After increment 1 to b, a is now: {a}, b is {b}");
+        outln!(out, "After increment 1 to b, a is now: {a}, b is {b}")?;
     // Boxes Live in the Heap
         // - heap is a separate region of memory where data can live indefinitely.
             // - not tied to a specific stack frame
@@ -43,7 +44,7 @@ impl Ownership{
         let full = add_suffix(first);
 
     // Variables Cannot Be Used After Being Moved
-        println!("\nAfter add_suffix(), full is now: {full}");
+        outln!(out, "\nAfter add_suffix(), full is now: {full}")?;
             // - since first is moved 
         // println!("{first}");
         fn add_suffix(mut name: String) -> String {
@@ -57,12 +58,70 @@ impl Ownership{
         let first_clone = first.clone();
         let full = add_suffix(first_clone);
         // - first is still usable
-        println!("\nUsing clone, full is: {full}, first is still available: {first}");
+        outln!(out, "\nUsing clone, full is: {full}, first is still available: {first}")?;
         
     // Summary of Ownership
         // - all heap data must be owned by exactly one variable
         // - Rust deallocates heap data once its owner goes out of scope
         // - Ownership can be transferred by moves, which happen on assignments and function calls.
         // - Heap data can only be accessed through its current owner, not a previous owner.
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+use crate::compare::Comparison;
+use crate::lesson::{Lesson, Section};
+
+const CPP: &[Comparison] = &[Comparison {
+    topic: "`let s2 = s1;` vs copy and `std::move`",
+    cpp: "\
+#include <string>
+#include <utility>
+
+void ownership() {
+    std::string s1 = \"hello\";
+    std::string s2 = s1;            // a deep copy, both strings stay usable
+    std::string s3 = std::move(s1); // `s1` is left \"valid but unspecified\"
+    s1.append(\" world\");            // compiles, and works on whatever is left in `s1`
+}
+",
+    pitfalls: &[
+        "`=` copies a `std::string` implicitly, where Rust moves a `String` and makes copies explicit with `clone`",
+        "a moved-from object can still be used, the borrow checker rejects any use of a moved `String`",
+        "`std::move` is only a cast, nothing checks that the source is not used afterwards",
+    ],
+}];
+
+impl Lesson for Ownership {
+    fn id(&self) -> &'static str {
+        "ch4::ownership"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(4, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "What Is Ownership?"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["ownership"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch3::control_flow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("ownership.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        CPP
+    }
+}
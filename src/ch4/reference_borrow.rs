@@ -1,45 +1,46 @@
 // Ch4.2 - Reference and Borrow
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct ReferenceBorrow{
 }
 
 impl ReferenceBorrow{
-    pub fn print(&self) {
-        println!("\n======The note of reference and borrow======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of reference and borrow======")?;
         let m1 = String::from("Hello");
         let m2 = String::from("world");
-        let (m1_again, m2_again) = greet(m1, m2);
-        println!("After greet, m1_again is {m1_again}, m2_again is {m2_again}");
+        let (m1_again, m2_again) = greet(m1, m2, out)?;
+        outln!(out, "After greet, m1_again is {m1_again}, m2_again is {m2_again}")?;
 
-        greet2(&m1_again, &m2_again);
-        println!("After greet2, m1_again is {m1_again}, m2_again is {m2_again}");
+        greet2(&m1_again, &m2_again, out)?;
+        outln!(out, "After greet2, m1_again is {m1_again}, m2_again is {m2_again}")?;
     
         // this greet takes ownership of g1 and g2, and return ownership
             // - this is inconvenient
-        fn greet(g1: String, g2: String) -> (String, String) {
-            println!("{} {}!", g1, g2);
-            (g1, g2)
+        fn greet(g1: String, g2: String, out: &mut dyn Write) -> io::Result<(String, String)> {
+            outln!(out, "{} {}!", g1, g2)?;
+            Ok((g1, g2))
         }
     // References Are Non-Owning Pointers
         // - greet2 takes ref as parameter, does not take ownership of arguments
-        fn greet2(g1: &String, g2: &String) {
-            println!("{} {}!", g1, g2);
+        fn greet2(g1: &String, g2: &String, out: &mut dyn Write) -> io::Result<()> {
+            outln!(out, "{} {}!", g1, g2)
         }
     
     // Dereferencing a Pointer Accesses Its Data
     let mut x: Box<i32> = Box::new(1); // x points to a value in heap
     let a: i32 = *x;         // *x reads the heap value, so a = 1
-    println!("\na is now: {a}");
+    outln!(out, "\na is now: {a}")?;
     *x += 1;                 // *x on the left-side modifies the heap value,
                                 //     so x points to the value 2
-    println!("x is now: {x}");
+    outln!(out, "x is now: {x}")?;
     let r1: &Box<i32> = &x;  // r1 points to x on the stack
     let b: i32 = **r1;       // two dereferences get us to the heap value
-    println!("b is now: {b}");
+    outln!(out, "b is now: {b}")?;
     
     let r2: &i32 = &*x;      // r2 points to the heap value directly
     let c: i32 = *r2;    // so only one dereference is needed to read it
-    println!("c is now: {c}");
+    outln!(out, "c is now: {c}")?;
 
         // - Rust implicitly inserts dereference and referecne in some cases
             // - multiple times if necessary
@@ -69,10 +70,10 @@ impl ReferenceBorrow{
             // - actually, such push is not allowed by compiler, error message is v is borrowed as immutable by num
                 // - cannot be borrowed by push as mutable
     // v.push(4);
-    println!("Third element through *num, is {}", *num);
+    outln!(out, "Third element through *num, is {}", *num)?;
     v.push(4);
         
-    println!("After v.push fourth element is {}", &v[3]);
+    outln!(out, "After v.push fourth element is {}", &v[3])?;
 
     // Mutable References Provide Unique and Non-Owning Access to Data
         // - num now is a mutable/unique reference, as opposed to immutable/shared reference
@@ -83,17 +84,50 @@ impl ReferenceBorrow{
             // - num1 points to pointee too
             // - num lost write permission
     let num1 = &*num;
-    println!("num1 points to {num1}, num points to {num}");
+    outln!(out, "num1 points to {num1}, num points to {num}")?;
         // - if assign num to another variable, num will be unusable, i.e. lost all permission
     let num2 = num;
-    println!("Num2 is now a mutable ref to v[2]. Third element is {}", *num2);
+    outln!(out, "Num2 is now a mutable ref to v[2]. Third element is {}", *num2)?;
         // - mutate through v is not available, i.e. path v is unusable
     // v.push(4);
 
     // println!("Third element is {}", *num);
-    println!("Vector is now {:?}", v);
+    outln!(out, "Vector is now {:?}", v)?;
 
     // Permissions Are Returned At the End of a Reference's Lifetime
         // - from creation to last use
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for ReferenceBorrow {
+    fn id(&self) -> &'static str {
+        "ch4::reference_borrow"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(4, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "References and Borrowing"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["ownership", "borrowing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::ownership"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("reference_borrow.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
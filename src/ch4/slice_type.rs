@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch4.4 - The Slice Type
 #[derive(Debug)]
 pub struct SliceType{
@@ -7,11 +9,11 @@ pub struct SliceType{
 
 
 impl SliceType{
-    pub fn print(&self) {
-        println!("\n======The note of Slice Type======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of Slice Type======")?;
     // String slice
         // - is a &str type
-        fn string_slice() {
+        fn string_slice(out: &mut dyn Write) -> io::Result<()> {
             let s = String::from("hello world");
             
             let hello: &str = &s[0..5];
@@ -20,9 +22,9 @@ impl SliceType{
 
             // - since slices are reference, ownership rules applies - no immutable ref allowed now, as .push requires
             // s.push('!');
-            println!("\ns is {s}, hello is {hello}, world is {world}, and s2 is {s2}");
+            outln!(out, "\ns is {s}, hello is {hello}, world is {world}, and s2 is {s2}")
         }
-        string_slice();
+        string_slice(out)?;
 
     // Range Syntax
         fn range_syntax() {
@@ -60,25 +62,25 @@ impl SliceType{
         let my_string = String::from("hello world");
 
         let word = first_word(&my_string[0..6]);
-        println!("\nfirst_word of my_string[0..6] is {word}");
+        outln!(out, "\nfirst_word of my_string[0..6] is {word}")?;
 
         let word = first_word(&my_string[..]);
-        println!("first_word of my_string[..] is {word}");
+        outln!(out, "first_word of my_string[..] is {word}")?;
 
         let word = first_word(&my_string);
-        println!("first_word of my_string is {word}");
+        outln!(out, "first_word of my_string is {word}")?;
 
         // - taking string literal and slice
         let my_string_l = "hello world";
 
         let word = first_word(&my_string_l[0..6]);
-        println!("\nfirst_word of my_string_l[0..6] is {word}");
+        outln!(out, "\nfirst_word of my_string_l[0..6] is {word}")?;
 
         let word = first_word(&my_string_l[..]);
-        println!("first_word of my_string_l[..] is {word}");
+        outln!(out, "first_word of my_string_l[..] is {word}")?;
 
         let word = first_word(&my_string_l);
-        println!("first_word of my_string_l is {word}");
+        outln!(out, "first_word of my_string_l is {word}")?;
 
     // Other Slice
         fn other_slice() {
@@ -87,6 +89,38 @@ impl SliceType{
             assert_eq!(slice, &[2, 3]);
         }
         other_slice();
+        Ok(())
     }
 }
 
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for SliceType {
+    fn id(&self) -> &'static str {
+        "ch4::slice_type"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(4, 4)
+    }
+
+    fn title(&self) -> &'static str {
+        "The Slice Type"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["borrowing"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::reference_borrow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("slice_type.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
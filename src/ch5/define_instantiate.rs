@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch5.1 - Defining and Instantiating Struct
 #[derive(Debug)]
 pub struct DefineInstantiate{
@@ -16,8 +18,8 @@ struct User {
     sign_in_count: u64,
 }
 impl DefineInstantiate{
-    pub fn print(&self) {
-        println!("\n======The note of Define and Instantiate======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of Define and Instantiate======")?;
     // Instantiate a User
         // - directly
         let user1 = User {
@@ -26,7 +28,7 @@ impl DefineInstantiate{
             active: true,
             sign_in_count: 1,
         };
-        println!("\n user1 is an instance of User: {:#?}", user1);
+        outln!(out, "\n user1 is an instance of User: {:#?}", user1)?;
         // - or indirectly
         fn build_user(email: String, username: String) -> User {
             User {
@@ -38,7 +40,7 @@ impl DefineInstantiate{
         }
         let mut user2 = build_user("email".into(), "username".into());        
         user2.email = "someemail@somecom".into();
-        println!("user2 is an instance of User: {:#?}", user2);
+        outln!(out, "user2 is an instance of User: {:#?}", user2)?;
 
     // Using Field Init Shorthand 
         // - when parameter and struct field names are exactly the same
@@ -51,7 +53,7 @@ impl DefineInstantiate{
             }
         }
         let user3 = build_user2("email".into(), "username".into());        
-        println!("user3 is an instance of User: {:#?}", user3);
+        outln!(out, "user3 is an instance of User: {:#?}", user3)?;
 
     // Creating Instances from Other Instances with Struct Update Syntax
         // - create user4 from user1 with only differring email
@@ -59,7 +61,7 @@ impl DefineInstantiate{
             username: "User4".into(),
             ..user1
         };
-        println!("user4 is an instance of User: {:#?}", user4);
+        outln!(out, "user4 is an instance of User: {:#?}", user4)?;
 
     // Using Tuple Sructs Without Named Fields to Create Different Types
         // -  no names associated with fields
@@ -70,9 +72,9 @@ impl DefineInstantiate{
         struct Point(i32, i32, i32);
 
         let black = Color(0, 0, 0);
-        println!("\nblack is an instance of Color: {:#?}", black);
+        outln!(out, "\nblack is an instance of Color: {:#?}", black)?;
         let origin = Point(0, 0, 0);
-        println!("origin is an instance of Point: {:#?}", origin);
+        outln!(out, "origin is an instance of Point: {:#?}", origin)?;
 
     // Borrowing Fields of a Struct
     struct Point2 { x: i32, y: i32 }
@@ -82,6 +84,39 @@ impl DefineInstantiate{
         // - but p.y is not impacted
     let x = &mut p.x;
     *x += 1;
-    println!("{}, {}", p.x, p.y);
+    outln!(out, "{}, {}", p.x, p.y)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for DefineInstantiate {
+    fn id(&self) -> &'static str {
+        "ch5::define_instantiate"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(5, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Defining and Instantiating Structs"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["structs"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::ownership"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("define_instantiate.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch5.2 - Method Syntax
 #[derive(Debug)]
 pub struct Method{
@@ -94,8 +96,8 @@ impl Rectangle2 {
 }
 
 impl Method{
-    pub fn print(&self) {
-        println!("\n======The note of method syntax======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of method syntax======")?;
         
     // - Instantiate a instance and call its method
         let rect1 = Rectangle {
@@ -113,10 +115,10 @@ impl Method{
             height: 45,
         };
 
-        println!("The area of the rectangle is {} square pixels", rect1.area());
-        println!("The withd of the rectangle is {}, at {}", rect1.width(), rect1.width);
-        println!("Can rect1 hold rect2? - {}", rect1.can_hold(&rect2));
-        println!("Can rect1 hold rect3? - {}", rect1.can_hold(&rect3));
+        outln!(out, "The area of the rectangle is {} square pixels", rect1.area())?;
+        outln!(out, "The withd of the rectangle is {}, at {}", rect1.width(), rect1.width)?;
+        outln!(out, "Can rect1 hold rect2? - {}", rect1.can_hold(&rect2))?;
+        outln!(out, "Can rect1 hold rect3? - {}", rect1.can_hold(&rect3))?;
 
         // - Method call is a short hand calling associated function
         let area1 = rect1.area();
@@ -124,7 +126,7 @@ impl Method{
         assert_eq!(area1, area2);
 
         let max_rect = rect1.max(rect2);
-        println!("max(rect1, rect2) - {:?}", max_rect);
+        outln!(out, "max(rect1, rect2) - {:?}", max_rect)?;
         // - rect2 and rect1 are not usable after max
         // println!("After max(rect1, rect2), rect2 is - {:?}", rect2);
         // println!("After max(rect1, rect2), rect1 is - {:?}", rect1);
@@ -137,7 +139,7 @@ impl Method{
             height: 1,
         };
         rect4.set_width(77);
-        println!("After set_width, rect4 is: {:#?}", rect4);
+        outln!(out, "After set_width, rect4 is: {:#?}", rect4)?;
 
         let mut r1 = Rectangle2 {
             width: 9,
@@ -148,7 +150,52 @@ impl Method{
             height: 11,
         };
         r1.set_to_max(r2);
-        println!("\nAfter set_to_max, r1 is: {:#?}", r1);
-        println!("After set_to_max, r2 is: {:#?}", r2);
+        outln!(out, "\nAfter set_to_max, r1 is: {:#?}", r1)?;
+        outln!(out, "After set_to_max, r2 is: {:#?}", r2)?;
+        Ok(())
+    }
+}
+
+use crate::exercise::Exercise;
+use crate::lesson::{Lesson, Section};
+
+const EXERCISES: &[Exercise] = &[Exercise {
+    name: "ch5_set_to_max",
+    task: "Implement `Rectangle::set_to_max` without moving `*self`",
+    stub: include_str!("../../exercises/ch5_set_to_max/stub.rs"),
+    checks: include_str!("../../exercises/ch5_set_to_max/checks.rs"),
+}];
+
+impl Lesson for Method {
+    fn id(&self) -> &'static str {
+        "ch5::method"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(5, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Method Syntax"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["structs"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch5::define_instantiate", "ch4::reference_borrow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("method.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
     }
 }
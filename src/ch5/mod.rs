@@ -1,2 +1,13 @@
+#![allow(dead_code, unused_variables, clippy::redundant_field_names)]
 pub mod define_instantiate;
 pub mod method;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(define_instantiate::DefineInstantiate {}),
+        Box::new(method::Method {}),
+    ]
+}
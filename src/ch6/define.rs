@@ -1,11 +1,12 @@
 // Ch6.1 - Define an Enum
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct DefineEnum {
 }
 
 impl DefineEnum {
-    pub fn print(&self) {
-        println!("\n======The note of define an enum ======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of define an enum ======")?;
     // Enum Values
         // - we can put data directly into each enum variant
         // - each variant can have different types
@@ -16,9 +17,9 @@ impl DefineEnum {
             V6(String),
         }
         let home = IpAddr::V4(127, 0, 0, 1);
-        println!("\nhome is: {:?}", home);
+        outln!(out, "\nhome is: {:?}", home)?;
         let loopback = IpAddr::V6(String::from("::1"));
-        println!("loopback is: {:?}", loopback);
+        outln!(out, "loopback is: {:?}", loopback)?;
 
     // Methods on Enum
         // - use `impl` block, similar to struct
@@ -32,12 +33,12 @@ impl DefineEnum {
         }
 
         impl Message {
-            fn call(&self) {
-                println!("Calling from Message::call().")
+            fn call(&self, out: &mut dyn Write) -> io::Result<()> {
+                outln!(out, "Calling from Message::call().")
             }
         }
         let m = Message::Write("hello".into());
-        m.call();
+        m.call(out)?;
     
     // Option Enum
         // - Rust does not have nulls
@@ -52,5 +53,38 @@ impl DefineEnum {
         let _some_char = Some('e');
         
         let _absent_number: Option<i32> = None;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for DefineEnum {
+    fn id(&self) -> &'static str {
+        "ch6::define"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(6, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Defining an Enum"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["enums"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch5::define_instantiate"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("define.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
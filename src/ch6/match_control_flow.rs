@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch6.2 - Match Control Flow Construct
 #[derive(Debug)]
 pub struct MatchControlFlow{
@@ -24,31 +26,32 @@ enum Coin {
     // - compiler confirms that all possible cases are handled
 
 impl MatchControlFlow{
-    pub fn print(&self) {
-        println!("\n======The note of MatchControlFlow======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note of MatchControlFlow======")?;
     // Match with Pattern
         // - variable after `match` keyword can be any time, as opposed to bool to `if`
         // - each arm consists of pattern and code to run, separated by `=>` operator
             // - code could be in a curly brackets block. make sure it returns a valid value
         // - pattern can be cound to a value for later use
-        fn value_in_cents(coin: Coin) -> u8 {
-            match coin {
+        fn value_in_cents(coin: Coin, out: &mut dyn Write) -> io::Result<u8> {
+            let cents = match coin {
                 Coin::Penny => {
-                    println!("Luck Penny!");
+                    outln!(out, "Luck Penny!")?;
                     1
                 }
                 Coin::Nickel => 5,
                 Coin::Dime => 10,
                 // when Coin::Quarter matches, the state variable will bind to the value of that quarter's state
                 Coin::Quarter(state) => {
-                    println!("State quarter from {:?}", state);
+                    outln!(out, "State quarter from {:?}", state)?;
                     25
                 }
-            }
+            };
+            Ok(cents)
         }
-        value_in_cents(Coin::Dime);
-        value_in_cents(Coin::Penny);
-        value_in_cents(Coin::Quarter(UsState::Alabama));
+        value_in_cents(Coin::Dime, out)?;
+        value_in_cents(Coin::Penny, out)?;
+        value_in_cents(Coin::Quarter(UsState::Alabama), out)?;
     // Match with Option<T>
         fn plus_one(x: Option<i32>) -> Option<i32> {
             match x {
@@ -59,9 +62,9 @@ impl MatchControlFlow{
 
         let five = Some(5);
         let six = plus_one(five);
-        println!("\nsix is: {:?}", six);
+        outln!(out, "\nsix is: {:?}", six)?;
         let none = plus_one(None);
-        println!("none is: {:?}", none);
+        outln!(out, "none is: {:?}", none)?;
 
     // Matches are exhaustive
         // - the last arm, other arm handles all cases that are not 3 or 7
@@ -72,55 +75,56 @@ impl MatchControlFlow{
             3 => add_fancy_hat(),
             7 => remove_fancy_hat(),
             // - must only one of either
-            other => move_player(other),
+            other => move_player(other, out)?,
             // _ => (),
         }
 
         fn add_fancy_hat() {}
         fn remove_fancy_hat() {}
-        fn move_player(num_spaces: u8) {println!("Move {num_spaces} steps");}
+        fn move_player(num_spaces: u8, out: &mut dyn Write) -> io::Result<()> {outln!(out, "Move {num_spaces} steps")}
 
     // How Match interacts with Ownership
         let opt: Option<String> = Some(String::from("Hello world"));
 
         // - match String and does not bind
-        fn match_string(opt: Option<String>){
+        fn match_string(opt: Option<String>, out: &mut dyn Write) -> io::Result<()> {
             match opt {
                 // - do not bind pattern
-                Some(_) => println!("\nMatched Some arm"),
-                None => println!("Matched None arm")
+                Some(_) => outln!(out, "\nMatched Some arm")?,
+                None => outln!(out, "Matched None arm")?
             };
-            println!("At the end of match_string(opt), opt is: {:?}", opt);
+            outln!(out, "At the end of match_string(opt), opt is: {:?}", opt)
         }
             // - match_string takes ownership of opt
-        match_string(opt);
+        match_string(opt, out)?;
             // - so opt is unusable
         // println!("After match_string(opt), opt is: {:?}", opt);
 
         // - match String and bind to a variable
-        fn _match_string_bind(opt: Option<String>){
+        fn _match_string_bind(opt: Option<String>, out: &mut dyn Write) -> io::Result<()> {
             match opt {
                 // bind pattern to s
-                Some(s) => println!("\ns is:{s}, matched Some arm"),
-                None => println!("Matched None arm")
+                Some(s) => outln!(out, "\ns is:{s}, matched Some arm")?,
+                None => outln!(out, "Matched None arm")?
             };
                 // - opt is not usable since s takes ownership
             // println!("After match_string(opt), opt is: {:?}", opt);
+            Ok(())
         }
 
         // - match String ref and bind
-        fn match_string_ref_bind(opt: &Option<String>){
+        fn match_string_ref_bind(opt: &Option<String>, out: &mut dyn Write) -> io::Result<()> {
             match opt {
                 // bind pattern to s
-                Some(s) => println!("\ns is:{s}, matched Some arm"),
-                None => println!("Matched None arm")
+                Some(s) => outln!(out, "\ns is:{s}, matched Some arm")?,
+                None => outln!(out, "Matched None arm")?
             };
                 // - opt is not usable since s takes ownership
-            println!("At the end of match_string_ref_bind(opt), opt is: {:?}", opt);
+            outln!(out, "At the end of match_string_ref_bind(opt), opt is: {:?}", opt)
         }
         let opt: Option<String> = Some(String::from("Hello world"));
-        match_string_ref_bind(&opt);
-        println!("After match_string_ref_bind(opt), opt is: {:?}", opt);
+        match_string_ref_bind(&opt, out)?;
+        outln!(out, "After match_string_ref_bind(opt), opt is: {:?}", opt)?;
     
     // Match Construct is an Expression
         #[allow(dead_code)]
@@ -135,7 +139,7 @@ impl MatchControlFlow{
           Location::Range(0, _) => 0,
           _ => -2
         };
-        println!("Matching l returns: {n}");
+        outln!(out, "Matching l returns: {n}")?;
     
     // if let statement and two-arm match
         let mut count = 0;
@@ -143,26 +147,60 @@ impl MatchControlFlow{
         let coin1 = Coin::Quarter(UsState::Alaska);
         let coin2 = Coin::Penny;
 
-        fn match_coin(coin: &Coin, count: &mut i32) {
+        fn match_coin(coin: &Coin, count: &mut i32, out: &mut dyn Write) -> io::Result<()> {
             match coin {
-                Coin::Quarter(state) => println!("\nState quarter from {:?}!", state),
+                Coin::Quarter(state) => outln!(out, "\nState quarter from {:?}!", state)?,
                 _ => *count += 1,
             }
+            Ok(())
         }
-        match_coin(&coin1, &mut count);
-        match_coin(&coin2, &mut count);
+        match_coin(&coin1, &mut count, out)?;
+        match_coin(&coin2, &mut count, out)?;
         // - above match construct is equivalent to below `if let` statement
-        fn match_coin2(coin: &Coin, count: &mut i32) {
+        fn match_coin2(coin: &Coin, count: &mut i32, out: &mut dyn Write) -> io::Result<()> {
             if let Coin::Quarter(state) = coin {
-                println!("State quarter from {:?}!", state);
+                outln!(out, "State quarter from {:?}!", state)?;
             } else {
                 *count += 1;
             }
+            Ok(())
         }
-        match_coin2(&coin1, &mut count);
-        match_coin2(&coin2, &mut count);
+        match_coin2(&coin1, &mut count, out)?;
+        match_coin2(&coin2, &mut count, out)?;
     
-        println!("Now count is: {count}");
+        outln!(out, "Now count is: {count}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for MatchControlFlow {
+    fn id(&self) -> &'static str {
+        "ch6::match_control_flow"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(6, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "The match Control Flow Construct"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["enums", "pattern-matching"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch6::define"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("match_control_flow.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
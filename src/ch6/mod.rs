@@ -1,2 +1,13 @@
+#![allow(unreachable_patterns, clippy::manual_map)]
 pub mod define;
 pub mod match_control_flow;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(define::DefineEnum {}),
+        Box::new(match_control_flow::MatchControlFlow {}),
+    ]
+}
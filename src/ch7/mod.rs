@@ -0,0 +1,12 @@
+pub mod modules;
+pub mod packages;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(packages::Packages {}),
+        Box::new(modules::Modules {}),
+    ]
+}
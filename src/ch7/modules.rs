@@ -0,0 +1,183 @@
+//! # Ch7.2 - Defining Modules to Control Scope and Privacy
+//!     * `mod name { ... }` defines a module, which groups related items, e.g. functions, structs, enums, other modules
+//!     * the modules form a tree, rooted at the implicit module `crate`, the crate root
+//!         * a module inside another is its child, the outer one its parent
+//!     * items are private to their parent module by default
+//!         * an item can use the private items of its ancestors, but not those of its descendants
+//!         * `pub` makes an item visible to the outside, `pub(crate)` only to the current crate, `pub(super)` to the parent
+//!         * a `pub mod` only makes the module reachable, its contents still need their own `pub`
+//!         * a `pub struct` keeps its fields private unless each is marked `pub`, the variants of a `pub enum` are all public
+//! # Ch7.3 - Paths for Referring to an Item in the Module Tree
+//!     * an absolute path starts at the crate root, `crate::` for this crate, or the crate's name for an external one
+//!     * a relative path starts at the current module, or at `self::`, or at the parent with `super::`
+//!         * similar to a `..` in a file system path, or a C++ qualified name without the leading `::`
+//! # Ch7.4 - Bringing Paths into Scope with the `use` Keyword
+//!     * `use` creates a shortcut to a path, like a symbolic link, only in the scope where it appears
+//!     * idiomatic: `use` the parent module of a function, and the full path of a struct, an enum or another item
+//!         * `as` renames an item that would clash, e.g. `use std::io::Result as IoResult;`
+//!     * `pub use` re-exports: code outside can use the item through the new path, e.g. `restaurant::hosting`
+//!         * the public API can then differ from the internal module tree
+//!     * nested paths: `use std::{cmp::Ordering, io};`, `use std::io::{self, Write};`, and the glob `use std::collections::*;`
+//! # Ch7.5 - Separating Modules into Different Files
+//!     * `mod front_of_house;` with a semicolon loads the module's body from `src/front_of_house.rs`
+//!         * and its child `hosting` from `src/front_of_house/hosting.rs`, or `src/front_of_house/hosting/mod.rs` in the older style
+//!         * a file is loaded once, with `mod`, not like a C++ `#include`; other modules refer to it by its path
+//!     * the `restaurant` crate under `crates/` is the book's example split into files
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub struct Modules {}
+
+// Defining modules
+// - the restaurant of the book, inline in this note; `crates/restaurant` is the same split into files
+mod front_of_house {
+    pub mod hosting {
+        pub fn add_to_waitlist() -> &'static str {
+            "added to waitlist"
+        }
+
+        // - private, only `hosting` and its children can call it
+        fn seat_at_table() -> &'static str {
+            "seated"
+        }
+
+        pub fn seat_next() -> &'static str {
+            seat_at_table()
+        }
+    }
+
+    // - `pub(super)` is visible in the parent, the `ch7::modules` module, but no further
+    pub(super) mod serving {
+        // - `pub(super)` here would only reach `front_of_house`, the parent of `serving`
+        // - `pub(crate)` is capped by the module, so `take_order` is not seen further than `serving` is
+        pub(crate) fn take_order() -> &'static str {
+            "order taken"
+        }
+    }
+}
+
+mod back_of_house {
+    pub fn fix_incorrect_order() -> String {
+        cook_order();
+        // - `super` is the parent module, here `ch7::modules`
+        super::deliver_order()
+    }
+
+    fn cook_order() {}
+}
+
+fn deliver_order() -> String {
+    // - `self::` makes the relative path explicit
+    format!("delivered, {}", self::front_of_house::hosting::seat_next())
+}
+
+// - a `use` in the module scope, the function bodies below can say `hosting::`
+use self::front_of_house::hosting;
+// - `as` gives a local name, the way `using` aliases in C++ do
+use restaurant::back_of_house::Breakfast as Meal;
+
+impl Modules {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on modules======")?;
+        // Paths
+        // - absolute path
+        outln!(
+            out,
+            "{}",
+            crate::ch7::modules::front_of_house::hosting::add_to_waitlist()
+        )?;
+        // - relative path
+        outln!(out, "{}", front_of_house::hosting::add_to_waitlist())?;
+        // - through the `use`
+        outln!(out, "{}", hosting::add_to_waitlist())?;
+        // - does not compile, `seat_at_table` is private (E0603)
+        // hosting::seat_at_table();
+        outln!(out, "{}", front_of_house::serving::take_order())?;
+        outln!(out, "{}", back_of_house::fix_incorrect_order())?;
+
+        // Paths into another crate
+        // - the `restaurant` crate is a dependency, its name starts the absolute path
+        let mut waitlist = restaurant::hosting::Waitlist::new();
+        // - does not compile, `front_of_house` is private in `restaurant` (E0603)
+        // - `restaurant::hosting` works because the crate re-exports it with `pub use`
+        // restaurant::front_of_house::hosting::add_to_waitlist(&mut waitlist, "Ferris");
+        restaurant::hosting::add_to_waitlist(&mut waitlist, "Corro");
+        outln!(
+            out,
+            "{}",
+            restaurant::eat_at_restaurant(&mut waitlist, "Ferris")
+        )?;
+        outln!(out, "still waiting: {waitlist:?}")?;
+
+        // Private fields and public variants
+        // - `Meal { toast, seasonal_fruit }` does not compile, `seasonal_fruit` is private (E0451)
+        let mut meal = Meal::summer("Rye");
+        meal.toast = String::from("Wheat");
+        outln!(out, "{} toast with {}", meal.toast, meal.seasonal_fruit())?;
+        // - a `use` inside a block is only in scope in that block
+        {
+            use restaurant::back_of_house::Appetizer;
+            let order1 = Appetizer::Soup;
+            let order2 = Appetizer::Salad;
+            outln!(out, "appetizers: {order1:?} and {order2:?}")?;
+        }
+        // - does not compile here, out of the `use`'s scope (E0433)
+        // let order3 = Appetizer::Soup;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Modules {
+    fn id(&self) -> &'static str {
+        "ch7::modules"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(7, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Defining Modules to Control Scope and Privacy"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["modules"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch7::packages", "ch5::define_instantiate", "ch6::define"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("modules.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_items_are_reachable_from_child_modules() {
+        // - `tests` is a child of `ch7::modules`, so its private modules are in reach
+        assert_eq!(back_of_house::fix_incorrect_order(), "delivered, seated");
+        assert_eq!(super::front_of_house::serving::take_order(), "order taken");
+    }
+
+    #[test]
+    fn calls_into_the_restaurant_crate() {
+        let mut out = Vec::new();
+        Modules {}.print(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(
+            "Corro would like Wheat toast please, with peaches and a Salad\n\
+             still waiting: [\"Ferris\", \"the next guest\"]\n"
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! # Ch7.1 - Packages and Crates
+//!     * a crate is the smallest amount of code the compiler considers at a time, even a single file given to `rustc`
+//!         * a binary crate compiles to an executable and has a `main` function
+//!         * a library crate has no `main`, it defines functionality shared with other projects
+//!         * "crate" alone usually means a library crate, like a library in C++
+//!     * the crate root is the source file the compiler starts from, and the root module of the crate
+//!     * a package is a bundle of one or more crates, described by a `Cargo.toml`
+//!         * at most one library crate, and any number of binary crates
+//!         * `src/main.rs` is the root of a binary crate named after the package, `src/lib.rs` the root of its library crate
+//!         * more binary crates go in `src/bin/`, one per file
+//!     * this package has both, the `rust_after_cpp` library with the notes and the `rust_after_cpp` binary that runs them
+//!         * the binary uses the library like any other crate, `use rust_after_cpp::registry;`
+//!     * `add_one` and `restaurant` are library crates of their own packages, under `crates/`, see `ch14::workspaces`
+use std::io::{self, Write};
+
+#[derive(Debug)]
+pub struct Packages {}
+
+impl Packages {
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on packages and crates======")?;
+        // - Cargo tells the compiler which package it builds through environment variables
+        outln!(
+            out,
+            "package: {} {}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        )?;
+        // - the name of the crate being compiled, the library one here
+        outln!(out, "crate: {}", env!("CARGO_CRATE_NAME"))?;
+
+        // - `module_path!` is the path of the current module, starting at the crate root
+        outln!(out, "this note is in the module {}", module_path!())?;
+        // - the path of an item names its crate first
+        // - `type_name` gives the path where the item is defined, `std::collections::HashMap` is a `pub use` of it
+        outln!(
+            out,
+            "a HashMap is a {}",
+            std::any::type_name::<std::collections::HashMap<String, i32>>()
+        )?;
+        outln!(
+            out,
+            "a breakfast is a {}",
+            std::any::type_name::<restaurant::back_of_house::Breakfast>()
+        )?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Packages {
+    fn id(&self) -> &'static str {
+        "ch7::packages"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(7, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Packages and Crates"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["modules", "cargo"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch1::main"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("packages.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_the_module_paths() {
+        let mut out = Vec::new();
+        Packages {}.print(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("crate: rust_after_cpp\n"));
+        assert!(out.contains("this note is in the module rust_after_cpp::ch7::packages\n"));
+        assert!(out.contains("a breakfast is a restaurant::back_of_house::Breakfast\n"));
+    }
+}
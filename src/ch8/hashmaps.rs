@@ -6,32 +6,33 @@
 /// * peers in other language - hash, map, object, hash table, dictionary, or associative array
 /// 
 use std::collections::HashMap;
+use std::io::{self, Write};
 #[derive(Debug)]
 pub struct HashMaps{}
 
 impl HashMaps{
-    pub fn print(&self) {
-        println!("\n======The note on HashMap======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on HashMap======")?;
     // Create a New Hash Map
         // - together with insert, compiler can infer types for K and V; otherwise need to annotate
         let mut scores = HashMap::new();
         scores.insert(String::from("Blue"), 10);
         scores.insert(String::from("Yellow"), 50);
-        println!("\nscores is now: {:?}", scores);
+        outln!(out, "\nscores is now: {:?}", scores)?;
 
     // Accessing Values in a Hash Map
         // - individual key
         let team_name = String::from("Blue");
         let score = scores.get(&team_name).copied().unwrap_or(0);
-        println!("\nScore for team {team_name} is {score}");
+        outln!(out, "\nScore for team {team_name} is {score}")?;
 
         let team_name = String::from("Black");
         let score = scores.get(&team_name).copied().unwrap_or(0);
-        println!("Score for team {team_name} is {score}");
+        outln!(out, "Score for team {team_name} is {score}")?;
         // - loop over entire hashmap
             // - order is arbitrary
         for (key, value) in &scores {
-            println!("{key}: {value}");
+            outln!(out, "{key}: {value}")?;
         }
     
     // HashMap and Ownership
@@ -43,22 +44,22 @@ impl HashMaps{
             // - e.g., new_team_name are not usable from here on
         // println!("{new_team_name}");
             // - e.g., new_team_score are usable from here on
-        println!("\nnew_team_score is still usable: {new_team_score}");
+        outln!(out, "\nnew_team_score is still usable: {new_team_score}")?;
 
     // Updating a Hash Map
         // - overwriting a value
-        println!("\n{:?}", scores);
+        outln!(out, "\n{:?}", scores)?;
         scores.insert(String::from("Blue"), 11);
-        println!("{:?}", scores);
+        outln!(out, "{:?}", scores)?;
         scores.insert(String::from("Blue"), 25);
-        println!("{:?}", scores);
+        outln!(out, "{:?}", scores)?;
 
         // - adding a key:value only if the key is not present
             // - Yellow is present so do nothing
         scores.entry(String::from("Yellow")).or_insert(49);
             // - Green is not prsent so insert an entry for it
         scores.entry(String::from("Green")).or_insert(39);
-        println!("\nAfter .entry(), scores is: {:?}", scores);
+        outln!(out, "\nAfter .entry(), scores is: {:?}", scores)?;
 
         // - updating a value based on old value
         let text = "hello world wonderful world";
@@ -70,7 +71,39 @@ impl HashMaps{
                 // - update the pointee
             *count += 1;
         }
-        println!("\n{:?}", map);
+        outln!(out, "\n{:?}", map)?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for HashMaps {
+    fn id(&self) -> &'static str {
+        "ch8::hashmaps"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(8, 3)
+    }
+
+    fn title(&self) -> &'static str {
+        "Storing Keys with Associated Values in Hash Maps"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["collections"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch8::vectors", "ch8::strings"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("hashmaps.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -1,3 +1,14 @@
 pub mod vectors;
 pub mod strings;
-pub mod hashmaps;
\ No newline at end of file
+pub mod hashmaps;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(vectors::Vectors {}),
+        Box::new(strings::Strings {}),
+        Box::new(hashmaps::HashMaps {}),
+    ]
+}
@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch8.2 - Storing UTF-8 Encoded Text with Strings
 /// * Strings are implemented in RUst as a collectin of bytes
 /// * plus some methods to provide useful functionality when those bytes are interpreted as text
@@ -10,8 +12,8 @@
 pub struct Strings {}
 
 impl Strings{
-    pub fn print(&self) {
-        println!("\n======The note on Storing UTF-8 Encoded Text with Strings======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on Storing UTF-8 Encoded Text with Strings======")?;
     // Create a New String
         // - many Vec<T> operations available with `String` because it is a vector of bytes
 
@@ -19,42 +21,42 @@ impl Strings{
         let mut s1 = String::new();
         s1.push('H');
         s1.push_str("ello world");
-        println!("\nAfter push and push_str, s1 is {s1}");
+        outln!(out, "\nAfter push and push_str, s1 is {s1}")?;
 
         // - from string literal
         let data = "initial contents";
             // - .to_string() works with any type that implements `Display`
         let s2 = data.to_string();
-        println!("After to_string, s2 is {s2}");
+        outln!(out, "After to_string, s2 is {s2}")?;
             // - the method also works on a literal directly:
         let s3 = "initial contents".to_string();
-        println!("After to_string, s3 is {s3}");
+        outln!(out, "After to_string, s3 is {s3}")?;
             // - or use String::from()
         let s4 = String::from("initial contents");
-        println!("After String::from(), s4 is {s4}");
+        outln!(out, "After String::from(), s4 is {s4}")?;
             // - works with any UTF-8 encode string literals
         let hello = String::from("السلام عليكم");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("Dobrý den");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("Hello");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("שָׁלוֹם");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("नमस्ते");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("こんにちは");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("안녕하세요");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("你好");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("Olá");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("Здравствуйте");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
         let hello = String::from("Hola");
-        println!("hell is: {hello}");
+        outln!(out, "hell is: {hello}")?;
 
     // Updating a String
         // - size and cotents, just like Vec<T>
@@ -69,12 +71,12 @@ impl Strings{
                 // - compiler coerces &s6 which is &String into a &str
                     // - i.e., deref coerceion: &s6 to &s2[..]
         let s7 = s5 + &s6; 
-        println!("\nAfter s5 + &s6, s6 is: {s6}");
-        println!("After s5 + &s6, s7 is: {s7}");
+        outln!(out, "\nAfter s5 + &s6, s6 is: {s6}")?;
+        outln!(out, "After s5 + &s6, s7 is: {s7}")?;
         // - format! macro
             // - format! does not take ownership of any of its arguments
         let s8 = format!("{s7} - {s6}");
-        println!("\nAfter format!, s8 is: {s8}");
+        outln!(out, "\nAfter format!, s8 is: {s8}")?;
 
     // Indexing into Strings
         // - Rust strings does not support direct indexing 
@@ -84,31 +86,60 @@ impl Strings{
 
         // - A String is a wrapper over a Vec<u8>
         let hello = String::from("Hola");
-        println!("\n{hello}'s length: {}", hello.len());
-        println!("\"{hello}\" is essentially {:?}", hello.as_bytes());
+        outln!(out, "\n{hello}'s length: {}", hello.len())?;
+        outln!(out, "\"{hello}\" is essentially {:?}", hello.as_bytes())?;
         let hello = String::from("Здравствуйте");
-        println!("\n{hello}'s length: {}", hello.len());
-        println!("\"{hello}\" is essentially {:?}", hello.as_bytes());
+        outln!(out, "\n{hello}'s length: {}", hello.len())?;
+        outln!(out, "\"{hello}\" is essentially {:?}", hello.as_bytes())?;
         
         // - View strings as bytes, scalar values, and grapheme clusters
         // - Slicing Strings
             // - better idea that indexing into a string
         let hello = "Здравствуйте";
         let s = &hello[0..4];
-        println!("\nFirst 4 elements of \"{hello}\" is: {s}");
+        outln!(out, "\nFirst 4 elements of \"{hello}\" is: {s}")?;
 
         // - Iterating Over Strings
             // - over character
         for c in hello.chars() {
-            println!("{c}");
+            outln!(out, "{c}")?;
         }
             // - over bytes
         for b in hello.bytes() {
-            println!("{b}");
+            outln!(out, "{b}")?;
         }
-            
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Strings {
+    fn id(&self) -> &'static str {
+        "ch8::strings"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(8, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Storing UTF-8 Encoded Text with Strings"
+    }
 
+    fn tags(&self) -> &'static [&'static str] {
+        &["collections"]
+    }
 
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::slice_type"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("strings.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
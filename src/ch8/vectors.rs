@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch8.1 - Storing Lists of Values with Vectors
 /// * a collection type `Vec<T>`, aka vector.
 /// * store more than one value in a single data structure that puts all values next to each other in memory,i.e. contiguous?
@@ -7,29 +9,29 @@ pub struct Vectors{
 }
 
 impl Vectors{
-    pub fn print(&self) {
-        println!("\n======The note on storing lists of values with vectors======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on storing lists of values with vectors======")?;
 
     // Create a New Vecotr
         // - e.g., v is an new and empty vector to hold values of type i32
         let mut v: Vec<i32> = Vec::new();
-        println!("\nInitially v is {:?}", v);
+        outln!(out, "\nInitially v is {:?}", v)?;
             // - we can use push to add element when v is mutable
         v.push(1);
         v.push(2);
         v.push(3);
-        println!("After push(), v is {:?}", v);
+        outln!(out, "After push(), v is {:?}", v)?;
         // - use macro `vec![]` to create a new vector and initialize with values
             // - Rust will infer the type so no need to anotate type
             // - v1 does not need to be mutable
         let v1 = vec![1,2,3];
-        println!("After defined with vec!, v1 is {:?}", v1);
+        outln!(out, "After defined with vec!, v1 is {:?}", v1)?;
     
     // Reading Elements of Vector
         // - via indexing
             // - panic at runtime if out of index, i.e. error
         let third1 = &v[2];
-        println!("\nThe third element is {third1}");
+        outln!(out, "\nThe third element is {third1}")?;
         // let fourth= &v[3];
         // println!("\nThe fourth element is {fourth}");
 
@@ -38,13 +40,13 @@ impl Vectors{
             // - easy handling of errous index
         let third2 = v.get(2);
         match third2 {
-            Some(third3) => println!("Third element exists and is {third3}"),
-            None => println!("Third element does not exist."),
+            Some(third3) => outln!(out, "Third element exists and is {third3}")?,
+            None => outln!(out, "Third element does not exist.")?,
         }
         let fourth2= v.get(3);
         match fourth2{
-            Some(fourth2) => println!("Fourth element exists and is {fourth2}"),
-            None => println!("Fourth element does not exist."),
+            Some(fourth2) => outln!(out, "Fourth element exists and is {fourth2}")?,
+            None => outln!(out, "Fourth element does not exist.")?,
         }
 
         // - reference to element borrows entire vector
@@ -57,13 +59,13 @@ impl Vectors{
             // - so push() is invalid, i.e., cannot borrow v as mutable ref because v is borrowed as immutable
             // - i.e, if compiled, after push, first may point to a freed memory - undefined behavior
         // v.push(4);
-        println!("\nFirst element is: {_first_ref}");
+        outln!(out, "\nFirst element is: {_first_ref}")?;
 
         // - move out of vector, i.e, a collection
             // - below assignment works because i32 does not have heap data so v[0] is copied when assigning to first_own
         let first_own = v[0];
-        println!("\nAfter first_own=v[0], first_own is {first_own:?}");
-        println!("After first_own=v[0], v is {v:?}");
+        outln!(out, "\nAfter first_own=v[0], first_own is {first_own:?}")?;
+        outln!(out, "After first_own=v[0], v is {v:?}")?;
 
         let mut strings = vec![String::from("Hello ")];
             // - below assignment does not work because String has heap data so v[0] can not be copied without move
@@ -71,25 +73,25 @@ impl Vectors{
                 // - must use reference
         // let mut s=strings[0];
         let s= &mut strings[0];
-        println!("\nAfter s =&strings[0], s is {s}");
+        outln!(out, "\nAfter s =&strings[0], s is {s}")?;
         s.push_str(" world!");
-        println!("After s.push_str(), s is {s}");
-        println!("After s.push_str(), strings is {strings:?}");
+        outln!(out, "After s.push_str(), s is {s}")?;
+        outln!(out, "After s.push_str(), strings is {strings:?}")?;
 
     // Iterating over the Values in a Vector
         // - better to use `for` loop than index-based loop, i.e., one at a time
         // - over immutable ref
-        println!("\n");
+        outln!(out, "\n")?;
         for n_ref in &v {
             let n_plus_one = *n_ref + 1;
-            println!("n_plus_one is {n_plus_one}");
+            outln!(out, "n_plus_one is {n_plus_one}")?;
         }
 
         // - over mutable ref
         for n_ref in &mut v {
             *n_ref += 1;
         }
-        println!("After mutable loop, v is {:?}", v);
+        outln!(out, "After mutable loop, v is {:?}", v)?;
     // Dropping a Vector drops its elements.
 
     // Case Study #1
@@ -104,7 +106,39 @@ impl Vectors{
         *v2[0] = 5;
         let a = *v2[0];
         let b = v[0];
-        println!("\nAfter *v2[0]=5, a is: {a}, b is: {b}");
+        outln!(out, "\nAfter *v2[0]=5, a is: {a}, b is: {b}")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Vectors {
+    fn id(&self) -> &'static str {
+        "ch8::vectors"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(8, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Storing Lists of Values with Vectors"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["collections"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch4::reference_borrow", "ch6::match_control_flow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("vectors.rs")
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
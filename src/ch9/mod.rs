@@ -1,2 +1,13 @@
+#![allow(dead_code, unused_variables, clippy::empty_line_after_doc_comments, clippy::question_mark)]
 pub mod panics;
-pub mod results;
\ No newline at end of file
+pub mod results;
+
+use crate::lesson::Lesson;
+
+/// Lessons of this chapter, in book order
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    vec![
+        Box::new(panics::Panics {}),
+        Box::new(results::Results {}),
+    ]
+}
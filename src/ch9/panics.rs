@@ -1,3 +1,5 @@
+use std::io::{self, Write};
+
 /// # Ch9.1 - Unrecoverable Errors with panic!
 /// * by default, when a panic occurs, the program starts to unwinding, 
 /// * i.e., Rust walks back up the stack and cleans up data from each function it encounters
@@ -12,7 +14,40 @@ pub struct Panics {
 }
 
 impl Panics {
-    pub fn print(&self) {
-        println!("\n======The note on panic ======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on panic ======")?;
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Panics {
+    fn id(&self) -> &'static str {
+        "ch9::panics"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(9, 1)
+    }
+
+    fn title(&self) -> &'static str {
+        "Unrecoverable Errors with panic!"
     }
-}
\ No newline at end of file
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["error-handling"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch3::control_flow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("panics.rs")
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
+    }
+}
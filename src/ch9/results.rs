@@ -10,14 +10,14 @@
 /// * E reprensets the type of error that will be returned in a failure case whinin the Err variant
 
 use std::fs::{self, File};
-use std::io::{self, Read, ErrorKind};
+use std::io::{self, ErrorKind, Read, Write};
 #[derive(Debug)]
 pub struct Results{
 }
 
 impl Results{
-    pub fn print(&self) {
-        println!("\n======The note on results======");
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        outln!(out, "\n======The note on results======")?;
     
     // Simpe Match
         // - File::open returns a Result
@@ -134,7 +134,42 @@ impl Results{
 
     // `main` can also return `Result`
         // - by default returns `()`
+        Ok(())
+    }
+}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Results {
+    fn id(&self) -> &'static str {
+        "ch9::results"
+    }
+
+    fn section(&self) -> Section {
+        Section::new(9, 2)
+    }
+
+    fn title(&self) -> &'static str {
+        "Recoverable Errors with Result"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["error-handling", "enums"]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["ch9::panics", "ch6::match_control_flow"]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("results.rs")
+    }
 
+    fn panics(&self) -> bool {
+        true
+    }
 
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.print(out)
     }
 }
@@ -0,0 +1,571 @@
+//! # Command line of the `rust_after_cpp` binary
+//!     * `list` prints every lesson in book order, `list --tag concurrency` only those on a topic
+//!     * `graph` prints which lessons build on which, `graph --dot` as Graphviz
+//!     * `run ch15.4` or `run ch15::rc_pointers` runs a single lesson
+//!     * `run ch16 --all` runs every lesson of a chapter, `run --all` runs the whole book
+//!     * `run --match iterators` runs every lesson whose id or title contains `iterators`
+//!     * `export book` writes every note with its output as Markdown, plus an HTML index, into `book/`
+//!     * `progress` summarizes the lessons run so far and suggests the next one
+//!     * `run ch9.2 --annotate` prefixes each output line with the source line that printed it, `--source` quotes the code above its output
+//!     * `run` warns about prerequisites not completed yet, `run --strict` refuses to run such a lesson
+//!     * `run ch15.4 --compare cpp` follows the lesson's output with its C++ equivalents
+//!     * `run ch16 --all --record <file>`, then `--replay <file>`, reruns the thread demos with the same interleaving
+//!     * `quiz` asks the quizzes of every lesson, `quiz ch16 --all` etc. select lessons like `run`
+//!     * `exercise` checks every exercise in the scratch directory, `exercise ch5_set_to_max` only one
+//!     * `guess` plays the chapter 2 guessing game, `guess --seed 7 --script guesses.txt` a reproducible one from a file
+//!     * `match 'Some(4)' 'Some(x) if x > 5' 'Some(_)' 'None'` shows which arm takes the value and which are unreachable, see `ch18::playground`
+//!     * `minigrep -n to poem.txt` is the chapter 12 project, see `ch12::minigrep`
+//!     * `serve` is the chapter 20 project, a web server on `127.0.0.1:7878`, see `ch20::web_server`
+use crate::annotate::Annotation;
+use crate::compare::Language;
+use crate::lesson::{Lesson, Section};
+use crate::registry;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: rust_after_cpp <command>
+
+Commands:
+    list                      list every lesson in book order
+    list --tag <tag>          list the lessons on a topic, e.g. `list --tag concurrency`
+    graph [--dot]             print the prerequisites of every lesson, as Graphviz DOT with `--dot`
+    run <section|id>          run one lesson, e.g. `run ch15.4` or `run ch15::rc_pointers`
+    run <chapter> --all       run every lesson of a chapter, e.g. `run ch16 --all`
+    run --all                 run every lesson
+    run --match <text>        run every lesson whose id or title contains <text>
+    run ... --annotate        prefix every output line with the source line that printed it
+    run ... --source          print the source lines above the output they produce
+    run ... --strict          refuse to run a lesson whose prerequisites are not completed yet
+    run ... --compare cpp     print the C++ equivalents and pitfalls after each lesson
+    run ... --record <file>   save the order in which the threads of the lessons ran
+    run ... --replay <file>   run the threads in the order saved by `--record`, giving the same output
+    run ... --fast-forward    skip the sleeps of the threads
+    export [dir]              write the notes and their output as Markdown with an HTML index, into `book` by default
+    progress                  show the lessons completed per chapter and the next one to run
+    quiz [lessons]            answer the quizzes of the lessons, selected like `run`, or all of them
+    exercise [name]           copy the exercise stubs to a scratch directory and check them with `cargo test`
+    exercise ... --dir <dir>  use <dir> as the scratch directory
+    exercise ... --reset      overwrite the edited stubs with fresh ones
+    guess                     play the guessing game, typing the guesses
+    guess ... --seed <n>      pick the secret number from seed <n>, the same one every time
+    guess ... --script <file> read the guesses from <file>, one per line
+    match <value> <arm>...    match <value> against the arms, e.g. `match 'Some(4)' 'Some(x) if x > 5' _`,
+                              and show which arm takes it and which arms are unreachable
+    minigrep [-i] [-n] [-C <n>] <query> [file...]
+                              print the lines of the files, or stdin, matching <query>, where `*` and `?` are wildcards
+    serve [addr]              serve `hello.txt` over HTTP with a pool of 4 threads, on 127.0.0.1:7878 by default
+    help                      print this message";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Every lesson, or only those with the tag
+    List(Option<String>),
+    Graph {
+        dot: bool,
+    },
+    Run(Selector, RunOptions),
+    Export(PathBuf),
+    Progress,
+    Quiz(Selector),
+    Exercise(ExerciseOptions),
+    Guess(GuessOptions),
+    /// A value and the arms of a `match`, for `ch18::playground`
+    Match {
+        value: String,
+        arms: Vec<String>,
+    },
+    /// The arguments of `minigrep`, parsed by `ch12::minigrep::Config`
+    Minigrep(Vec<String>),
+    /// The address to serve on, see `ch20::web_server::Server`
+    Serve(String),
+    Help,
+}
+
+/// Flags of the `run` command besides the lesson selection
+#[derive(Debug, Default, PartialEq)]
+pub struct RunOptions {
+    pub annotation: Option<Annotation>,
+    /// Refuse, rather than warn, when prerequisites are not completed
+    pub strict: bool,
+    /// Language to show the lessons' equivalents in, after their output
+    pub compare: Option<Language>,
+    /// Where to save, or read, the order in which the lessons' threads ran
+    pub schedule: Option<ScheduleFile>,
+    /// Skip the sleeps of the lessons' threads
+    pub fast_forward: bool,
+}
+
+/// `--record <file>` or `--replay <file>`, see `schedule`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleFile {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// Arguments of the `exercise` command
+#[derive(Debug, Default, PartialEq)]
+pub struct ExerciseOptions {
+    /// Only the exercise of this name, e.g. `ch5_set_to_max`
+    pub name: Option<String>,
+    /// Scratch directory, `exercise::default_dir()` otherwise
+    pub dir: Option<PathBuf>,
+    /// Overwrite the learner's edits with the stubs
+    pub reset: bool,
+}
+
+/// Arguments of the `guess` command
+#[derive(Debug, Default, PartialEq)]
+pub struct GuessOptions {
+    /// Seed of the secret number, the current time otherwise
+    pub seed: Option<u64>,
+    /// File of guesses to play instead of stdin
+    pub script: Option<PathBuf>,
+}
+
+/// Which lessons a `run` or `quiz` command applies to
+#[derive(Debug, PartialEq)]
+pub enum Selector {
+    /// Every lesson
+    All,
+    /// A lesson id or section, e.g. `ch15::rc_pointers`, `ch15.4`
+    Key(String),
+    /// Every lesson of a chapter, e.g. `ch16 --all`
+    Chapter(u32),
+    /// Every lesson whose id or title contains the text, case-insensitively
+    Match(String),
+}
+
+impl Selector {
+    /// Lessons picked by this selector, in book order
+    pub fn select(&self) -> Result<Vec<Box<dyn Lesson>>, String> {
+        let lessons = registry::lessons();
+        let selected: Vec<Box<dyn Lesson>> = match self {
+            Selector::All => lessons,
+            Selector::Key(key) => {
+                if let Some(Section { chapter, number: 0 }) = Section::parse(key) {
+                    return Err(format!(
+                        "`{key}` is a whole chapter, use `run {key} --all` or pick a section such as `ch{chapter}.1`"
+                    ));
+                }
+                registry::find(key).into_iter().collect()
+            }
+            Selector::Chapter(chapter) => lessons
+                .into_iter()
+                .filter(|lesson| lesson.section().chapter == *chapter)
+                .collect(),
+            Selector::Match(text) => {
+                let text = text.to_lowercase();
+                lessons
+                    .into_iter()
+                    .filter(|lesson| {
+                        lesson.id().to_lowercase().contains(&text)
+                            || lesson.title().to_lowercase().contains(&text)
+                    })
+                    .collect()
+            }
+        };
+        if selected.is_empty() {
+            return Err(format!("No lesson matches {}", self));
+        }
+        Ok(selected)
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Selector::All => write!(f, "--all"),
+            Selector::Key(key) => write!(f, "`{key}`"),
+            Selector::Chapter(chapter) => write!(f, "`ch{chapter} --all`"),
+            Selector::Match(text) => write!(f, "`--match {text}`"),
+        }
+    }
+}
+
+/// Parse the arguments after the program name
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Ok(Command::Help),
+    };
+    let rest: Vec<String> = args.collect();
+
+    match command.as_str() {
+        "list" => match rest.as_slice() {
+            [] => Ok(Command::List(None)),
+            [flag, tag] if flag == "--tag" => Ok(Command::List(Some(tag.clone()))),
+            [flag] if flag == "--tag" => {
+                Err(String::from("`--tag` needs a tag such as `ownership`"))
+            }
+            [arg, ..] => Err(format!("Unexpected argument `{arg}`")),
+        },
+        "graph" => match rest.as_slice() {
+            [] => Ok(Command::Graph { dot: false }),
+            [flag] if flag == "--dot" => Ok(Command::Graph { dot: true }),
+            [arg, ..] => Err(format!("Unexpected argument `{arg}`")),
+        },
+        "run" => parse_run(&rest),
+        "export" => match rest.as_slice() {
+            [] => Ok(Command::Export(PathBuf::from("book"))),
+            [dir] if !dir.starts_with("--") => Ok(Command::Export(PathBuf::from(dir))),
+            [_, arg, ..] => Err(format!("Unexpected argument `{arg}`")),
+            [flag] => Err(format!("Unknown flag `{flag}`")),
+        },
+        "progress" => no_more_args(&rest).map(|_| Command::Progress),
+        "quiz" if rest.is_empty() => Ok(Command::Quiz(Selector::All)),
+        "quiz" => parse_selector(&rest).map(Command::Quiz),
+        "exercise" => parse_exercise(&rest),
+        "guess" => parse_guess(&rest),
+        "match" => match rest.split_first() {
+            Some((value, arms)) if !arms.is_empty() => Ok(Command::Match {
+                value: value.clone(),
+                arms: arms.to_vec(),
+            }),
+            _ => Err(String::from("`match` needs a value and at least one arm")),
+        },
+        "minigrep" => Ok(Command::Minigrep(rest)),
+        "serve" => match rest.as_slice() {
+            [] => Ok(Command::Serve(String::from("127.0.0.1:7878"))),
+            [addr] if !addr.starts_with("--") => Ok(Command::Serve(addr.clone())),
+            [_, arg, ..] => Err(format!("Unexpected argument `{arg}`")),
+            [flag] => Err(format!("Unknown flag `{flag}`")),
+        },
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        other => Err(format!("Unknown command `{other}`")),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Command, String> {
+    let mut options = RunOptions::default();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let flag = match arg.as_str() {
+            "--annotate" => Annotation::Locations,
+            "--source" => Annotation::Source,
+            "--strict" => {
+                options.strict = true;
+                continue;
+            }
+            "--fast-forward" => {
+                options.fast_forward = true;
+                continue;
+            }
+            "--record" | "--replay" => {
+                let Some(path) = args.next() else {
+                    return Err(format!("`{arg}` needs a file to keep the schedules in"));
+                };
+                if options.schedule.is_some() {
+                    return Err(String::from("`--record` and `--replay` cannot be combined"));
+                }
+                let path = PathBuf::from(path);
+                options.schedule = Some(match arg.as_str() {
+                    "--record" => ScheduleFile::Record(path),
+                    _ => ScheduleFile::Replay(path),
+                });
+                continue;
+            }
+            "--compare" => {
+                let Some(language) = args.next() else {
+                    return Err(String::from("`--compare` needs a language, i.e. `cpp`"));
+                };
+                match Language::parse(language) {
+                    Some(language) => options.compare = Some(language),
+                    None => return Err(format!("Cannot compare with `{language}`, only `cpp`")),
+                }
+                continue;
+            }
+            _ => {
+                rest.push(arg.clone());
+                continue;
+            }
+        };
+        if options.annotation.is_some_and(|a| a != flag) {
+            return Err(String::from(
+                "`--annotate` and `--source` cannot be combined",
+            ));
+        }
+        options.annotation = Some(flag);
+    }
+    parse_selector(&rest).map(|selector| Command::Run(selector, options))
+}
+
+fn parse_exercise(args: &[String]) -> Result<Command, String> {
+    let mut options = ExerciseOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--reset" => options.reset = true,
+            "--dir" => match args.next() {
+                Some(dir) => options.dir = Some(PathBuf::from(dir)),
+                None => return Err(String::from("`--dir` needs a directory")),
+            },
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag `{flag}`")),
+            _ if options.name.is_some() => return Err(format!("Unexpected argument `{arg}`")),
+            _ => options.name = Some(arg.clone()),
+        }
+    }
+    Ok(Command::Exercise(options))
+}
+
+fn parse_guess(args: &[String]) -> Result<Command, String> {
+    let mut options = GuessOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => match args.next().map(|seed| seed.parse()) {
+                Some(Ok(seed)) => options.seed = Some(seed),
+                Some(Err(_)) => return Err(String::from("`--seed` needs a whole number")),
+                None => return Err(String::from("`--seed` needs a number")),
+            },
+            "--script" => match args.next() {
+                Some(file) => options.script = Some(PathBuf::from(file)),
+                None => return Err(String::from("`--script` needs a file")),
+            },
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag `{flag}`")),
+            _ => return Err(format!("Unexpected argument `{arg}`")),
+        }
+    }
+    Ok(Command::Guess(options))
+}
+
+/// Lessons picked by `<section|id>`, `<chapter> --all`, `--all` or `--match <text>`
+fn parse_selector(args: &[String]) -> Result<Selector, String> {
+    let mut all = false;
+    let mut text = None;
+    let mut key = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--match" => match args.next() {
+                Some(t) => text = Some(t.clone()),
+                None => return Err(String::from("`--match` needs a text to look for")),
+            },
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag `{flag}`")),
+            _ if key.is_some() => return Err(format!("Unexpected argument `{arg}`")),
+            _ => key = Some(arg.clone()),
+        }
+    }
+
+    match (key, text, all) {
+        (None, None, true) => Ok(Selector::All),
+        (None, Some(text), false) => Ok(Selector::Match(text)),
+        (Some(key), None, false) => Ok(Selector::Key(key)),
+        (Some(key), None, true) => match Section::parse(&key) {
+            Some(Section { chapter, number: 0 }) => Ok(Selector::Chapter(chapter)),
+            _ => Err(format!(
+                "`--all` expects a chapter such as `ch16`, got `{key}`"
+            )),
+        },
+        (None, None, false) => Err(String::from(
+            "`run` needs a section, `--all` or `--match <text>`",
+        )),
+        _ => Err(String::from(
+            "`--match` cannot be combined with a section or `--all`",
+        )),
+    }
+}
+
+fn no_more_args(args: &[String]) -> Result<(), String> {
+    match args.first() {
+        Some(arg) => Err(format!("Unexpected argument `{arg}`")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_run_variants() {
+        assert_eq!(
+            parse(args("run ch15.4")),
+            Ok(Command::Run(
+                Selector::Key(String::from("ch15.4")),
+                RunOptions::default()
+            ))
+        );
+        assert_eq!(
+            parse(args("run ch16 --all")),
+            Ok(Command::Run(Selector::Chapter(16), RunOptions::default()))
+        );
+        assert_eq!(
+            parse(args("run --all")),
+            Ok(Command::Run(Selector::All, RunOptions::default()))
+        );
+        assert_eq!(
+            parse(args("run --match iterators")),
+            Ok(Command::Run(
+                Selector::Match(String::from("iterators")),
+                RunOptions::default()
+            ))
+        );
+        assert_eq!(
+            parse(args("run ch9.2 --annotate")),
+            Ok(Command::Run(
+                Selector::Key(String::from("ch9.2")),
+                RunOptions {
+                    annotation: Some(Annotation::Locations),
+                    ..RunOptions::default()
+                }
+            ))
+        );
+        assert_eq!(
+            parse(args("run --source ch16 --all --strict")),
+            Ok(Command::Run(
+                Selector::Chapter(16),
+                RunOptions {
+                    annotation: Some(Annotation::Source),
+                    strict: true,
+                    ..RunOptions::default()
+                }
+            ))
+        );
+        assert_eq!(
+            parse(args("run ch15.4 --compare cpp")),
+            Ok(Command::Run(
+                Selector::Key(String::from("ch15.4")),
+                RunOptions {
+                    compare: Some(Language::Cpp),
+                    ..RunOptions::default()
+                }
+            ))
+        );
+        assert_eq!(
+            parse(args("run ch16 --all --replay threads.txt --fast-forward")),
+            Ok(Command::Run(
+                Selector::Chapter(16),
+                RunOptions {
+                    schedule: Some(ScheduleFile::Replay(PathBuf::from("threads.txt"))),
+                    fast_forward: true,
+                    ..RunOptions::default()
+                }
+            ))
+        );
+        assert_eq!(
+            parse(args("minigrep -n to poem.txt")),
+            Ok(Command::Minigrep(vec![
+                String::from("-n"),
+                String::from("to"),
+                String::from("poem.txt")
+            ]))
+        );
+        assert_eq!(
+            parse(args("guess")),
+            Ok(Command::Guess(GuessOptions::default()))
+        );
+        assert_eq!(
+            parse(args("guess --script guesses.txt --seed 7")),
+            Ok(Command::Guess(GuessOptions {
+                seed: Some(7),
+                script: Some(PathBuf::from("guesses.txt")),
+            }))
+        );
+        assert_eq!(
+            parse(args("match Some(4) Some(x) _")),
+            Ok(Command::Match {
+                value: String::from("Some(4)"),
+                arms: vec![String::from("Some(x)"), String::from("_")],
+            })
+        );
+        assert_eq!(
+            parse(args("serve")),
+            Ok(Command::Serve(String::from("127.0.0.1:7878")))
+        );
+        assert_eq!(
+            parse(args("serve 0.0.0.0:8080")),
+            Ok(Command::Serve(String::from("0.0.0.0:8080")))
+        );
+        assert_eq!(parse(args("list")), Ok(Command::List(None)));
+        assert_eq!(
+            parse(args("list --tag ownership")),
+            Ok(Command::List(Some(String::from("ownership"))))
+        );
+        assert_eq!(parse(args("graph")), Ok(Command::Graph { dot: false }));
+        assert_eq!(parse(args("graph --dot")), Ok(Command::Graph { dot: true }));
+        assert_eq!(parse(args("progress")), Ok(Command::Progress));
+        assert_eq!(
+            parse(args("export")),
+            Ok(Command::Export(PathBuf::from("book")))
+        );
+        assert_eq!(
+            parse(args("export /tmp/notes")),
+            Ok(Command::Export(PathBuf::from("/tmp/notes")))
+        );
+        assert_eq!(parse(args("quiz")), Ok(Command::Quiz(Selector::All)));
+        assert_eq!(
+            parse(args("exercise")),
+            Ok(Command::Exercise(ExerciseOptions::default()))
+        );
+        assert_eq!(
+            parse(args("exercise ch5_set_to_max --dir /tmp/ex --reset")),
+            Ok(Command::Exercise(ExerciseOptions {
+                name: Some(String::from("ch5_set_to_max")),
+                dir: Some(PathBuf::from("/tmp/ex")),
+                reset: true
+            }))
+        );
+        assert_eq!(
+            parse(args("quiz ch16 --all")),
+            Ok(Command::Quiz(Selector::Chapter(16)))
+        );
+        assert_eq!(parse(args("")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parse(args("fly")).is_err());
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run ch15.4 --all")).is_err());
+        assert!(parse(args("run --match")).is_err());
+        assert!(parse(args("run --match x --all")).is_err());
+        assert!(parse(args("export a b")).is_err());
+        assert!(parse(args("run ch9.2 --annotate --source")).is_err());
+        assert!(parse(args("quiz --annotate")).is_err());
+        assert!(parse(args("list --tag")).is_err());
+        assert!(parse(args("graph --svg")).is_err());
+        assert!(parse(args("run ch15.4 --compare")).is_err());
+        assert!(parse(args("exercise a b")).is_err());
+        assert!(parse(args("run ch16.1 --record")).is_err());
+        assert!(parse(args("run ch16.1 --record a --replay b")).is_err());
+        assert!(parse(args("exercise --dir")).is_err());
+        assert!(parse(args("run ch15.4 --compare java")).is_err());
+        assert!(parse(args("guess --seed seven")).is_err());
+        assert!(parse(args("guess --script")).is_err());
+        assert!(parse(args("guess 7")).is_err());
+        assert!(parse(args("match Some(4)")).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ch13", feature = "ch15", feature = "ch16"))]
+    fn selects_lessons_in_book_order() {
+        let ids = |selector: Selector| -> Vec<&'static str> {
+            selector.select().unwrap().iter().map(|l| l.id()).collect()
+        };
+        assert_eq!(
+            ids(Selector::Chapter(16)),
+            vec!["ch16::threads", "ch16::messages", "ch16::mutexes"]
+        );
+        assert_eq!(
+            ids(Selector::Match(String::from("ITERATORS"))),
+            vec!["ch13::iterators"]
+        );
+        assert_eq!(
+            ids(Selector::Key(String::from("ch15.4"))),
+            vec!["ch15::rc_pointers"]
+        );
+        assert!(Selector::Key(String::from("ch16")).select().is_err());
+        assert!(Selector::Chapter(21).select().is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! # Comparisons with C++
+//!     * a lesson may carry the C++ it replaces, e.g. `Rc<T>` next to `std::shared_ptr<T>`, see `Lesson::cpp`
+//!     * `run ... --compare cpp` prints them in a panel after the lesson's output
+//!     * each snippet is a complete C++17 translation unit without `main`, so `g++ -fsyntax-only` can check it
+//!         * `cargo test --test cpp_snippets -- --ignored` does, when a `g++` is installed
+use crate::lesson::Lesson;
+use std::fmt;
+use std::io::{self, Write};
+
+/// Language a lesson can be compared with, given to `--compare`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Cpp,
+}
+
+impl Language {
+    pub fn parse(s: &str) -> Option<Language> {
+        match s.to_lowercase().as_str() {
+            "cpp" | "c++" => Some(Language::Cpp),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Language::Cpp => f.pad("C++"),
+        }
+    }
+}
+
+/// The C++ counterpart of something a note shows in Rust
+#[derive(Debug)]
+pub struct Comparison {
+    /// What is compared, e.g. "`Rc<T>` vs `std::shared_ptr<T>`"
+    pub topic: &'static str,
+    /// C++17 code, with its `#include`s and without `main`
+    pub cpp: &'static str,
+    /// Mistakes the C++ version lets through, and how Rust rules them out
+    pub pitfalls: &'static [&'static str],
+}
+
+/// Print the comparisons of `lesson` with `language`, nothing when it has none
+pub fn write_panel(lesson: &dyn Lesson, language: Language, out: &mut dyn Write) -> io::Result<()> {
+    let comparisons = match language {
+        Language::Cpp => lesson.cpp(),
+    };
+    for comparison in comparisons {
+        writeln!(out, "\n+--- {language}: {}", comparison.topic)?;
+        for line in comparison.cpp.trim_end().lines() {
+            writeln!(out, "{}", format!("| {line}").trim_end())?;
+        }
+        if !comparison.pitfalls.is_empty() {
+            writeln!(out, "|")?;
+            writeln!(out, "| Pitfalls:")?;
+            for pitfall in comparison.pitfalls {
+                writeln!(out, "|   * {pitfall}")?;
+            }
+        }
+        writeln!(out, "+---")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::TestLesson;
+
+    fn compared() -> TestLesson {
+        TestLesson {
+            cpp: &[Comparison {
+                topic: "`Box<T>` vs `std::unique_ptr<T>`",
+                cpp: "#include <memory>\n\nauto b = std::make_unique<int>(5);\n",
+                pitfalls: &["a moved-from `unique_ptr` is null"],
+            }],
+            ..TestLesson::new("ch0::compared")
+        }
+    }
+
+    #[test]
+    fn parses_languages() {
+        assert_eq!(Language::parse("cpp"), Some(Language::Cpp));
+        assert_eq!(Language::parse("C++"), Some(Language::Cpp));
+        assert_eq!(Language::parse("java"), None);
+    }
+
+    #[test]
+    fn writes_a_panel_per_comparison() {
+        let mut out = Vec::new();
+        write_panel(&compared(), Language::Cpp, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\n+--- C++: `Box<T>` vs `std::unique_ptr<T>`\n\
+             | #include <memory>\n\
+             |\n\
+             | auto b = std::make_unique<int>(5);\n\
+             |\n\
+             | Pitfalls:\n\
+             |   * a moved-from `unique_ptr` is null\n\
+             +---\n"
+        );
+    }
+}
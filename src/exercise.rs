@@ -0,0 +1,173 @@
+//! # Exercises on the notes
+//!     * a lesson may carry exercises, e.g. `set_to_max` for `ch5::method`, see `Lesson::exercises`
+//!     * each one is a stub crate the learner finishes, plus checks the stub does not show
+//!     * `exercise` copies the stubs into a scratch directory, writes the checks next to them and runs `cargo test` there
+//!         * a stub already copied is left alone, so the learner's edits survive, unless `--reset`
+//!     * the stubs, checks and reference solutions live in `exercises/<name>/`
+use crate::lesson::Lesson;
+use crate::progress;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A task at the end of a section, e.g. implementing `MyIterator` for `Counter`
+#[derive(Debug)]
+pub struct Exercise {
+    /// Crate name of the exercise, e.g. `ch5_set_to_max`, also its directory in the scratch directory
+    pub name: &'static str,
+    /// What to do, in one line
+    pub task: &'static str,
+    /// `src/lib.rs` of the crate, for the learner to edit
+    pub stub: &'static str,
+    /// `tests/checks.rs` of the crate, what the finished stub must pass
+    pub checks: &'static str,
+}
+
+/// Default scratch directory, `None` when no home or data directory is known
+pub fn default_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("RUST_AFTER_CPP_EXERCISES") {
+        return Some(PathBuf::from(dir));
+    }
+    Some(progress::data_dir()?.join("exercises"))
+}
+
+/// Every exercise of `lessons`, with its lesson, in book order
+pub fn exercises(lessons: &[Box<dyn Lesson>]) -> Vec<(&dyn Lesson, &'static Exercise)> {
+    lessons
+        .iter()
+        .flat_map(|lesson| lesson.exercises().iter().map(|e| (lesson.as_ref(), e)))
+        .collect()
+}
+
+/// Write the crate of `exercise` into `dir/<name>`, returning the crate's directory
+///     * `src/lib.rs` is only written when missing or with `reset`, `Cargo.toml` and the checks always are
+pub fn prepare(exercise: &Exercise, dir: &Path, reset: bool) -> io::Result<PathBuf> {
+    let root = dir.join(exercise.name);
+    fs::create_dir_all(root.join("src"))?;
+    fs::create_dir_all(root.join("tests"))?;
+    // - an empty `[workspace]` keeps cargo from looking for a workspace above the scratch directory
+    // - the notes' indented bullets read as code blocks to rustdoc, hence no doctests, as for this crate
+    fs::write(
+        root.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [lib]\ndoctest = false\n\n[workspace]\n",
+            exercise.name
+        ),
+    )?;
+    let lib = root.join("src").join("lib.rs");
+    if reset || !lib.exists() {
+        fs::write(&lib, exercise.stub)?;
+    }
+    fs::write(root.join("tests").join("checks.rs"), exercise.checks)?;
+    Ok(root)
+}
+
+/// How the checks of an exercise went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Passed,
+    Failed,
+    DoesNotCompile,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Verdict::Passed => "passed",
+            Verdict::Failed => "failed",
+            Verdict::DoesNotCompile => "does not compile",
+        })
+    }
+}
+
+/// Run `cargo test` in the crate prepared at `root`
+///     * `CARGO` selects cargo, as set when running under cargo itself, `cargo` from `PATH` otherwise
+///     * the crates of a scratch directory share its `target` directory
+pub fn check(root: &Path) -> io::Result<Verdict> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let target = root.parent().unwrap_or(root).join("target");
+    let output = Command::new(cargo)
+        .args(["test", "--quiet", "--color", "never"])
+        .current_dir(root)
+        .env("CARGO_TARGET_DIR", target)
+        .output()?;
+    if output.status.success() {
+        Ok(Verdict::Passed)
+    } else if String::from_utf8_lossy(&output.stderr).contains("could not compile") {
+        Ok(Verdict::DoesNotCompile)
+    } else {
+        Ok(Verdict::Failed)
+    }
+}
+
+/// One row per exercise with its verdict and the file to edit, then how many passed
+pub fn write_report(
+    results: &[(&dyn Lesson, &Exercise, Verdict)],
+    dir: &Path,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    for (lesson, exercise, verdict) in results {
+        writeln!(
+            out,
+            "{:<8} {:<20} {:<16} {}",
+            lesson.section(),
+            exercise.name,
+            verdict,
+            exercise.task
+        )?;
+        if *verdict != Verdict::Passed {
+            let lib = dir.join(exercise.name).join("src").join("lib.rs");
+            writeln!(out, "{:<8} edit {}", "", lib.display())?;
+        }
+    }
+    let passed = results
+        .iter()
+        .filter(|(_, _, verdict)| *verdict == Verdict::Passed)
+        .count();
+    writeln!(
+        out,
+        "\n{} of {} exercises passed, run `cargo test` in an exercise's directory for details",
+        passed,
+        results.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXERCISE: Exercise = Exercise {
+        name: "ch0_answer",
+        task: "Return 42",
+        stub: "pub fn answer() -> u32 {\n    todo!()\n}\n",
+        checks: "#[test]\nfn answers() {\n    assert_eq!(ch0_answer::answer(), 42);\n}\n",
+    };
+
+    #[test]
+    fn prepare_keeps_the_learners_edits() {
+        let dir = env::temp_dir().join(format!("rust_after_cpp-exercise-{}", std::process::id()));
+        let root = prepare(&EXERCISE, &dir, false).unwrap();
+        let lib = root.join("src").join("lib.rs");
+        assert_eq!(fs::read_to_string(&lib).unwrap(), EXERCISE.stub);
+        assert_eq!(
+            fs::read_to_string(root.join("tests").join("checks.rs")).unwrap(),
+            EXERCISE.checks
+        );
+        assert!(fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("name = \"ch0_answer\""));
+
+        let solved = "pub fn answer() -> u32 {\n    42\n}\n";
+        fs::write(&lib, solved).unwrap();
+        prepare(&EXERCISE, &dir, false).unwrap();
+        assert_eq!(fs::read_to_string(&lib).unwrap(), solved);
+
+        prepare(&EXERCISE, &dir, true).unwrap();
+        assert_eq!(fs::read_to_string(&lib).unwrap(), EXERCISE.stub);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+//! # Export the notes as a static study guide
+//!     * one Markdown file per section, `<dir>/ch16/mutexes.md`, holding the note's doc comments, its code, its captured output and its C++ equivalents if any
+//!     * `<dir>/index.html` links every section, grouped by chapter, with a chapter navigation bar
+//!     * everything is produced from `Lesson::source` and `Lesson::run`, so the export works offline and from the binary alone
+use crate::lesson::Lesson;
+use crate::runner::{self, Outcome};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Write the Markdown files and the HTML index of `lessons` into `dir`, returning the files written
+pub fn export(lessons: &[Box<dyn Lesson>], dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for lesson in lessons {
+        let path = dir.join(markdown_file(lesson.as_ref()));
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(
+            &path,
+            markdown(lesson.as_ref(), &captured_output(lesson.as_ref())?),
+        )?;
+        written.push(path);
+    }
+    let index = dir.join("index.html");
+    fs::write(&index, index_html(lessons))?;
+    written.push(index);
+    Ok(written)
+}
+
+/// e.g. `ch16/mutexes.md`, relative to the export directory and usable as a link
+fn markdown_file(lesson: &dyn Lesson) -> String {
+    format!("{}.md", lesson.id().replace("::", "/"))
+}
+
+/// What the lesson prints, ending with a note when it panics, e.g. `ch9::results` on purpose
+fn captured_output(lesson: &dyn Lesson) -> io::Result<String> {
+    let mut out = Vec::new();
+    let outcome = runner::run_isolated(lesson, &mut out)?;
+    let mut output = String::from_utf8_lossy(&out).into_owned();
+    if let Outcome::Panicked { panic, .. } = outcome {
+        output.push_str(&format!(
+            "\n(the lesson panicked: {})\n",
+            panic.message.trim()
+        ));
+    }
+    Ok(output)
+}
+
+/// The note's top-level doc comments as Markdown, without its own `# Ch..` heading line
+fn notes(source: &str) -> String {
+    let mut lines: Vec<&str> = source
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("//!")
+                .or_else(|| line.strip_prefix("///"))
+        })
+        .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+        .collect();
+    if lines
+        .first()
+        .is_some_and(|first| first.starts_with("# ") || first.starts_with("Ch"))
+    {
+        lines.remove(0);
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// The note's code without its top-level doc comments, up to the `Lesson` boilerplate
+fn code(source: &str) -> String {
+    let end = source
+        .find("use crate::lesson::{Lesson, Section};")
+        .unwrap_or(source.len());
+    let lines: Vec<&str> = source[..end]
+        .lines()
+        .filter(|line| !line.starts_with("//!") && !line.starts_with("///"))
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+fn markdown(lesson: &dyn Lesson, output: &str) -> String {
+    let mut md = format!("# {} - {}\n\n", lesson.section(), lesson.title());
+    let notes = notes(lesson.source());
+    if !notes.is_empty() {
+        md.push_str(&notes);
+        md.push_str("\n\n");
+    }
+    md.push_str(&format!(
+        "## Code\n\n```rust\n{}\n```\n\n",
+        code(lesson.source())
+    ));
+    md.push_str(&format!(
+        "## Output\n\n```text\n{}\n```\n",
+        output.trim_matches('\n')
+    ));
+    for comparison in lesson.cpp() {
+        md.push_str(&format!(
+            "\n## C++: {}\n\n```cpp\n{}\n```\n",
+            comparison.topic,
+            comparison.cpp.trim_end()
+        ));
+        for pitfall in comparison.pitfalls {
+            md.push_str(&format!("\n* {pitfall}"));
+        }
+        if !comparison.pitfalls.is_empty() {
+            md.push('\n');
+        }
+    }
+    md
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn index_html(lessons: &[Box<dyn Lesson>]) -> String {
+    let mut chapters: Vec<u32> = lessons.iter().map(|l| l.section().chapter).collect();
+    chapters.dedup();
+
+    let nav: Vec<String> = chapters
+        .iter()
+        .map(|chapter| format!("<a href=\"#ch{chapter}\">ch{chapter}</a>"))
+        .collect();
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Rust after C++</title>\n</head>\n<body>\n\
+         <h1>Rust after C++</h1>\n<nav>{}</nav>\n",
+        nav.join(" | ")
+    );
+    for chapter in chapters {
+        html.push_str(&format!(
+            "<h2 id=\"ch{chapter}\">Chapter {chapter}</h2>\n<ul>\n"
+        ));
+        for lesson in lessons.iter().filter(|l| l.section().chapter == chapter) {
+            html.push_str(&format!(
+                "<li><a href=\"{}\">{} - {}</a></li>\n",
+                markdown_file(lesson.as_ref()),
+                lesson.section(),
+                escape_html(lesson.title())
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::{Section, TestLesson};
+
+    const SOURCE: &str = "\
+//! # Ch2.1 - A Note
+//! * first point
+//!     * nested point
+use std::io::{self, Write};
+
+/// The lesson
+pub struct Note {}
+
+use crate::lesson::{Lesson, Section};
+
+impl Lesson for Note {}
+";
+
+    fn note() -> TestLesson {
+        TestLesson {
+            section: Section::new(2, 1),
+            title: "A <Note>",
+            source: SOURCE,
+            run: |out| writeln!(out, "printed"),
+            ..TestLesson::new("ch2::note")
+        }
+    }
+
+    #[test]
+    fn splits_notes_from_code() {
+        assert_eq!(
+            notes(SOURCE),
+            "* first point\n    * nested point\nThe lesson"
+        );
+        assert_eq!(
+            code(SOURCE),
+            "use std::io::{self, Write};\n\npub struct Note {}"
+        );
+    }
+
+    #[test]
+    fn writes_markdown_and_index() {
+        let dir =
+            std::env::temp_dir().join(format!("rust_after_cpp-export-{}", std::process::id()));
+        let lessons: Vec<Box<dyn Lesson>> = vec![Box::new(note())];
+        let written = export(&lessons, &dir).unwrap();
+        assert_eq!(
+            written,
+            vec![dir.join("ch2/note.md"), dir.join("index.html")]
+        );
+
+        let md = fs::read_to_string(dir.join("ch2/note.md")).unwrap();
+        assert!(md.starts_with("# ch2.1 - A <Note>\n\n* first point\n"));
+        assert!(md.ends_with("## Output\n\n```text\nprinted\n```\n"));
+        let html = fs::read_to_string(dir.join("index.html")).unwrap();
+        assert!(html.contains("<nav><a href=\"#ch2\">ch2</a></nav>"));
+        assert!(html.contains("<li><a href=\"ch2/note.md\">ch2.1 - A &lt;Note&gt;</a></li>"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
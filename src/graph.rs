@@ -0,0 +1,178 @@
+//! # Prerequisites between lessons
+//!     * each lesson names the lessons to study first, e.g. `ch16::mutexes` builds on `ch15::rc_pointers` and `ch16::threads`
+//!     * `graph` prints the dependencies as text, `graph --dot` as a Graphviz digraph with one cluster per chapter
+//!     * `run` warns, or with `--strict` refuses, when a lesson's prerequisites have not been completed yet
+//!     * a prerequisite from a chapter left out by the cargo features is ignored
+use crate::lesson::Lesson;
+use std::io::{self, Write};
+
+/// Prerequisites of `lesson` that are among `lessons`, i.e. whose chapter is compiled in
+fn known_prerequisites<'a>(
+    lesson: &dyn Lesson,
+    lessons: &'a [Box<dyn Lesson>],
+) -> Vec<&'a dyn Lesson> {
+    lesson
+        .prerequisites()
+        .iter()
+        .filter_map(|id| lessons.iter().find(|l| l.id() == *id))
+        .map(|l| l.as_ref())
+        .collect()
+}
+
+/// For each of `to_run` with unmet prerequisites, those not `completed` yet nor run before it in `to_run`
+pub fn unmet_prerequisites<'a>(
+    to_run: &'a [Box<dyn Lesson>],
+    lessons: &[Box<dyn Lesson>],
+    completed: impl Fn(&str) -> bool,
+) -> Vec<(&'a dyn Lesson, Vec<&'static str>)> {
+    let mut unmet = Vec::new();
+    for (i, lesson) in to_run.iter().enumerate() {
+        let missing: Vec<&'static str> = known_prerequisites(lesson.as_ref(), lessons)
+            .into_iter()
+            .map(|prerequisite| prerequisite.id())
+            .filter(|id| !completed(id) && !to_run[..i].iter().any(|l| l.id() == *id))
+            .collect();
+        if !missing.is_empty() {
+            unmet.push((lesson.as_ref(), missing));
+        }
+    }
+    unmet
+}
+
+/// One line per lesson, followed by the lessons it requires
+pub fn write_text(lessons: &[Box<dyn Lesson>], out: &mut dyn Write) -> io::Result<()> {
+    for lesson in lessons {
+        let prerequisites: Vec<&str> = known_prerequisites(lesson.as_ref(), lessons)
+            .iter()
+            .map(|l| l.id())
+            .collect();
+        let requires = match prerequisites.as_slice() {
+            [] => String::from("(start here)"),
+            ids => format!("<- {}", ids.join(", ")),
+        };
+        writeln!(
+            out,
+            "{:<8} {:<28} {}",
+            lesson.section(),
+            lesson.id(),
+            requires
+        )?;
+    }
+    Ok(())
+}
+
+/// A Graphviz digraph, edges going from a prerequisite to the lessons that build on it
+pub fn write_dot(lessons: &[Box<dyn Lesson>], out: &mut dyn Write) -> io::Result<()> {
+    let mut chapters: Vec<u32> = lessons.iter().map(|l| l.section().chapter).collect();
+    chapters.dedup();
+
+    writeln!(out, "digraph lessons {{")?;
+    writeln!(out, "    rankdir=LR;")?;
+    writeln!(out, "    node [shape=box];")?;
+    for chapter in chapters {
+        writeln!(out, "    subgraph cluster_ch{chapter} {{")?;
+        writeln!(out, "        label=\"Chapter {chapter}\";")?;
+        for lesson in lessons.iter().filter(|l| l.section().chapter == chapter) {
+            writeln!(
+                out,
+                "        \"{}\" [label=\"{}\\n{}\"];",
+                lesson.id(),
+                lesson.section(),
+                lesson.title().replace('"', "\\\"")
+            )?;
+        }
+        writeln!(out, "    }}")?;
+    }
+    for lesson in lessons {
+        for prerequisite in known_prerequisites(lesson.as_ref(), lessons) {
+            writeln!(out, "    \"{}\" -> \"{}\";", prerequisite.id(), lesson.id())?;
+        }
+    }
+    writeln!(out, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::{Section, TestLesson};
+    use crate::registry;
+
+    fn note(
+        id: &'static str,
+        chapter: u32,
+        prerequisites: &'static [&'static str],
+    ) -> Box<dyn Lesson> {
+        Box::new(TestLesson {
+            section: Section::new(chapter, 1),
+            title: "A \"note\"",
+            prerequisites,
+            ..TestLesson::new(id)
+        })
+    }
+
+    fn notes() -> Vec<Box<dyn Lesson>> {
+        vec![
+            note("ch1::a", 1, &[]),
+            note("ch2::b", 2, &["ch1::a", "ch99::left_out"]),
+            note("ch3::c", 3, &["ch1::a", "ch2::b"]),
+        ]
+    }
+
+    #[test]
+    fn prerequisites_exist_and_come_earlier_in_the_book() {
+        let lessons = registry::lessons();
+        for lesson in &lessons {
+            for id in lesson.prerequisites() {
+                let chapter = &id[..id.find("::").unwrap() + 2];
+                if let Some(prerequisite) = lessons.iter().find(|l| l.id() == *id) {
+                    assert!(
+                        prerequisite.section() < lesson.section(),
+                        "{id} after {}",
+                        lesson.id()
+                    );
+                } else {
+                    // - only a chapter left out by the features may be missing
+                    assert!(
+                        !lessons.iter().any(|l| l.id().starts_with(chapter)),
+                        "{} requires unknown {id}",
+                        lesson.id()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_unmet_prerequisites() {
+        let lessons = notes();
+        let unmet = unmet_prerequisites(&lessons[2..], &lessons, |id| id == "ch1::a");
+        assert_eq!(unmet.len(), 1);
+        assert_eq!(unmet[0].0.id(), "ch3::c");
+        assert_eq!(unmet[0].1, vec!["ch2::b"]);
+
+        // - a prerequisite run earlier in the same run counts as met
+        assert!(unmet_prerequisites(&lessons[1..], &lessons, |id| id == "ch1::a").is_empty());
+    }
+
+    #[test]
+    fn writes_text_and_dot() {
+        let lessons = notes();
+        let mut text = Vec::new();
+        write_text(&lessons, &mut text).unwrap();
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "ch1.1    ch1::a                       (start here)\n\
+             ch2.1    ch2::b                       <- ch1::a\n\
+             ch3.1    ch3::c                       <- ch1::a, ch2::b\n"
+        );
+
+        let mut dot = Vec::new();
+        write_dot(&lessons, &mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph lessons {\n"));
+        assert!(dot.contains("    subgraph cluster_ch2 {\n        label=\"Chapter 2\";\n        \"ch2::b\" [label=\"ch2.1\\nA \\\"note\\\"\"];\n    }\n"));
+        assert!(dot.contains("    \"ch2::b\" -> \"ch3::c\";\n"));
+        assert!(!dot.contains("left_out"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
@@ -0,0 +1,362 @@
+//! # The `Lesson` trait shared by every note
+//!     * each note module exposes a unit struct, e.g. `ch3::data_types::DataTypes`, that implements `Lesson`
+//!     * `registry::lessons()` collects all of them in book order so the binary can pick one at runtime
+//!     * lessons write to a supplied `Write` rather than stdout, so their output can be captured, compared or saved
+//!     * a lesson may also carry the quizzes of its note, see `quiz::Quiz`, and its C++ counterparts, see `compare`
+//!     * and exercises for the learner to finish, see `exercise`
+//!     * lessons print with `outln!`, a `writeln!` that also records the source line, see `annotate`
+use crate::compare::Comparison;
+use crate::exercise::Exercise;
+use crate::quiz::Quiz;
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// Book section a lesson belongs to, e.g. `ch15.4`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Section {
+    pub chapter: u32,
+    pub number: u32,
+}
+
+impl Section {
+    pub const fn new(chapter: u32, number: u32) -> Section {
+        Section { chapter, number }
+    }
+
+    /// Parse `ch15.4`, `15.4` or, for a whole chapter, `ch15` (number `0`)
+    pub fn parse(s: &str) -> Option<Section> {
+        let s = s.strip_prefix("ch").unwrap_or(s);
+        match s.split_once('.') {
+            Some((chapter, number)) => {
+                Some(Section::new(chapter.parse().ok()?, number.parse().ok()?))
+            }
+            None => Some(Section::new(s.parse().ok()?, 0)),
+        }
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // - `pad` so that width and alignment flags, e.g. `{:<8}`, apply to the whole label
+        f.pad(&format!("ch{}.{}", self.chapter, self.number))
+    }
+}
+
+/// Source line that wrote a line of a lesson's output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// As given by `file!()`, e.g. `src/ch9/results.rs`
+    pub file: &'static str,
+    pub line: u32,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("{}:{}", self.file, self.line))
+    }
+}
+
+thread_local! {
+    static LOCATION: Cell<Option<Location>> = const { Cell::new(None) };
+}
+
+/// Record where the next output of this thread comes from, called by `outln!`
+pub fn set_location(location: Option<Location>) {
+    LOCATION.with(|current| current.set(location));
+}
+
+/// Where the last `outln!` of this thread was
+pub fn location() -> Option<Location> {
+    LOCATION.with(|current| current.get())
+}
+
+/// `writeln!` for lesson output that also records its `file!()` and `line!()`
+///     * a drop-in for `println!` in a note, e.g. `outln!(out, "x is {x}")?;`
+///     * the location is read by the `annotate` writers, plain writers ignore it
+#[macro_export]
+macro_rules! outln {
+    ($out:expr $(, $($arg:tt)*)?) => {{
+        $crate::lesson::set_location(Some($crate::lesson::Location {
+            file: file!(),
+            line: line!(),
+        }));
+        writeln!($out $(, $($arg)*)?)
+    }};
+}
+
+pub trait Lesson {
+    /// Module path relative to the crate root, e.g. `ch15::rc_pointers`
+    fn id(&self) -> &'static str;
+
+    fn section(&self) -> Section;
+
+    fn title(&self) -> &'static str;
+
+    /// Topics of the note, e.g. `ownership` or `concurrency`, for `list --tag`
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Ids of the lessons to study first, e.g. `ch15::rc_pointers` for `ch16::mutexes`
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// The note's source file, e.g. `include_str!("rc_pointers.rs")`, for exporting or quoting it
+    fn source(&self) -> &'static str;
+
+    /// Run the note's code, writing its output to `out`
+    fn run(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    /// Whether the note panics on purpose, e.g. to show `expect`, see `runner::run_isolated`
+    fn panics(&self) -> bool {
+        false
+    }
+
+    /// Questions on the note, asked by the `quiz` command
+    fn quizzes(&self) -> &'static [Quiz] {
+        &[]
+    }
+
+    /// The C++ equivalents of the note, shown by `run --compare cpp`
+    fn cpp(&self) -> &'static [Comparison] {
+        &[]
+    }
+
+    /// Tasks on the note, checked by the `exercise` command
+    fn exercises(&self) -> &'static [Exercise] {
+        &[]
+    }
+}
+
+/// A lesson's output shared with the threads it spawns
+///     * a spawned thread cannot borrow the lesson's `&mut dyn Write`, so it owns a `ThreadWriter` instead
+///     * complete lines written by threads are forwarded to the lesson's output whenever the main thread starts a new line
+///     * `flush` forwards everything that is left
+///     * each forwarded line keeps the `outln!` location it was written from on its thread
+pub struct SharedOutput<'a> {
+    out: &'a mut dyn Write,
+    pending: Arc<Mutex<Pending>>,
+    at_line_start: bool,
+}
+
+/// Bytes written by threads, and the location each of their lines starts from
+#[derive(Default)]
+struct Pending {
+    bytes: Vec<u8>,
+    line_starts: Vec<(usize, Option<Location>)>,
+}
+
+impl<'a> SharedOutput<'a> {
+    pub fn new(out: &'a mut dyn Write) -> SharedOutput<'a> {
+        SharedOutput {
+            out,
+            pending: Arc::new(Mutex::new(Pending::default())),
+            at_line_start: true,
+        }
+    }
+
+    /// A writer that can be moved into `thread::spawn`
+    pub fn writer(&self) -> ThreadWriter {
+        ThreadWriter {
+            pending: Arc::clone(&self.pending),
+        }
+    }
+
+    /// Forward what threads wrote so far, only up to their last complete line unless `everything`
+    fn forward_pending(&mut self, everything: bool) -> io::Result<()> {
+        let (forwarded, mut line_starts) = {
+            let mut pending = self.pending.lock().unwrap();
+            let end = match pending.bytes.iter().rposition(|&b| b == b'\n') {
+                _ if everything => pending.bytes.len(),
+                Some(i) => i + 1,
+                None => 0,
+            };
+            let split = pending
+                .line_starts
+                .partition_point(|&(start, _)| start < end);
+            let line_starts: Vec<(usize, Option<Location>)> =
+                pending.line_starts.drain(..split).collect();
+            for (start, _) in pending.line_starts.iter_mut() {
+                *start -= end;
+            }
+            // - what is left may be the rest of a line that started in the forwarded part
+            let last_location = line_starts.last().and_then(|&(_, location)| location);
+            if end < pending.bytes.len()
+                && pending
+                    .line_starts
+                    .first()
+                    .is_none_or(|&(start, _)| start > 0)
+            {
+                pending.line_starts.insert(0, (0, last_location));
+            }
+            let forwarded: Vec<u8> = pending.bytes.drain(..end).collect();
+            (forwarded, line_starts)
+        };
+        if line_starts.first().is_none_or(|&(start, _)| start > 0) {
+            line_starts.insert(0, (0, None));
+        }
+        line_starts.push((forwarded.len(), None));
+
+        let main_location = location();
+        for pair in line_starts.windows(2) {
+            let ((start, location), (end, _)) = (pair[0], pair[1]);
+            set_location(location);
+            self.out.write_all(&forwarded[start..end])?;
+        }
+        set_location(main_location);
+        Ok(())
+    }
+}
+
+impl Write for SharedOutput<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // - `writeln!` may call `write` several times per line, so only interleave between lines
+        if self.at_line_start {
+            self.forward_pending(false)?;
+        }
+        let n = self.out.write(buf)?;
+        if n > 0 {
+            self.at_line_start = buf[n - 1] == b'\n';
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.forward_pending(true)?;
+        self.out.flush()
+    }
+}
+
+/// The spawned-thread end of a `SharedOutput`
+#[derive(Clone)]
+pub struct ThreadWriter {
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl Write for ThreadWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.bytes.last().is_none_or(|&b| b == b'\n') {
+            let start = pending.bytes.len();
+            pending.line_starts.push((start, location()));
+        }
+        pending.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A lesson set up field by field, for the tests of the modules that take lessons
+///     * e.g. `TestLesson { panics: true, ..TestLesson::new("ch0::panicky") }`
+#[cfg(test)]
+pub(crate) struct TestLesson {
+    pub id: &'static str,
+    pub section: Section,
+    pub title: &'static str,
+    pub prerequisites: &'static [&'static str],
+    pub source: &'static str,
+    pub run: fn(&mut dyn Write) -> io::Result<()>,
+    pub panics: bool,
+    pub quizzes: &'static [Quiz],
+    pub cpp: &'static [Comparison],
+}
+
+#[cfg(test)]
+impl TestLesson {
+    /// A lesson of section `ch0.1` that writes nothing
+    pub(crate) fn new(id: &'static str) -> TestLesson {
+        TestLesson {
+            id,
+            section: Section::new(0, 1),
+            title: "A note",
+            prerequisites: &[],
+            source: "",
+            run: |_| Ok(()),
+            panics: false,
+            quizzes: &[],
+            cpp: &[],
+        }
+    }
+}
+
+#[cfg(test)]
+impl Lesson for TestLesson {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn section(&self) -> Section {
+        self.section
+    }
+
+    fn title(&self) -> &'static str {
+        self.title
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        self.prerequisites
+    }
+
+    fn source(&self) -> &'static str {
+        self.source
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        (self.run)(out)
+    }
+
+    fn panics(&self) -> bool {
+        self.panics
+    }
+
+    fn quizzes(&self) -> &'static [Quiz] {
+        self.quizzes
+    }
+
+    fn cpp(&self) -> &'static [Comparison] {
+        self.cpp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections_and_chapters() {
+        assert_eq!(Section::parse("ch15.4"), Some(Section::new(15, 4)));
+        assert_eq!(Section::parse("3.2"), Some(Section::new(3, 2)));
+        assert_eq!(Section::parse("ch16"), Some(Section::new(16, 0)));
+        assert_eq!(Section::parse("iterators"), None);
+    }
+
+    #[test]
+    fn displays_as_chapter_dot_section() {
+        assert_eq!(Section::new(15, 4).to_string(), "ch15.4");
+    }
+
+    #[test]
+    fn shared_output_forwards_thread_writes_in_order() {
+        let mut buf = Vec::new();
+        let mut shared = SharedOutput::new(&mut buf);
+        let mut writer = shared.writer();
+        std::thread::spawn(move || writeln!(writer, "from thread"))
+            .join()
+            .unwrap()
+            .unwrap();
+        writeln!(shared, "from main").unwrap();
+        let mut writer = shared.writer();
+        writeln!(writer, "late").unwrap();
+        shared.flush().unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "from thread\nfrom main\nlate\n"
+        );
+    }
+}
@@ -1,7 +1,63 @@
 //! # For external to use moduels we need this lib.rs file and statement like below
 //!
 //!     ## e.g. integration_test
+//!
+//! * every chapter is a cargo feature, all enabled by default; `registry::lessons()` lists the enabled ones for the binary
+//!     * e.g., `cargo test --no-default-features --features ch15,ch16` builds only those two chapters
+// The notes keep the book's code as written, so each chapter's `mod.rs` allows the lints its notes trip,
+// e.g. unused bindings or patterns clippy would rewrite, and the tooling modules below are linted as usual
+// - first, so that the chapters can use its `outln!` macro
+#[macro_use]
+pub mod lesson;
+
+#[cfg(feature = "ch1")]
+pub mod ch1;
+#[cfg(feature = "ch2")]
+pub mod ch2;
+#[cfg(feature = "ch3")]
+pub mod ch3;
+#[cfg(feature = "ch4")]
+pub mod ch4;
+#[cfg(feature = "ch5")]
+pub mod ch5;
+#[cfg(feature = "ch6")]
+pub mod ch6;
+#[cfg(feature = "ch7")]
+pub mod ch7;
+#[cfg(feature = "ch8")]
+pub mod ch8;
+#[cfg(feature = "ch9")]
+pub mod ch9;
+#[cfg(feature = "ch10")]
+pub mod ch10;
+#[cfg(feature = "ch11")]
 pub mod ch11;
-// pub mod ch13;
+#[cfg(feature = "ch12")]
+pub mod ch12;
+#[cfg(feature = "ch13")]
+pub mod ch13;
+#[cfg(feature = "ch14")]
+pub mod ch14;
+#[cfg(feature = "ch15")]
 pub mod ch15;
+#[cfg(feature = "ch16")]
 pub mod ch16;
+#[cfg(feature = "ch17")]
+pub mod ch17;
+#[cfg(feature = "ch18")]
+pub mod ch18;
+#[cfg(feature = "ch19")]
+pub mod ch19;
+#[cfg(feature = "ch20")]
+pub mod ch20;
+pub mod annotate;
+pub mod cli;
+pub mod compare;
+pub mod exercise;
+pub mod export;
+pub mod graph;
+pub mod progress;
+pub mod quiz;
+pub mod registry;
+pub mod runner;
+pub mod schedule;
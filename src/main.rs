@@ -1,136 +1,424 @@
-#![allow(dead_code, unused_variables)]
-// pub mod ch1;
-// pub mod ch3;
-// pub mod ch4;
-// pub mod ch5;
-// pub mod ch6;
-// pub mod ch8;
-// pub mod ch9;
-// pub mod ch10;
-// pub mod ch11;
-// pub mod ch13;
-// pub mod ch15;
-// pub mod ch16;
-// pub mod ch17;
-pub mod ch19;
-fn main() {
-    // let ch1_main = ch1::main::Main {};
-    // ch1_main.print();
-
-    // let ch3_vm= ch3::var_mutability::VarMutability{};
-    // ch3_vm.print();
-
-    // let ch3_dt= ch3::data_types::DataTypes{};
-    // ch3_dt.print();
-
-    // let ch3_fn= ch3::functions::Functions{};
-    // ch3_fn.print();
-
-    // let ch3_cf= ch3::control_flow::ControlFlow{};
-    // ch3_cf.print();
-
-    // let ch4_os= ch4::ownership::Ownership{};
-    // ch4_os.print();
-
-    // let ch4_rb= ch4::reference_borrow::ReferenceBorrow{};
-    // ch4_rb.print();
-
-    // let ch4_foe= ch4::fix_ownership_error::FixOwnershipError{};
-    // ch4_foe.print();
-
-    // let ch4_st= ch4::slice_type::SliceType{};
-    // ch4_st.print();
-
-    // let ch5_di= ch5::define_instantiate::DefineInstantiate{};
-    // ch5_di.print();
-
-    // let ch5_ms= ch5::method::Method{};
-    // ch5_ms.print();
-
-    // let ch6_de = ch6::define::DefineEnum{};
-    // ch6_de.print();
-
-    // let ch6_mcf= ch6::match_control_flow::MatchControlFlow{};
-    // ch6_mcf.print();
-
-    // let ch8_vec= ch8::vectors::Vectors{};
-    // ch8_vec.print();
-
-    // let ch8_str= ch8::strings::Strings{};
-    // ch8_str.print();
-
-    // let ch8_hm= ch8::hashmaps::HashMaps{};
-    // ch8_hm.print();
-
-    // let ch9_pan= ch9::panics::Panics{};
-    // ch9_pan.print();
-
-    // let ch9_res= ch9::results::Results{};
-    // ch9_res.print();
-
-    // let ch10_gen= ch10::generics::Generics{};
-    // ch10_gen.print();
-
-    // let ch10_tr= ch10::traits::Traits{};
-    // ch10_tr.print();
-
-    // let ch10_lt= ch10::lifetimes::Lifetimes{};
-    // ch10_lt.print();
-
-    // let ch11_wt = ch11::writing_tests::WritingTests{};
-    // ch11_wt.print();
-
-    // let ch11_ct = ch11::controlling_tests::ControllingTests{};
-    // ch11_ct.print();
-
-    // let ch11_ot = ch11::organizing_tests::OrganizingTests{};
-    // ch11_ot.print();
-
-    // let ch13_cl = ch13::closures::Closures {};
-    // ch13_cl.print();
-
-    // let ch13_it = ch13::iterators::Iterators{};
-    // ch13_it.print();
-
-    // let ch15_pth = ch15::pointers_to_heap::PointersToHeap{};
-    // ch15_pth.print();
-
-    // let ch15_deref= ch15::dref_trait::DerefTrait{};
-    // ch15_deref.print();
-
-    // let ch15_drop= ch15::drop_trait::DropTrait{};
-    // ch15_drop.print();
+use rust_after_cpp::annotate::Annotated;
+use rust_after_cpp::cli::{self, Command, GuessOptions, RunOptions, ScheduleFile};
+use rust_after_cpp::exercise::{self, Verdict};
+use rust_after_cpp::lesson::Lesson;
+use rust_after_cpp::progress::{self, Progress, Run};
+use rust_after_cpp::runner::{self, Outcome};
+use rust_after_cpp::schedule::{self, Clock, FastForward, Mode, RealClock, Scheduler, Trace};
+use rust_after_cpp::{compare, export, graph, quiz, registry};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+/// The learner's progress file, or `None` with a warning when it cannot be found or read
+fn load_progress() -> Option<Progress> {
+    let Some(path) = progress::default_path() else {
+        eprintln!(
+            "No data directory to keep progress in, set RUST_AFTER_CPP_PROGRESS to a file path"
+        );
+        return None;
+    };
+    match Progress::load(&path) {
+        Ok(progress) => Some(progress),
+        Err(e) => {
+            eprintln!("Cannot read the progress file {}: {e}", path.display());
+            None
+        }
+    }
+}
 
-    // let ch15_rc= ch15::rc_pointers::RCPointers{};
-    //ch15_rc.print();
+/// The schedules saved by `run --record` in `path`, exiting when they cannot be read
+fn load_traces(path: &Path) -> Vec<(String, Trace)> {
+    let traces = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| schedule::parse_traces(&text));
+    match traces {
+        Ok(traces) => traces,
+        Err(e) => {
+            eprintln!("Cannot read the schedules in {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
 
-    // let ch15_rfc= ch15::refcell_pointers::RefCellPointers{};
-    // ch15_rfc.print();
+/// The scheduler to run `lesson`'s threads under, exiting when there is no schedule to replay for it
+fn scheduler_for(
+    lesson: &dyn Lesson,
+    options: &RunOptions,
+    replayed: &[(String, Trace)],
+) -> Arc<Scheduler> {
+    let clock: Arc<dyn Clock> = if options.fast_forward {
+        Arc::new(FastForward::new())
+    } else {
+        Arc::new(RealClock::new())
+    };
+    let mode = match &options.schedule {
+        None => Mode::Free,
+        Some(ScheduleFile::Record(_)) => Mode::Record,
+        Some(ScheduleFile::Replay(path)) => {
+            match replayed.iter().find(|(id, _)| id == lesson.id()) {
+                Some((_, trace)) => Mode::Replay(trace.clone()),
+                None => {
+                    eprintln!("No schedule of {} in {}", lesson.id(), path.display());
+                    process::exit(1);
+                }
+            }
+        }
+    };
+    Scheduler::new(mode, clock)
+}
 
-    // let ch16_thr = ch16::threads::Threads{};
-    // ch16_thr.print();
+/// Exits with 1 when the guesses ran out before the secret number was found
+#[cfg(feature = "ch2")]
+fn guess(options: GuessOptions) {
+    use rust_after_cpp::ch2::guessing_game::{self, Outcome, Rng};
+
+    let mut rng = match options.seed {
+        Some(seed) => Rng::new(seed),
+        None => Rng::from_time(),
+    };
+    let secret_number = rng.gen_range(1..=100);
+    let mut out = io::stdout().lock();
+    let outcome = match &options.script {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(script) => {
+                guessing_game::play(secret_number, &mut script.as_bytes(), &mut out, true)
+            }
+            Err(e) => {
+                eprintln!("Cannot read {}: {e}", path.display());
+                process::exit(1);
+            }
+        },
+        None => guessing_game::play(secret_number, &mut io::stdin().lock(), &mut out, false),
+    };
+    match outcome {
+        Ok(Outcome::Won(_)) => {}
+        Ok(Outcome::GaveUp) => process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to play: {e}");
+            process::exit(1);
+        }
+    }
+}
 
-    // let ch16_msg= ch16::messages::Messages{};
-    // ch16_msg.print();
+#[cfg(not(feature = "ch2"))]
+fn guess(_options: GuessOptions) {
+    eprintln!("The guessing game is part of chapter 2, build with `--features ch2`");
+    process::exit(2);
+}
 
-    // let ch16_mut= ch16::mutexes::Mutexes{};
-    // ch16_mut.print();
+/// Exits with 1 when no arm matches the value, 2 when the value or an arm does not parse
+#[cfg(feature = "ch18")]
+fn match_arms(value: String, arms: Vec<String>) {
+    use rust_after_cpp::ch18::playground;
+
+    let arms: Vec<&str> = arms.iter().map(String::as_str).collect();
+    match playground::evaluate(&value, &arms) {
+        Ok(report) => {
+            println!("{report}");
+            if report.matched().is_none() {
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(2);
+        }
+    }
+}
 
-    // let ch17_dt = ch17::dyn_traits::DynTraits{};
-    // ch17_dt.print();
+#[cfg(not(feature = "ch18"))]
+fn match_arms(_value: String, _arms: Vec<String>) {
+    eprintln!("The pattern playground is part of chapter 18, build with `--features ch18`");
+    process::exit(2);
+}
 
-    // let ch19_ur = ch19::unsafe_rust::UnsafeRust{};
-    // ch19_ur.print();
+/// `grep`'s exit codes: 0 when a line matched, 1 when none did, 2 on an error
+#[cfg(feature = "ch12")]
+fn minigrep(args: Vec<String>) {
+    use rust_after_cpp::ch12::minigrep::{self, Config};
+
+    let config = Config::build(args.into_iter()).unwrap_or_else(|err| {
+        // - errors go to stderr, so that `minigrep ... > output.txt` only holds the matches
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(2);
+    });
+    match minigrep::run(&config, &mut io::stdin().lock(), &mut io::stdout().lock()) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
 
-    // let ch19_at = ch19::advanced_traits::AdvancedTraits{};
-    // ch19_at.print();
+#[cfg(not(feature = "ch12"))]
+fn minigrep(_args: Vec<String>) {
+    eprintln!("minigrep is part of chapter 12, build with `--features ch12`");
+    process::exit(2);
+}
 
-    // let ch19_atp = ch19::advanced_types::AdvancedTypes{};
-    // ch19_atp.print();
+#[cfg(feature = "ch20")]
+fn serve(addr: String) {
+    use rust_after_cpp::ch20::web_server::Server;
+
+    let server = Server::bind(&addr, 4).unwrap_or_else(|e| {
+        eprintln!("Cannot listen on {addr}: {e}");
+        process::exit(1);
+    });
+    println!("Serving on http://{addr}, Ctrl-C to stop");
+    if let Err(e) = server.serve(None) {
+        eprintln!("Server error: {e}");
+        process::exit(1);
+    }
+}
 
-    // let ch19_afc = ch19::advanced_fn_closure::AdvancedFnClosure{};
-    // ch19_afc.print();
+#[cfg(not(feature = "ch20"))]
+fn serve(_addr: String) {
+    eprintln!("The web server is part of chapter 20, build with `--features ch20`");
+    process::exit(2);
+}
 
-    let ch19_mcr = ch19::macros::Macros {};
-    ch19_mcr.print();
+fn main() {
+    let command = match cli::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::List(tag) => {
+            let lessons = registry::lessons();
+            if let Some(tag) = &tag {
+                if !lessons
+                    .iter()
+                    .any(|lesson| lesson.tags().contains(&tag.as_str()))
+                {
+                    let mut tags: Vec<&str> =
+                        lessons.iter().flat_map(|l| l.tags()).copied().collect();
+                    tags.sort();
+                    tags.dedup();
+                    eprintln!(
+                        "No lesson is tagged `{tag}`, the tags are: {}",
+                        tags.join(", ")
+                    );
+                    process::exit(1);
+                }
+            }
+            for lesson in lessons {
+                if tag
+                    .as_ref()
+                    .is_some_and(|tag| !lesson.tags().contains(&tag.as_str()))
+                {
+                    continue;
+                }
+                println!(
+                    "{:<8} {:<28} {:<48} {}",
+                    lesson.section(),
+                    lesson.id(),
+                    lesson.title(),
+                    lesson.tags().join(", ")
+                );
+            }
+        }
+        Command::Graph { dot } => {
+            let lessons = registry::lessons();
+            let mut out = io::stdout().lock();
+            let result = if dot {
+                graph::write_dot(&lessons, &mut out)
+            } else {
+                graph::write_text(&lessons, &mut out)
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to write the graph: {e}");
+                process::exit(1);
+            }
+        }
+        Command::Run(selector, options) => match selector.select() {
+            Ok(lessons) => {
+                let mut progress = load_progress();
+                if let Some(progress) = &progress {
+                    let all = registry::lessons();
+                    let unmet =
+                        graph::unmet_prerequisites(&lessons, &all, |id| progress.completed(id));
+                    for (lesson, missing) in &unmet {
+                        for id in missing {
+                            let section = all.iter().find(|l| l.id() == *id).map(|l| l.section());
+                            eprintln!(
+                                "{} builds on {id}, not completed yet (`run {}`)",
+                                lesson.id(),
+                                section.map_or(id.to_string(), |s| s.to_string())
+                            );
+                        }
+                    }
+                    if options.strict && !unmet.is_empty() {
+                        eprintln!("Not running with --strict, complete the lessons above first");
+                        process::exit(2);
+                    }
+                }
+                let replayed = match &options.schedule {
+                    Some(ScheduleFile::Replay(path)) => load_traces(path),
+                    _ => Vec::new(),
+                };
+                let mut recorded: Vec<(&str, Trace)> = Vec::new();
+                let mut out = io::stdout().lock();
+                let mut results: Vec<(&dyn Lesson, Outcome)> = Vec::new();
+                for lesson in &lessons {
+                    let lesson = lesson.as_ref();
+                    let scheduler = scheduler_for(lesson, &options, &replayed);
+                    let (started, timer) = (SystemTime::now(), Instant::now());
+                    let result = scheduler.run(|| match options.annotation {
+                        Some(annotation) => runner::run_isolated(
+                            lesson,
+                            &mut Annotated::new(&mut out, annotation, lesson),
+                        ),
+                        None => runner::run_isolated(lesson, &mut out),
+                    });
+                    if let Some(divergence) = scheduler.divergence() {
+                        eprintln!("The replay of {} diverged: {divergence}", lesson.id());
+                    }
+                    if let Some(ScheduleFile::Record(_)) = options.schedule {
+                        recorded.push((lesson.id(), scheduler.trace()));
+                    }
+                    let result = match (result, options.compare) {
+                        (Ok(outcome), Some(language)) => {
+                            compare::write_panel(lesson, language, &mut out).map(|()| outcome)
+                        }
+                        (result, _) => result,
+                    };
+                    let completed = result.as_ref().is_ok_and(Outcome::is_ok);
+                    if let Some(progress) = &mut progress {
+                        let run = Run::new(lesson.id(), started, timer.elapsed(), completed);
+                        if let Err(e) = progress.record(run) {
+                            eprintln!("Cannot save progress to {}: {e}", progress.path().display());
+                        }
+                    }
+                    match result {
+                        Ok(outcome) => results.push((lesson, outcome)),
+                        Err(e) => {
+                            eprintln!("Failed to write the output of {}: {e}", lesson.id());
+                            process::exit(1);
+                        }
+                    }
+                }
+
+                if let Some(ScheduleFile::Record(path)) = &options.schedule {
+                    let saved = fs::File::create(path)
+                        .and_then(|mut file| schedule::write_traces(&recorded, &mut file));
+                    if let Err(e) = saved {
+                        eprintln!("Cannot save the schedules to {}: {e}", path.display());
+                        process::exit(1);
+                    }
+                }
+
+                let all_completed = results.iter().all(|(_, o)| *o == Outcome::Completed);
+                if results.len() > 1 || !all_completed {
+                    if let Err(e) = runner::write_summary(&results, &mut out) {
+                        eprintln!("Failed to write the summary: {e}");
+                        process::exit(1);
+                    }
+                }
+                if !results.iter().all(|(_, outcome)| outcome.is_ok()) {
+                    process::exit(1);
+                }
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        },
+        Command::Export(dir) => match export::export(&registry::lessons(), &dir) {
+            Ok(written) => println!("Wrote {} files to {}", written.len(), dir.display()),
+            Err(e) => {
+                eprintln!("Failed to export to {}: {e}", dir.display());
+                process::exit(1);
+            }
+        },
+        Command::Progress => {
+            if let Some(progress) = load_progress() {
+                let lessons = registry::lessons();
+                if let Err(e) = progress.write_summary(&lessons, &mut io::stdout().lock()) {
+                    eprintln!("Failed to write the progress summary: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Quiz(selector) => {
+            let lessons = selector.select().and_then(|lessons| {
+                let quizzed: Vec<_> = lessons
+                    .into_iter()
+                    .filter(|lesson| !lesson.quizzes().is_empty())
+                    .collect();
+                if quizzed.is_empty() {
+                    return Err(format!("No lesson matching {selector} has a quiz"));
+                }
+                Ok(quizzed)
+            });
+            match lessons {
+                Ok(lessons) => {
+                    let result =
+                        quiz::take(&lessons, &mut io::stdin().lock(), &mut io::stdout().lock());
+                    if let Err(e) = result {
+                        eprintln!("Quiz interrupted: {e}");
+                        process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Exercise(options) => {
+            let Some(dir) = options.dir.or_else(exercise::default_dir) else {
+                eprintln!("No data directory to keep the exercises in, pass `--dir <dir>`");
+                process::exit(1);
+            };
+            let lessons = registry::lessons();
+            let exercises: Vec<_> = exercise::exercises(&lessons)
+                .into_iter()
+                .filter(|(_, e)| options.name.as_ref().is_none_or(|name| e.name == name))
+                .collect();
+            if exercises.is_empty() {
+                let name = options.name.unwrap_or_default();
+                eprintln!("No exercise named `{name}`, see `exercise` for all of them");
+                process::exit(1);
+            }
+
+            let mut results = Vec::new();
+            for (lesson, e) in exercises {
+                let verdict = exercise::prepare(e, &dir, options.reset)
+                    .and_then(|root| exercise::check(&root));
+                match verdict {
+                    Ok(verdict) => results.push((lesson, e, verdict)),
+                    Err(err) => {
+                        eprintln!("Cannot check {} in {}: {err}", e.name, dir.display());
+                        process::exit(1);
+                    }
+                }
+            }
+            if let Err(e) = exercise::write_report(&results, &dir, &mut io::stdout().lock()) {
+                eprintln!("Failed to write the report: {e}");
+                process::exit(1);
+            }
+            if results
+                .iter()
+                .any(|(_, _, verdict)| *verdict != Verdict::Passed)
+            {
+                process::exit(1);
+            }
+        }
+        Command::Guess(options) => guess(options),
+        Command::Match { value, arms } => match_arms(value, arms),
+        Command::Minigrep(args) => minigrep(args),
+        Command::Serve(addr) => serve(addr),
+        Command::Help => println!("{}", cli::USAGE),
+    }
 }
@@ -0,0 +1,330 @@
+//! # Learner progress, kept across runs of the binary
+//!     * every `run` of a lesson appends one line to `progress.tsv` under the user's data directory
+//!         * `$XDG_DATA_HOME` or `~/.local/share` on Linux, `~/Library/Application Support` on macOS, `%APPDATA%` on Windows
+//!         * `RUST_AFTER_CPP_PROGRESS` overrides the whole file path, e.g. for a scratch run
+//!     * a line is `<unix seconds>\t<lesson id>\t<milliseconds>\t<completed|failed>`, so the file stays readable and diffable
+//!     * `progress` prints a summary per chapter and suggests the first lesson that has not been completed yet
+use crate::lesson::Lesson;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HEADER: &str = "# rust_after_cpp progress: started(unix s)\tlesson\tduration(ms)\toutcome";
+
+/// One run of one lesson
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub id: String,
+    /// Seconds since the Unix epoch
+    pub started: u64,
+    pub duration: Duration,
+    /// `false` when the lesson failed to write its output, or panicked without being marked `panics`
+    pub completed: bool,
+}
+
+impl Run {
+    pub fn new(id: &str, started: SystemTime, duration: Duration, completed: bool) -> Run {
+        Run {
+            id: id.to_string(),
+            started: started
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            duration,
+            completed,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.started,
+            self.id,
+            self.duration.as_millis(),
+            if self.completed {
+                "completed"
+            } else {
+                "failed"
+            }
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Run> {
+        let mut fields = line.split('\t');
+        let run = Run {
+            started: fields.next()?.parse().ok()?,
+            id: fields.next()?.to_string(),
+            duration: Duration::from_millis(fields.next()?.parse().ok()?),
+            completed: match fields.next()? {
+                "completed" => true,
+                "failed" => false,
+                _ => return None,
+            },
+        };
+        fields.next().is_none().then_some(run)
+    }
+}
+
+/// Directory kept for the learner's files, e.g. `~/.local/share/rust_after_cpp`, `None` when no home or data directory is known
+pub fn data_dir() -> Option<PathBuf> {
+    let data_dir = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library/Application Support")
+    } else {
+        match env::var_os("XDG_DATA_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+        }
+    };
+    Some(data_dir.join("rust_after_cpp"))
+}
+
+/// Default location of the progress file, `None` when no home or data directory is known
+pub fn default_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("RUST_AFTER_CPP_PROGRESS") {
+        return Some(PathBuf::from(path));
+    }
+    Some(data_dir()?.join("progress.tsv"))
+}
+
+/// All recorded runs, oldest first, backed by a file
+#[derive(Debug)]
+pub struct Progress {
+    path: PathBuf,
+    pub runs: Vec<Run>,
+}
+
+impl Progress {
+    /// Read the runs recorded in `path`, a missing file has none
+    ///     * lines that cannot be parsed, e.g. from a newer version, are skipped
+    pub fn load(path: &Path) -> io::Result<Progress> {
+        let runs = match fs::read_to_string(path) {
+            Ok(text) => text
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter_map(Run::from_line)
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Progress {
+            path: path.to_path_buf(),
+            runs,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `run` to the file, creating it and its directory when needed
+    pub fn record(&mut self, run: Run) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{HEADER}")?;
+        }
+        writeln!(file, "{}", run.to_line())?;
+        self.runs.push(run);
+        Ok(())
+    }
+
+    /// Whether a run of the lesson `id` has completed
+    pub fn completed(&self, id: &str) -> bool {
+        self.runs.iter().any(|run| run.id == id && run.completed)
+    }
+
+    /// First lesson, in the order given, that has never been completed
+    pub fn next_unvisited<'a>(&self, lessons: &'a [Box<dyn Lesson>]) -> Option<&'a dyn Lesson> {
+        lessons
+            .iter()
+            .map(|lesson| lesson.as_ref())
+            .find(|lesson| !self.completed(lesson.id()))
+    }
+
+    /// Per chapter: lessons completed, time spent and the last run, then the suggested next lesson
+    pub fn write_summary(
+        &self,
+        lessons: &[Box<dyn Lesson>],
+        out: &mut dyn Write,
+    ) -> io::Result<()> {
+        let mut chapters: Vec<u32> = lessons.iter().map(|l| l.section().chapter).collect();
+        chapters.dedup();
+
+        for chapter in chapters {
+            let ids: Vec<&str> = lessons
+                .iter()
+                .filter(|l| l.section().chapter == chapter)
+                .map(|l| l.id())
+                .collect();
+            let runs: Vec<&Run> = self
+                .runs
+                .iter()
+                .filter(|run| ids.contains(&run.id.as_str()))
+                .collect();
+            let completed = ids.iter().filter(|id| self.completed(id)).count();
+            let spent: Duration = runs.iter().map(|run| run.duration).sum();
+            let last_run = match runs.iter().map(|run| run.started).max() {
+                Some(started) => format!("last run {}", format_timestamp(started)),
+                None => String::from("not started"),
+            };
+            writeln!(
+                out,
+                "{:<6} {:>2}/{:<2} completed  {:>8} spent  {last_run}",
+                format!("ch{chapter}"),
+                completed,
+                ids.len(),
+                format_duration(spent)
+            )?;
+        }
+
+        match self.next_unvisited(lessons) {
+            Some(lesson) => writeln!(
+                out,
+                "\nNext: {} {} - {} (`run {}`)",
+                lesson.section(),
+                lesson.id(),
+                lesson.title(),
+                lesson.section()
+            ),
+            None => writeln!(out, "\nEvery lesson has been completed"),
+        }
+    }
+}
+
+/// e.g. `1m05s` or `850ms`
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0 => format!("{}ms", duration.as_millis()),
+        1..=59 => format!("{secs}s"),
+        _ => format!("{}m{:02}s", secs / 60, secs % 60),
+    }
+}
+
+/// Unix seconds as `YYYY-MM-DD HH:MM UTC`
+fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let (hour, minute) = (secs % 86_400 / 3600, secs % 3600 / 60);
+    // - civil-from-days, counting eras of 400 years from 0000-03-01
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02} UTC")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::{Section, TestLesson};
+
+    fn note(id: &'static str, section: Section) -> Box<dyn Lesson> {
+        Box::new(TestLesson {
+            section,
+            ..TestLesson::new(id)
+        })
+    }
+
+    fn notes() -> Vec<Box<dyn Lesson>> {
+        vec![
+            note("ch3::a", Section::new(3, 1)),
+            note("ch3::b", Section::new(3, 2)),
+            note("ch4::c", Section::new(4, 1)),
+        ]
+    }
+
+    fn run(id: &str, started: u64, completed: bool) -> Run {
+        Run {
+            id: id.to_string(),
+            started,
+            duration: Duration::from_millis(1500),
+            completed,
+        }
+    }
+
+    fn run_ms(id: &str, started: u64, ms: u64) -> Run {
+        Run {
+            duration: Duration::from_millis(ms),
+            ..run(id, started, true)
+        }
+    }
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let path = env::temp_dir()
+            .join(format!("rust_after_cpp-{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn records_and_loads_runs() {
+        let path = scratch_file("records_and_loads_runs.tsv");
+        let mut progress = Progress::load(&path).unwrap();
+        assert!(progress.runs.is_empty());
+        progress.record(run("ch3::a", 1_700_000_000, true)).unwrap();
+        progress
+            .record(run("ch3::b", 1_700_000_100, false))
+            .unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("# "));
+        assert!(text.ends_with("1700000100\tch3::b\t1500\tfailed\n"));
+        assert_eq!(Progress::load(&path).unwrap().runs, progress.runs);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn skips_unreadable_lines() {
+        assert_eq!(
+            Run::from_line("1\tch3::a\t2\tcompleted"),
+            Some(run_ms("ch3::a", 1, 2))
+        );
+        assert_eq!(Run::from_line("1\tch3::a\t2\tmaybe"), None);
+        assert_eq!(Run::from_line("1\tch3::a\t2\tcompleted\textra"), None);
+        assert_eq!(Run::from_line("garbage"), None);
+    }
+
+    #[test]
+    fn summarizes_chapters_and_suggests_next_lesson() {
+        let notes = notes();
+        let progress = Progress {
+            path: PathBuf::new(),
+            runs: vec![run("ch3::a", 0, true), run("ch3::b", 86_400 + 61, false)],
+        };
+        assert_eq!(progress.next_unvisited(&notes).unwrap().id(), "ch3::b");
+
+        let mut out = Vec::new();
+        progress.write_summary(&notes, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "ch3     1/2  completed        3s spent  last run 1970-01-02 00:01 UTC\n\
+             ch4     0/1  completed       0ms spent  not started\n\
+             \n\
+             Next: ch3.2 ch3::b - A note (`run ch3.2`)\n"
+        );
+    }
+
+    #[test]
+    fn formats_timestamps_and_durations() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_duration(Duration::from_millis(850)), "850ms");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m05s");
+    }
+}
@@ -0,0 +1,192 @@
+//! # Quizzes attached to lessons
+//!     * several notes end with a "Quiz" comment, e.g. the `n` counter in `ch16::threads`
+//!     * a lesson exposes them through `Lesson::quizzes`, and `take` asks them one by one on an input stream
+//!     * a wrong answer is followed by the right one and the explanation from the note
+use crate::lesson::Lesson;
+use std::io::{self, BufRead, Write};
+
+/// A multiple choice question about a snippet of code
+#[derive(Debug)]
+pub struct Quiz {
+    pub question: &'static str,
+    /// Code the question is about, may be empty
+    pub snippet: &'static str,
+    pub choices: &'static [&'static str],
+    /// Index into `choices`
+    pub answer: usize,
+    pub explanation: &'static str,
+}
+
+impl Quiz {
+    /// Label of the choice at `index`, i.e. `a`, `b`, ...
+    fn label(index: usize) -> char {
+        (b'a' + index as u8) as char
+    }
+
+    /// Read an answer such as `b`, `B` or `2` into an index into `choices`
+    pub fn parse_answer(&self, answer: &str) -> Option<usize> {
+        let answer = answer.trim().to_lowercase();
+        let index = match answer.parse::<usize>() {
+            Ok(n) => n.checked_sub(1)?,
+            Err(_) => {
+                let mut chars = answer.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c @ 'a'..='z'), None) => c as usize - 'a' as usize,
+                    _ => return None,
+                }
+            }
+        };
+        (index < self.choices.len()).then_some(index)
+    }
+
+    fn write_question(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.question)?;
+        if !self.snippet.is_empty() {
+            writeln!(out)?;
+            for line in self.snippet.lines() {
+                writeln!(out, "    {line}")?;
+            }
+        }
+        writeln!(out)?;
+        for (i, choice) in self.choices.iter().enumerate() {
+            writeln!(out, "  {}) {choice}", Quiz::label(i))?;
+        }
+        Ok(())
+    }
+}
+
+/// How many questions were answered, and how many of them correctly
+#[derive(Debug, Default, PartialEq)]
+pub struct Score {
+    pub answered: usize,
+    pub correct: usize,
+}
+
+/// Ask every quiz of `lessons` in turn, reading answers line by line from `input`
+///     * an answer that is not one of the choices is asked again
+///     * the end of `input` stops the quiz early, the score covers the questions answered so far
+pub fn take(
+    lessons: &[Box<dyn Lesson>],
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> io::Result<Score> {
+    let quizzes: Vec<(&str, &Quiz)> = lessons
+        .iter()
+        .flat_map(|lesson| lesson.quizzes().iter().map(|quiz| (lesson.id(), quiz)))
+        .collect();
+    let mut score = Score::default();
+
+    'quizzes: for (n, (id, quiz)) in quizzes.iter().enumerate() {
+        writeln!(out, "\nQuestion {}/{} ({id})", n + 1, quizzes.len())?;
+        quiz.write_question(out)?;
+
+        let index = loop {
+            write!(out, "Your answer: ")?;
+            out.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                break 'quizzes;
+            }
+            match quiz.parse_answer(&line) {
+                Some(index) => break index,
+                None => writeln!(
+                    out,
+                    "Please answer with a letter from a to {}",
+                    Quiz::label(quiz.choices.len() - 1)
+                )?,
+            }
+        };
+
+        score.answered += 1;
+        if index == quiz.answer {
+            score.correct += 1;
+            writeln!(out, "Correct!")?;
+        } else {
+            writeln!(
+                out,
+                "Not quite, the answer is {}) {}",
+                Quiz::label(quiz.answer),
+                quiz.choices[quiz.answer]
+            )?;
+            writeln!(out, "{}", quiz.explanation)?;
+        }
+    }
+
+    writeln!(out, "\nScore: {}/{}", score.correct, score.answered)?;
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::TestLesson;
+
+    const QUIZZES: &[Quiz] = &[
+        Quiz {
+            question: "What is 1 + 1?",
+            snippet: "let x = 1 + 1;",
+            choices: &["1", "2", "3"],
+            answer: 1,
+            explanation: "Integer addition.",
+        },
+        Quiz {
+            question: "Is `String` `Copy`?",
+            snippet: "",
+            choices: &["yes", "no"],
+            answer: 1,
+            explanation: "A `String` owns heap memory, so it is moved.",
+        },
+    ];
+
+    fn take_with(input: &str) -> (Score, String) {
+        let lessons: Vec<Box<dyn Lesson>> = vec![Box::new(TestLesson {
+            quizzes: QUIZZES,
+            ..TestLesson::new("ch0::quizzed")
+        })];
+        let mut out = Vec::new();
+        let score = take(&lessons, &mut input.as_bytes(), &mut out).unwrap();
+        (score, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn parses_letters_and_numbers() {
+        let quiz = &QUIZZES[0];
+        assert_eq!(quiz.parse_answer("b\n"), Some(1));
+        assert_eq!(quiz.parse_answer(" C "), Some(2));
+        assert_eq!(quiz.parse_answer("1"), Some(0));
+        assert_eq!(quiz.parse_answer("d"), None);
+        assert_eq!(quiz.parse_answer("0"), None);
+        assert_eq!(quiz.parse_answer("ab"), None);
+    }
+
+    #[test]
+    fn scores_and_explains_mistakes() {
+        let (score, out) = take_with("b\na\n");
+        assert_eq!(
+            score,
+            Score {
+                answered: 2,
+                correct: 1
+            }
+        );
+        assert!(out.contains("Question 1/2 (ch0::quizzed)\nWhat is 1 + 1?\n\n    let x = 1 + 1;\n"));
+        assert!(out.contains("Not quite, the answer is b) no\nA `String` owns heap memory"));
+        assert!(!out.contains("Integer addition."));
+        assert!(out.ends_with("Score: 1/2\n"));
+    }
+
+    #[test]
+    fn asks_again_and_stops_at_end_of_input() {
+        let (score, out) = take_with("x\nb\n");
+        assert_eq!(
+            score,
+            Score {
+                answered: 1,
+                correct: 1
+            }
+        );
+        assert!(out.contains("Please answer with a letter from a to c"));
+        assert!(out.ends_with("Score: 1/1\n"));
+    }
+}
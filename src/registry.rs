@@ -0,0 +1,107 @@
+//! # Registry of every lesson in book order
+//!     * each chapter's `mod.rs` lists its own lessons, `lessons()` gathers the chapters enabled as cargo features
+use crate::lesson::{Lesson, Section};
+
+/// All lessons of the enabled chapter features, sorted by book section
+pub fn lessons() -> Vec<Box<dyn Lesson>> {
+    let mut lessons: Vec<Box<dyn Lesson>> = Vec::new();
+    #[cfg(feature = "ch1")]
+    lessons.extend(crate::ch1::lessons());
+    #[cfg(feature = "ch2")]
+    lessons.extend(crate::ch2::lessons());
+    #[cfg(feature = "ch3")]
+    lessons.extend(crate::ch3::lessons());
+    #[cfg(feature = "ch4")]
+    lessons.extend(crate::ch4::lessons());
+    #[cfg(feature = "ch5")]
+    lessons.extend(crate::ch5::lessons());
+    #[cfg(feature = "ch6")]
+    lessons.extend(crate::ch6::lessons());
+    #[cfg(feature = "ch7")]
+    lessons.extend(crate::ch7::lessons());
+    #[cfg(feature = "ch8")]
+    lessons.extend(crate::ch8::lessons());
+    #[cfg(feature = "ch9")]
+    lessons.extend(crate::ch9::lessons());
+    #[cfg(feature = "ch10")]
+    lessons.extend(crate::ch10::lessons());
+    #[cfg(feature = "ch11")]
+    lessons.extend(crate::ch11::lessons());
+    #[cfg(feature = "ch12")]
+    lessons.extend(crate::ch12::lessons());
+    #[cfg(feature = "ch13")]
+    lessons.extend(crate::ch13::lessons());
+    #[cfg(feature = "ch14")]
+    lessons.extend(crate::ch14::lessons());
+    #[cfg(feature = "ch15")]
+    lessons.extend(crate::ch15::lessons());
+    #[cfg(feature = "ch16")]
+    lessons.extend(crate::ch16::lessons());
+    #[cfg(feature = "ch17")]
+    lessons.extend(crate::ch17::lessons());
+    #[cfg(feature = "ch18")]
+    lessons.extend(crate::ch18::lessons());
+    #[cfg(feature = "ch19")]
+    lessons.extend(crate::ch19::lessons());
+    #[cfg(feature = "ch20")]
+    lessons.extend(crate::ch20::lessons());
+    lessons.sort_by_key(|lesson| lesson.section());
+    lessons
+}
+
+/// Look a lesson up by its id, e.g. `ch15::rc_pointers`, or by its section, e.g. `ch15.4`
+pub fn find(key: &str) -> Option<Box<dyn Lesson>> {
+    let section = Section::parse(key);
+    lessons()
+        .into_iter()
+        .find(|lesson| lesson.id() == key || Some(lesson.section()) == section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lessons_are_in_book_order_and_unique() {
+        let lessons = lessons();
+        for pair in lessons.windows(2) {
+            assert!(pair[0].section() < pair[1].section());
+            assert_ne!(pair[0].id(), pair[1].id());
+        }
+    }
+
+    #[test]
+    fn lessons_carry_their_own_source() {
+        for lesson in lessons() {
+            let id = lesson.id();
+            assert!(
+                lesson.source().contains(&format!("\"{id}\"")),
+                "{id} has the source of another note"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ch15")]
+    fn finds_by_id_or_section() {
+        assert_eq!(
+            find("ch15::rc_pointers").unwrap().section(),
+            Section::new(15, 4)
+        );
+        assert_eq!(find("ch15.4").unwrap().id(), "ch15::rc_pointers");
+        assert!(find("ch99.1").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "ch15")]
+    fn lesson_output_can_be_captured() {
+        let mut out = Vec::new();
+        find("ch15::drop_trait").unwrap().run(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with(
+            "CustomSmartPointer dropped before the end of main.\n\
+             Dropping CustomSmartPointer with data `stuff e`!\n\
+             Dropping CustomSmartPointer with data `stuff d`!\n"
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+//! # Running lessons in isolation
+//!     * a panicking lesson, e.g. `ch9::results` on purpose, must not stop `run --all` from running the rest
+//!     * `run_isolated` runs a lesson under `catch_unwind`, while a panic hook records the panic's message and location
+//!         * instead of printing them to stderr, so they can be reported with the lesson
+//!     * `write_summary` prints how each lesson ended as a table
+use crate::lesson::Lesson;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
+
+/// What a caught panic said, and where
+#[derive(Debug, Clone, PartialEq)]
+pub struct Panic {
+    pub message: String,
+    /// `file:line` of the `panic!`, `expect`, ...
+    pub location: Option<String>,
+}
+
+impl Panic {
+    fn from_hook(info: &PanicHookInfo) -> Panic {
+        Panic {
+            message: message_of(info.payload()),
+            location: info
+                .location()
+                .map(|location| format!("{}:{}", location.file(), location.line())),
+        }
+    }
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // - messages such as `ch9::results`' start with a newline, only their text matters here
+        write!(f, "{}", self.message.trim())?;
+        match &self.location {
+            Some(location) => write!(f, " at {location}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The text of a panic payload, i.e. of `panic!("...")` or `expect("...")`
+fn message_of(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| String::from("Box<dyn Any>")),
+    }
+}
+
+/// How a lesson run ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Completed,
+    /// `expected` when the lesson is known to panic, see `Lesson::panics`
+    Panicked {
+        panic: Panic,
+        expected: bool,
+    },
+}
+
+impl Outcome {
+    /// Whether the lesson ended as it should, i.e. did not panic by surprise
+    pub fn is_ok(&self) -> bool {
+        !matches!(
+            self,
+            Outcome::Panicked {
+                expected: false,
+                ..
+            }
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Completed => "completed",
+            Outcome::Panicked { expected: true, .. } => "panicked (expected)",
+            Outcome::Panicked {
+                expected: false, ..
+            } => "failed",
+        })
+    }
+}
+
+thread_local! {
+    /// Set while this thread runs a lesson under `run_isolated`
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static CAUGHT: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Record the panics of isolated lessons, and leave every other panic to the previous hook
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.with(Cell::get) {
+                CAUGHT.with(|caught| *caught.borrow_mut() = Some(Panic::from_hook(info)));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Run `lesson`, catching a panic instead of unwinding out of the caller
+///     * an error writing the output is still returned as `Err`, there is no point going on without an output
+///     * a panic on a thread the lesson spawns is reported by the default hook, and caught once the lesson joins it
+pub fn run_isolated(lesson: &dyn Lesson, out: &mut dyn Write) -> io::Result<Outcome> {
+    install_hook();
+    ISOLATED.with(|isolated| isolated.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| lesson.run(out)));
+    ISOLATED.with(|isolated| isolated.set(false));
+
+    match result {
+        Ok(result) => result.map(|()| Outcome::Completed),
+        Err(payload) => {
+            let panic = CAUGHT.with(|caught| caught.borrow_mut().take());
+            Ok(Outcome::Panicked {
+                panic: panic.unwrap_or_else(|| Panic {
+                    message: message_of(payload.as_ref()),
+                    location: None,
+                }),
+                expected: lesson.panics(),
+            })
+        }
+    }
+}
+
+/// One row per lesson: section, id, outcome and, for a panic, its message and location
+pub fn write_summary(results: &[(&dyn Lesson, Outcome)], out: &mut dyn Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "\n{:<8} {:<28} {:<20} Details",
+        "Section", "Lesson", "Outcome"
+    )?;
+    for (lesson, outcome) in results {
+        let details = match outcome {
+            Outcome::Completed => String::new(),
+            Outcome::Panicked { panic, .. } => panic.to_string(),
+        };
+        let row = format!(
+            "{:<8} {:<28} {:<20} {}",
+            lesson.section(),
+            lesson.id(),
+            outcome,
+            details
+        );
+        writeln!(out, "{}", row.trim_end())?;
+    }
+    let failed = results
+        .iter()
+        .filter(|(_, outcome)| !outcome.is_ok())
+        .count();
+    writeln!(out, "\n{} lessons run, {} failed", results.len(), failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::TestLesson;
+
+    fn panicky(expected: bool) -> TestLesson {
+        TestLesson {
+            run: |out| {
+                writeln!(out, "before")?;
+                let v: Vec<i32> = Vec::new();
+                let _ = v.first().expect("no first element");
+                writeln!(out, "after")
+            },
+            panics: expected,
+            ..TestLesson::new("ch0::panicky")
+        }
+    }
+
+    #[test]
+    fn catches_the_panic_message_and_location() {
+        let mut out = Vec::new();
+        let outcome = run_isolated(&panicky(true), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "before\n");
+        match outcome {
+            Outcome::Panicked { panic, expected } => {
+                assert!(expected);
+                assert_eq!(panic.message, "no first element");
+                assert!(panic.location.unwrap().starts_with(file!()));
+            }
+            Outcome::Completed => panic!("the lesson should have panicked"),
+        }
+    }
+
+    #[test]
+    fn summarizes_outcomes() {
+        let expected = panicky(true);
+        let surprise = panicky(false);
+        let results: Vec<(&dyn Lesson, Outcome)> = vec![
+            (&expected, run_isolated(&expected, &mut io::sink()).unwrap()),
+            (&surprise, run_isolated(&surprise, &mut io::sink()).unwrap()),
+        ];
+        assert!(results[0].1.is_ok());
+        assert!(!results[1].1.is_ok());
+
+        let mut out = Vec::new();
+        write_summary(&results, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("ch0.1    ch0::panicky                 panicked (expected)  no first element at src/runner.rs:"));
+        assert!(out.contains("ch0::panicky                 failed               no first element"));
+        assert!(out.ends_with("\n2 lessons run, 1 failed\n"));
+    }
+}
@@ -0,0 +1,427 @@
+//! # Deterministic schedules for the thread demos
+//!     * `ch16::threads` and `ch16::messages` interleave their threads differently on every run, and sleep for seconds
+//!     * the demos spawn, sleep, join and receive through this module instead of `std::thread` and `std::sync::mpsc`
+//!         * with no `Scheduler` running these are the `std` functions, so the notes behave as in the book
+//!     * a `Scheduler` lets one of its threads run at a time, handing the turn over where a thread sleeps or blocks
+//!         * `Mode::Record` gives the turn to whichever thread asks first, and keeps the order as a `Trace`
+//!         * `Mode::Replay` gives the turns in the order of a recorded `Trace`, so the output is the same on every run
+//!     * sleeps go through a `Clock`, and `FastForward` only advances a virtual time, so a demo of seconds runs at once
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::mpsc::{self, RecvError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Where the time of a schedule comes from
+pub trait Clock: Send + Sync {
+    /// Time elapsed since the clock started
+    fn now(&self) -> Duration;
+
+    fn sleep(&self, duration: Duration);
+}
+
+/// The wall clock, sleeping for real
+pub struct RealClock {
+    start: Instant,
+}
+
+impl RealClock {
+    pub fn new() -> RealClock {
+        RealClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        RealClock::new()
+    }
+}
+
+impl Clock for RealClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// A virtual clock that a sleep moves forward at once
+#[derive(Default)]
+pub struct FastForward {
+    now: Mutex<Duration>,
+}
+
+impl FastForward {
+    pub fn new() -> FastForward {
+        FastForward::default()
+    }
+}
+
+impl Clock for FastForward {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+/// The threads in the order they took their turns, `main` then `thread-1`, `thread-2`, ... in the order they were spawned
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace(pub Vec<String>);
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join(" "))
+    }
+}
+
+/// Schedules of several lessons, as saved by `run --record`
+///     * each lesson's turns follow a `# <id>` line, one thread name per line
+pub fn write_traces(traces: &[(&str, Trace)], out: &mut dyn Write) -> io::Result<()> {
+    for (id, trace) in traces {
+        writeln!(out, "# {id}")?;
+        for turn in &trace.0 {
+            writeln!(out, "{turn}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Read what `write_traces` wrote
+pub fn parse_traces(text: &str) -> Result<Vec<(String, Trace)>, String> {
+    let mut traces: Vec<(String, Trace)> = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if let Some(id) = line.strip_prefix("# ") {
+            traces.push((id.trim().to_string(), Trace::default()));
+        } else if !line.is_empty() {
+            match traces.last_mut() {
+                Some((_, trace)) => trace.0.push(line.to_string()),
+                None => {
+                    return Err(format!(
+                        "line {}: a turn before any `# <lesson>` line",
+                        n + 1
+                    ))
+                }
+            }
+        }
+    }
+    Ok(traces)
+}
+
+/// How a `Scheduler` picks the thread to run
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    /// Threads run as they please, only sleeping through the clock
+    Free,
+    Record,
+    Replay(Trace),
+}
+
+/// A replayed thread waiting this long, in real time, for a turn that does not come means the trace does not fit the code
+const DIVERGENCE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hands one turn at a time to the threads of a lesson, see the module docs
+pub struct Scheduler {
+    mode: Mode,
+    clock: Arc<dyn Clock>,
+    state: Mutex<State>,
+    turn_over: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    running: Option<String>,
+    turns: Vec<String>,
+    spawned: usize,
+    /// Set once the lesson returns, from then on every thread runs freely
+    closed: bool,
+    divergence: Option<String>,
+}
+
+thread_local! {
+    /// The scheduler this thread runs under, and the thread's name in it
+    static CURRENT: RefCell<Option<(Arc<Scheduler>, String)>> = const { RefCell::new(None) };
+}
+
+fn current() -> Option<(Arc<Scheduler>, String)> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+impl Scheduler {
+    pub fn new(mode: Mode, clock: Arc<dyn Clock>) -> Arc<Scheduler> {
+        Arc::new(Scheduler {
+            mode,
+            clock,
+            state: Mutex::new(State::default()),
+            turn_over: Condvar::new(),
+        })
+    }
+
+    /// Run `f` on this thread as `main`, the threads it spawns through this module being scheduled too
+    pub fn run<R>(self: &Arc<Self>, f: impl FnOnce() -> R) -> R {
+        let _turn = Turn::begin(Arc::clone(self), String::from("main"), true);
+        f()
+    }
+
+    /// The turns taken so far
+    pub fn trace(&self) -> Trace {
+        Trace(self.state.lock().unwrap().turns.clone())
+    }
+
+    /// Why a replay gave up following its trace, if it did
+    pub fn divergence(&self) -> Option<String> {
+        self.state.lock().unwrap().divergence.clone()
+    }
+
+    /// Wait for the turn of `name`
+    fn acquire(&self, name: &str) {
+        if self.mode == Mode::Free {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.closed {
+                return;
+            }
+            let next = match &self.mode {
+                Mode::Replay(trace) => trace.0.get(state.turns.len()).map(String::as_str),
+                _ => None,
+            };
+            if state.running.is_none() && next.is_none_or(|next| next == name) {
+                state.running = Some(name.to_string());
+                state.turns.push(name.to_string());
+                return;
+            }
+            let turns = state.turns.len();
+            let (guard, timeout) = self
+                .turn_over
+                .wait_timeout(state, DIVERGENCE_TIMEOUT)
+                .unwrap();
+            state = guard;
+            let replaying = matches!(self.mode, Mode::Replay(_));
+            if replaying && timeout.timed_out() && state.turns.len() == turns && !state.closed {
+                // - let every thread run freely rather than hang
+                state.divergence = Some(format!(
+                    "turn {} should go to `{}`, which never asked for it",
+                    turns + 1,
+                    next.unwrap_or("?")
+                ));
+                state.closed = true;
+                self.turn_over.notify_all();
+            }
+        }
+    }
+
+    fn release(&self) {
+        if self.mode == Mode::Free {
+            return;
+        }
+        self.state.lock().unwrap().running = None;
+        self.turn_over.notify_all();
+    }
+
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.turn_over.notify_all();
+    }
+
+    fn spawned_name(&self) -> String {
+        let mut state = self.state.lock().unwrap();
+        state.spawned += 1;
+        format!("thread-{}", state.spawned)
+    }
+}
+
+/// A thread's membership of a scheduler, giving up its turn when the thread is done, even by panicking
+struct Turn {
+    /// Whether this is `main`, whose end lets every other thread run freely
+    closes: bool,
+}
+
+impl Turn {
+    fn begin(scheduler: Arc<Scheduler>, name: String, closes: bool) -> Turn {
+        scheduler.acquire(&name);
+        CURRENT.with(|current| *current.borrow_mut() = Some((scheduler, name)));
+        Turn { closes }
+    }
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        if let Some((scheduler, _)) = CURRENT.with(|current| current.borrow_mut().take()) {
+            if self.closes {
+                scheduler.close();
+            } else {
+                scheduler.release();
+            }
+        }
+    }
+}
+
+/// Run `f`, which may block on other threads, without holding the turn
+fn blocking<R>(f: impl FnOnce() -> R) -> R {
+    match current() {
+        Some((scheduler, name)) => {
+            scheduler.release();
+            let result = f();
+            scheduler.acquire(&name);
+            result
+        }
+        None => f(),
+    }
+}
+
+/// `thread::spawn`, the new thread taking turns with the others under a scheduler
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let Some((scheduler, _)) = current() else {
+        return JoinHandle(thread::spawn(f));
+    };
+    // - named here, on the spawning thread's turn, so that the names are the same on every run
+    let name = scheduler.spawned_name();
+    JoinHandle(thread::spawn(move || {
+        let _turn = Turn::begin(scheduler, name, false);
+        f()
+    }))
+}
+
+/// `thread::sleep` on the scheduler's clock, letting another thread take a turn
+pub fn sleep(duration: Duration) {
+    match current() {
+        Some((scheduler, _)) => blocking(|| scheduler.clock.sleep(duration)),
+        None => thread::sleep(duration),
+    }
+}
+
+/// `thread::JoinHandle` whose `join` lets the joined thread take its turns
+pub struct JoinHandle<T>(thread::JoinHandle<T>);
+
+impl<T> JoinHandle<T> {
+    pub fn join(self) -> thread::Result<T> {
+        blocking(|| self.0.join())
+    }
+}
+
+/// `mpsc::channel` whose receiving end lets the senders take their turns
+pub fn channel<T>() -> (mpsc::Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    (tx, Receiver(rx))
+}
+
+pub struct Receiver<T>(mpsc::Receiver<T>);
+
+impl<T> Receiver<T> {
+    pub fn recv(&self) -> Result<T, RecvError> {
+        blocking(|| self.0.recv())
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+/// The values received until every sender is dropped, as `for received in rx` in the notes
+pub struct IntoIter<T>(Receiver<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two threads appending to a log, sleeping between entries
+    fn ping_pong(scheduler: &Arc<Scheduler>) -> Vec<String> {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        scheduler.run(|| {
+            let thread_log = Arc::clone(&log);
+            let handle = spawn(move || {
+                for i in 0..3 {
+                    thread_log.lock().unwrap().push(format!("pong {i}"));
+                    sleep(Duration::from_millis(1));
+                }
+            });
+            for i in 0..3 {
+                log.lock().unwrap().push(format!("ping {i}"));
+                sleep(Duration::from_millis(1));
+            }
+            handle.join().unwrap();
+        });
+        let log = log.lock().unwrap().clone();
+        log
+    }
+
+    #[test]
+    fn replays_a_recorded_interleaving() {
+        let recorder = Scheduler::new(Mode::Record, Arc::new(FastForward::new()));
+        let recorded = ping_pong(&recorder);
+        let trace = recorder.trace();
+        assert_eq!(trace.0[0], "main");
+
+        for _ in 0..5 {
+            let replayer =
+                Scheduler::new(Mode::Replay(trace.clone()), Arc::new(FastForward::new()));
+            assert_eq!(ping_pong(&replayer), recorded);
+            assert_eq!(replayer.trace(), trace);
+            assert_eq!(replayer.divergence(), None);
+        }
+    }
+
+    #[test]
+    fn replays_a_given_order() {
+        // - `main` holds the turn until it sleeps, and only the thread takes turns after `main` joins it
+        let turns = "main main main thread-1 main thread-1 thread-1 thread-1 main";
+        let trace = Trace(turns.split(' ').map(String::from).collect());
+        let scheduler = Scheduler::new(Mode::Replay(trace), Arc::new(FastForward::new()));
+        assert_eq!(
+            ping_pong(&scheduler),
+            ["ping 0", "ping 1", "ping 2", "pong 0", "pong 1", "pong 2"]
+        );
+    }
+
+    #[test]
+    fn fast_forward_does_not_sleep() {
+        let clock = Arc::new(FastForward::new());
+        let scheduler = Scheduler::new(Mode::Free, clock.clone());
+        let timer = Instant::now();
+        scheduler.run(|| sleep(Duration::from_secs(60)));
+        assert!(timer.elapsed() < Duration::from_secs(1));
+        assert_eq!(clock.now(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn writes_and_parses_traces() {
+        let trace = Trace(vec![String::from("main"), String::from("thread-1")]);
+        let mut out = Vec::new();
+        write_traces(&[("ch16::threads", trace.clone())], &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "# ch16::threads\nmain\nthread-1\n");
+        assert_eq!(
+            parse_traces(&text),
+            Ok(vec![(String::from("ch16::threads"), trace)])
+        );
+        assert!(parse_traces("main\n").is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! Compile-fail cases: the snippets the notes keep commented out because the compiler rejects them
+//! * each `tests/compile_fail/<chapter>_<name>.rs` is compiled with `rustc` into a temporary directory
+//! * its `// expect: E0502` line names the error code the compiler must report
+//! * `RUSTC` selects the compiler, `rustc` from `PATH` otherwise
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn cases_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("compile_fail")
+}
+
+/// Error code from the case's `// expect: E....` line
+fn expected_code(source: &str) -> Option<&str> {
+    source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("// expect:"))
+        .map(str::trim)
+}
+
+/// `rustc`'s diagnostics for `case`, or `None` when it compiled
+fn compile(case: &Path, out_dir: &Path) -> Option<String> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc)
+        .args([
+            "--edition",
+            "2021",
+            "--error-format",
+            "short",
+            "--emit",
+            "metadata",
+        ])
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(case)
+        .output()
+        .expect("failed to start rustc");
+    (!output.status.success()).then(|| String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+#[test]
+fn rejected_snippets_fail_with_expected_error_codes() {
+    let out_dir = env::temp_dir().join(format!(
+        "rust_after_cpp-compile-fail-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut cases: Vec<PathBuf> = fs::read_dir(cases_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no case in {}", cases_dir().display());
+
+    let mut failures = vec![];
+    for case in &cases {
+        let name = case.file_name().unwrap().to_string_lossy();
+        let source = fs::read_to_string(case).unwrap();
+        let Some(code) = expected_code(&source) else {
+            failures.push(format!("{name} has no `// expect: E....` line"));
+            continue;
+        };
+        match compile(case, &out_dir) {
+            None => failures.push(format!("{name} compiled, expected {code}")),
+            Some(stderr) if !stderr.contains(&format!("error[{code}]")) => {
+                failures.push(format!("{name} did not report {code}:\n{stderr}"))
+            }
+            Some(_) => {}
+        }
+    }
+    let _ = fs::remove_dir_all(&out_dir);
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
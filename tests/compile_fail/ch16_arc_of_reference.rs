@@ -0,0 +1,14 @@
+//! ch16::mutexes - an `Arc` of a reference does not make the borrowed `s` live as long as the thread
+// expect: E0597
+
+use std::sync::Arc;
+use std::thread;
+
+fn main() {
+    let s = String::from("Hello world");
+    let a = Arc::new(&s);
+    let a2 = Arc::clone(&a);
+    let t = thread::spawn(move || a2.len());
+    let len = t.join().unwrap();
+    println!("{} {}", a, len);
+}
@@ -0,0 +1,45 @@
+//! ch17::dyn_traits - without a `Box<dyn Draw>` annotation the vec's element type is `Box<SelectBox>`
+// expect: E0308
+
+pub trait Draw {
+    fn draw(&self);
+}
+
+pub struct Button {
+    pub width: u32,
+    pub height: u32,
+    pub label: String,
+}
+
+impl Draw for Button {
+    fn draw(&self) {}
+}
+
+struct SelectBox {
+    width: u32,
+    height: u32,
+    options: Vec<String>,
+}
+
+impl Draw for SelectBox {
+    fn draw(&self) {}
+}
+
+fn main() {
+    let components = vec![
+        Box::new(SelectBox {
+            width: 75,
+            height: 10,
+            options: vec![
+                String::from("Yes"),
+                String::from("Maybe"),
+                String::from("No"),
+            ],
+        }),
+        Box::new(Button {
+            width: 50,
+            height: 10,
+            label: String::from("OK"),
+        }),
+    ];
+}
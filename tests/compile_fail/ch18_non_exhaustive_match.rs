@@ -0,0 +1,9 @@
+//! ch18::refutability - the arms of a `match` must cover every value, here `None` is left out
+// expect: E0004
+
+fn main() {
+    let some_option_value: Option<i32> = None;
+    let n = match some_option_value {
+        Some(x) => x,
+    };
+}
@@ -0,0 +1,7 @@
+//! ch18::refutability - `let` needs an irrefutable pattern, and `Some(x)` does not cover `None`
+// expect: E0005
+
+fn main() {
+    let some_option_value: Option<i32> = None;
+    let Some(x) = some_option_value;
+}
@@ -0,0 +1,7 @@
+//! ch19::advanced_types - the size of `str` is not known at compile time, so a `str` cannot be a local
+// expect: E0277
+
+fn main() {
+    let s1: str = "Hello there!";
+    let s2: str = "How's it going?";
+}
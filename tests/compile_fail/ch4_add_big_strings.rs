@@ -0,0 +1,17 @@
+//! ch4::fix_ownership_error - `largest` borrows `dst` immutably while `dst.push` borrows it mutably
+// expect: E0502
+
+fn add_big_strings(dst: &mut Vec<String>, src: &[String]) {
+    let largest: &String = dst.iter().max_by_key(|s| s.len()).unwrap();
+    for s in src {
+        if s.len() > largest.len() {
+            dst.push(s.clone());
+        }
+    }
+}
+
+fn main() {
+    let mut dst: Vec<String> = vec!["a".into(), "bc".into(), "def".into()];
+    let src: Vec<String> = vec!["ghil".into(), "klm".into(), "nopq".into()];
+    add_big_strings(&mut dst, &src);
+}
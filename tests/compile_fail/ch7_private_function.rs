@@ -0,0 +1,15 @@
+//! ch7::modules - `hosting` is public, but `seat_at_table` inside it is still private to `hosting`
+// expect: E0603
+
+mod front_of_house {
+    pub mod hosting {
+        pub fn add_to_waitlist() {}
+
+        fn seat_at_table() {}
+    }
+}
+
+fn main() {
+    front_of_house::hosting::add_to_waitlist();
+    front_of_house::hosting::seat_at_table();
+}
@@ -0,0 +1,56 @@
+//! The C++ shown by `run --compare cpp` must at least compile
+//! * opt-in, as it needs a C++ compiler: `cargo test --test cpp_snippets -- --ignored`
+//! * each snippet is checked with `g++ -std=c++17 -fsyntax-only`, `CXX` selects another compiler
+//! * without a compiler on the machine the check is skipped with a note on stderr
+use rust_after_cpp::registry;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::process::Command;
+
+fn compiler() -> OsString {
+    env::var_os("CXX").unwrap_or_else(|| "g++".into())
+}
+
+#[test]
+#[ignore = "needs g++, run with `--ignored`"]
+fn cpp_snippets_compile() {
+    let cxx = compiler();
+    if Command::new(&cxx).arg("--version").output().is_err() {
+        eprintln!(
+            "skipped: no {} to compile the C++ snippets",
+            cxx.to_string_lossy()
+        );
+        return;
+    }
+
+    let dir = env::temp_dir().join(format!("rust_after_cpp-cpp-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut checked = 0;
+    let mut failures = vec![];
+    for lesson in registry::lessons() {
+        for (i, comparison) in lesson.cpp().iter().enumerate() {
+            let file = dir.join(format!("{}_{i}.cpp", lesson.id().replace("::", "_")));
+            fs::write(&file, comparison.cpp).unwrap();
+            let output = Command::new(&cxx)
+                .args(["-std=c++17", "-fsyntax-only", "-Wall"])
+                .arg(&file)
+                .output()
+                .unwrap();
+            if !output.status.success() {
+                failures.push(format!(
+                    "{} ({}):\n{}",
+                    lesson.id(),
+                    comparison.topic,
+                    String::from_utf8_lossy(&output.stderr)
+                ));
+            }
+            checked += 1;
+        }
+    }
+    let _ = fs::remove_dir_all(&dir);
+
+    assert!(checked > 0, "no lesson has a C++ comparison");
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,38 @@
+//! Every exercise is solvable, and its stub is not a solution already
+//! * the stub, with its checks, must fail `cargo test`
+//! * `exercises/<name>/solution.rs` in place of the stub must pass it
+use rust_after_cpp::exercise::{self, Verdict};
+use rust_after_cpp::registry;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn stubs_fail_and_solutions_pass() {
+    let dir = env::temp_dir().join(format!("rust_after_cpp-exercises-{}", std::process::id()));
+    let lessons = registry::lessons();
+    let exercises = exercise::exercises(&lessons);
+    assert!(!exercises.is_empty(), "no lesson has an exercise");
+
+    let mut failures = vec![];
+    for (_, e) in exercises {
+        let root = exercise::prepare(e, &dir, true).unwrap();
+        let verdict = exercise::check(&root).unwrap();
+        if verdict == Verdict::Passed {
+            failures.push(format!("the stub of {} passes its checks", e.name));
+        }
+
+        let solution = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("exercises")
+            .join(e.name)
+            .join("solution.rs");
+        fs::copy(&solution, root.join("src").join("lib.rs")).unwrap();
+        let verdict = exercise::check(&root).unwrap();
+        if verdict != Verdict::Passed {
+            failures.push(format!("the solution of {} {verdict}", e.name));
+        }
+    }
+    let _ = fs::remove_dir_all(&dir);
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
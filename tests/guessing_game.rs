@@ -0,0 +1,78 @@
+//! The `guess` subcommand end to end: a seeded secret number, guesses from stdin or a script, and exit codes
+use rust_after_cpp::ch2::guessing_game::Rng;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn guess(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_after_cpp"))
+        .arg("guess")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn secret_number(seed: u64) -> u32 {
+    Rng::new(seed).gen_range(1..=100)
+}
+
+#[test]
+fn wins_with_guesses_typed_on_stdin() {
+    let secret = secret_number(7);
+    let output = guess(&["--seed", "7"], &format!("abc\n0\n101\n{secret}\n"));
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("Guess the number!\nPlease input your guess.\n`abc` is not a number\n")
+    );
+    assert!(stdout.contains("You guessed: 0\nToo small!\n"));
+    assert!(stdout.contains("You guessed: 101\nToo big!\n"));
+    assert!(stdout.ends_with(&format!("You guessed: {secret}\nYou win!\n")));
+}
+
+#[test]
+fn plays_a_script_and_echoes_it() {
+    let secret = secret_number(123);
+    let script = std::env::temp_dir().join(format!("guesses-{}.txt", std::process::id()));
+    fs::write(&script, format!("{secret}\n")).unwrap();
+    let output = guess(&["--seed", "123", "--script", script.to_str().unwrap()], "");
+    fs::remove_file(&script).unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("Guess the number!\nPlease input your guess.\n{secret}\nYou guessed: {secret}\nYou win!\n")
+    );
+}
+
+#[test]
+fn fails_when_the_guesses_run_out() {
+    let secret = secret_number(7);
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let output = guess(&["--seed", "7"], &format!("{wrong}\n"));
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with(&format!(
+        "No more guesses, the secret number was {secret}\n"
+    )));
+}
+
+#[test]
+fn the_same_seed_picks_the_same_number() {
+    let secret = secret_number(99);
+    for _ in 0..2 {
+        assert!(guess(&["--seed", "99"], &format!("{secret}\n"))
+            .status
+            .success());
+    }
+    assert_eq!(guess(&["--seed", "x"], "").status.code(), Some(2));
+}
@@ -0,0 +1,65 @@
+//! The `minigrep` subcommand as a user runs it: arguments, `IGNORE_CASE`, stdin and exit codes
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn minigrep(args: &[&str], ignore_case: bool, stdin: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_rust_after_cpp"));
+    command
+        .arg("minigrep")
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .env_remove("IGNORE_CASE");
+    if ignore_case {
+        command.env("IGNORE_CASE", "1");
+    }
+    let mut child = command.spawn().unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn searches_files_with_their_names() {
+    let poem = Path::new("src").join("ch12").join("poem.txt");
+    let poem = poem.to_str().unwrap();
+    let output = minigrep(&["-n", "frog", poem, "-"], false, "a frog\n");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!("{poem}:7:How public, like a frog\n-:1:a frog\n")
+    );
+}
+
+#[test]
+fn ignore_case_comes_from_the_environment() {
+    let output = minigrep(&["rust"], false, "Rust:\nTrust me.\n");
+    assert_eq!(stdout(&output), "Trust me.\n");
+    let output = minigrep(&["rust"], true, "Rust:\nTrust me.\n");
+    assert_eq!(stdout(&output), "Rust:\nTrust me.\n");
+}
+
+#[test]
+fn exit_codes_follow_grep() {
+    assert_eq!(minigrep(&["x"], false, "x\n").status.code(), Some(0));
+    assert_eq!(minigrep(&["y"], false, "x\n").status.code(), Some(1));
+
+    let output = minigrep(&["x", "no/such/file.txt"], false, "");
+    assert_eq!(output.status.code(), Some(2));
+    // - errors go to stderr, leaving stdout for the matches
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).starts_with("Application error: no/such/file.txt")
+    );
+}
@@ -0,0 +1,119 @@
+//! Golden-output tests: every deterministic lesson's output is compared with `tests/snapshots/<chapter>/<note>.txt`
+//! * run `UPDATE_SNAPSHOTS=1 cargo test --test snapshots` to regenerate the files after an intended change
+//! * the thread demos replay the schedules in `tests/snapshots/schedules.txt`, fast-forwarding their sleeps
+//!     * after changing their threads, record new schedules with `cargo run -- run ch16 --all --record tests/snapshots/schedules.txt`
+use rust_after_cpp::lesson::Lesson;
+use rust_after_cpp::registry;
+use rust_after_cpp::runner::{self, Outcome};
+use rust_after_cpp::schedule::{self, FastForward, Mode, Scheduler, Trace};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Lessons whose output changes from run to run, so they have no snapshot
+/// * `ch8::hashmaps` prints `HashMap`s whose iteration order is random
+/// * `ch9::results` depends on a `hello.txt` in the working directory, and panics on purpose when it opens a missing file
+/// * `ch20::thread_pool` logs which worker got each job, which the OS decides
+const NONDETERMINISTIC: [&str; 3] = ["ch8::hashmaps", "ch9::results", "ch20::thread_pool"];
+
+/// The recorded schedules of the lessons whose threads interleave, e.g. `ch16::threads`
+fn schedules() -> Vec<(String, Trace)> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/schedules.txt");
+    schedule::parse_traces(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn snapshot_path(lesson: &dyn Lesson) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests");
+    path.push("snapshots");
+    path.push(format!("{}.txt", lesson.id().replace("::", "/")));
+    path
+}
+
+/// The lesson's output, followed by the message of a panic if it panics
+///     * a lesson with a schedule in `schedules` runs its threads in that order
+fn output_of(lesson: &dyn Lesson, schedules: &[(String, Trace)]) -> String {
+    let mode = match schedules.iter().find(|(id, _)| id == lesson.id()) {
+        Some((_, trace)) => Mode::Replay(trace.clone()),
+        None => Mode::Free,
+    };
+    let scheduler = Scheduler::new(mode, Arc::new(FastForward::new()));
+    let mut out = Vec::new();
+    let outcome = scheduler
+        .run(|| runner::run_isolated(lesson, &mut out))
+        .unwrap();
+    assert_eq!(scheduler.divergence(), None, "replaying {}", lesson.id());
+    let mut output = String::from_utf8(out).unwrap();
+    if let Outcome::Panicked { panic, .. } = outcome {
+        output.push_str(&format!("[panicked: {}]\n", panic.message));
+    }
+    output
+}
+
+/// Describe the first line where `actual` departs from `expected`
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut n = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => n += 1,
+            (e, a) => return format!("line {n}: expected {e:?}, got {a:?}"),
+        }
+    }
+}
+
+#[test]
+fn lesson_outputs_match_snapshots() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let schedules = schedules();
+    let mut failures = vec![];
+
+    for lesson in registry::lessons() {
+        if NONDETERMINISTIC.contains(&lesson.id()) {
+            continue;
+        }
+        let path = snapshot_path(lesson.as_ref());
+        let actual = output_of(lesson.as_ref(), &schedules);
+
+        if update {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{} differs from {}, {}",
+                lesson.id(),
+                path.display(),
+                first_difference(&expected, &actual)
+            )),
+            Err(e) => failures.push(format!(
+                "{} has no snapshot at {}: {e}",
+                lesson.id(),
+                path.display()
+            )),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "\n{}\n\nRun with UPDATE_SNAPSHOTS=1 to accept the new output",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn nondeterministic_lessons_exist() {
+    let lessons = registry::lessons();
+    for id in NONDETERMINISTIC {
+        // - a chapter left out with `--no-default-features` has nothing to check
+        let chapter = &id[..id.find("::").unwrap() + 2];
+        let chapter_enabled = lessons.iter().any(|l| l.id().starts_with(chapter));
+        if chapter_enabled {
+            assert!(registry::find(id).is_some(), "no lesson {id}");
+        }
+    }
+}
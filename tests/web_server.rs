@@ -0,0 +1,90 @@
+//! The chapter 20 server on an ephemeral localhost port, spoken to over `TcpStream` as a browser would
+use rust_after_cpp::ch20::web_server::{Server, HELLO, NOT_FOUND};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Start a server answering `limit` connections, `/sleep` taking `sleep`
+fn start(limit: usize, sleep: Duration) -> (SocketAddr, JoinHandle<()>) {
+    let server = Server::bind("127.0.0.1:0", 4).unwrap().with_sleep(sleep);
+    let addr = server.local_addr().unwrap();
+    let serving = thread::spawn(move || server.serve(Some(limit)).unwrap());
+    (addr, serving)
+}
+
+/// The status line and the body of the response to `GET path`
+fn get(addr: SocketAddr, path: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: {addr}\r\nUser-Agent: test\r\n\r\n"
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap().to_string();
+    let length: usize = lines
+        .find_map(|line| line.strip_prefix("Content-Length: "))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(length, body.len());
+    (status_line, body.to_string())
+}
+
+#[test]
+fn serves_hello_and_a_404_page() {
+    let (addr, serving) = start(2, Duration::ZERO);
+    assert_eq!(
+        get(addr, "/"),
+        ("HTTP/1.1 200 OK".to_string(), HELLO.to_string())
+    );
+    assert_eq!(
+        get(addr, "/missing"),
+        ("HTTP/1.1 404 NOT FOUND".to_string(), NOT_FOUND.to_string())
+    );
+    // - `serve` returns after its two connections
+    serving.join().unwrap();
+}
+
+#[test]
+fn a_slow_request_does_not_hold_up_the_others() {
+    let sleep = Duration::from_millis(500);
+    let (addr, serving) = start(2, sleep);
+    let (tx, rx) = mpsc::channel();
+    let slow = thread::spawn({
+        let tx = tx.clone();
+        move || {
+            let response = get(addr, "/sleep");
+            tx.send("/sleep").unwrap();
+            response
+        }
+    });
+    // - give the slow request time to reach a worker first
+    thread::sleep(Duration::from_millis(100));
+    let start = Instant::now();
+    assert_eq!(get(addr, "/").0, "HTTP/1.1 200 OK");
+    assert!(start.elapsed() < sleep);
+    tx.send("/").unwrap();
+    assert_eq!(slow.join().unwrap().1, HELLO);
+    assert_eq!(rx.iter().take(2).collect::<Vec<_>>(), ["/", "/sleep"]);
+    serving.join().unwrap();
+}
+
+#[test]
+fn shutting_down_finishes_the_requests_in_progress() {
+    let sleep = Duration::from_millis(200);
+    let (addr, serving) = start(3, sleep);
+    let clients: Vec<_> = (0..3)
+        .map(|_| thread::spawn(move || get(addr, "/sleep")))
+        .collect();
+    // - the last connection makes `serve` return, dropping the pool joins the workers still sleeping
+    serving.join().unwrap();
+    for client in clients {
+        assert_eq!(client.join().unwrap().0, "HTTP/1.1 200 OK");
+    }
+}
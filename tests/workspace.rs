@@ -0,0 +1,56 @@
+//! The workspace members called from outside: `add_one` and `restaurant` through their public paths and re-exports
+use restaurant::back_of_house::{Appetizer, Breakfast};
+use restaurant::hosting;
+use rust_after_cpp::ch14::workspaces;
+use std::process::Command;
+
+#[test]
+fn the_re_export_calls_the_member_crate() {
+    assert_eq!(add_one::add_one(41), 42);
+    assert_eq!(workspaces::add_one(41), add_one::add_one(41));
+    assert_eq!(
+        std::any::type_name_of_val(&workspaces::add_one),
+        "add_one::add_one"
+    );
+}
+
+#[test]
+fn the_restaurant_is_reached_through_its_public_api() {
+    let mut waitlist = hosting::Waitlist::new();
+    hosting::add_to_waitlist(&mut waitlist, "Ferris");
+    assert_eq!(
+        restaurant::eat_at_restaurant(&mut waitlist, "Corro"),
+        "Ferris would like Wheat toast please, with peaches and a Salad"
+    );
+    assert_eq!(
+        hosting::seat_at_table(&mut waitlist).as_deref(),
+        Some("Corro")
+    );
+    assert_eq!(
+        restaurant::back_of_house::fix_incorrect_order("eggs"),
+        "serving eggs, cooked again"
+    );
+
+    let mut meal = Breakfast::summer("Rye");
+    meal.toast.push_str(" and butter");
+    assert_eq!(meal.toast, "Rye and butter");
+    assert_eq!(meal.seasonal_fruit(), "peaches");
+    assert_ne!(Appetizer::Soup, Appetizer::Salad);
+}
+
+#[test]
+fn the_binary_runs_the_workspace_lessons() {
+    // - the run is recorded in a scratch file, not in the user's progress
+    let progress = std::env::temp_dir().join(format!("progress-{}.tsv", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_after_cpp"))
+        .args(["run", "ch14.3"])
+        .env("RUST_AFTER_CPP_PROGRESS", &progress)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Hello, world! 10 plus one is 11!\n"));
+    assert!(stdout.contains("add_one::add_one is add_one::add_one\n"));
+    assert!(progress.exists());
+    std::fs::remove_file(&progress).unwrap();
+}